@@ -0,0 +1,229 @@
+use rust_i18n::t;
+use std::collections::BTreeMap;
+
+use crate::output::{DependencyInfo, DependencyKinds, DependencyStatus};
+
+/// 已提交的输出文件与当前依赖之间的一处差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// 新增的依赖，文件中还没有
+    Added {
+        name: String,
+        kinds: DependencyKinds,
+    },
+    /// 已移除的依赖，文件中仍然存在
+    Removed {
+        name: String,
+        kinds: DependencyKinds,
+    },
+    /// 依赖类型不同，例如从开发依赖变为普通依赖
+    KindsChanged {
+        name: String,
+        committed: DependencyKinds,
+        current: DependencyKinds,
+    },
+    /// 描述不同，只在 `--check-descriptions` 时比较
+    DescriptionChanged {
+        name: String,
+        committed: Option<String>,
+        current: Option<String>,
+    },
+}
+
+impl Difference {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Added { name, .. }
+            | Self::Removed { name, .. }
+            | Self::KindsChanged { name, .. }
+            | Self::DescriptionChanged { name, .. } => name,
+        }
+    }
+}
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Added { name, kinds } => {
+                t!("check.added", name = name, kinds = kinds.to_string())
+            }
+            Self::Removed { name, kinds } => {
+                t!("check.removed", name = name, kinds = kinds.to_string())
+            }
+            Self::KindsChanged {
+                name,
+                committed,
+                current,
+            } => t!(
+                "check.kinds_changed",
+                name = name,
+                committed = committed.to_string(),
+                current = current.to_string()
+            ),
+            Self::DescriptionChanged {
+                name,
+                committed,
+                current,
+            } => t!(
+                "check.description_changed",
+                name = name,
+                committed = committed.as_deref().unwrap_or_default(),
+                current = current.as_deref().unwrap_or_default()
+            ),
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+/// 输出中的描述去掉了换行，CSV 中的逗号替换为 `;`，没有描述时为 `unknown`，比较前统一处理
+fn normalize_description(description: Option<&str>) -> Option<String> {
+    let description = description?
+        .replace('\n', " ")
+        .replace(',', ";")
+        .trim()
+        .to_string();
+    (!description.is_empty() && description != "unknown").then_some(description)
+}
+
+/// 比较当前的依赖与从已提交的文件中解析得到的依赖，差异按 crate 名称排序
+///
+/// `--update` 标记为已移除的依赖视为不在文件中
+pub fn compare(
+    current: &[DependencyInfo],
+    committed: &[DependencyInfo],
+    descriptions: bool,
+) -> Vec<Difference> {
+    let by_name = |deps: &[DependencyInfo]| {
+        deps.iter()
+            .filter(|dep| dep.status != DependencyStatus::Removed)
+            .map(|dep| (dep.name.clone(), dep.clone()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let current = by_name(current);
+    let mut committed = by_name(committed);
+
+    let mut differences = vec![];
+    for (name, dep) in current {
+        let Some(committed) = committed.remove(&name) else {
+            differences.push(Difference::Added {
+                name,
+                kinds: dep.dependency_kind,
+            });
+            continue;
+        };
+
+        if committed.dependency_kind != dep.dependency_kind {
+            differences.push(Difference::KindsChanged {
+                name: name.clone(),
+                committed: committed.dependency_kind,
+                current: dep.dependency_kind,
+            });
+        }
+
+        let committed = normalize_description(committed.description.as_deref());
+        let current = normalize_description(dep.description.as_deref());
+        if descriptions && committed != current {
+            differences.push(Difference::DescriptionChanged {
+                name,
+                committed,
+                current,
+            });
+        }
+    }
+    differences.extend(committed.into_values().map(|dep| Difference::Removed {
+        name: dep.name,
+        kinds: dep.dependency_kind,
+    }));
+
+    differences.sort_by(|a, b| a.name().cmp(b.name()));
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{DependencyKind, Formatter, OutputFormat};
+    use pretty_assertions::assert_eq;
+
+    fn dep(name: &str, kinds: &[DependencyKind], description: &str) -> DependencyInfo {
+        DependencyInfo {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            dependency_kind: kinds.iter().copied().collect(),
+            crate_url: Some(format!("https://crates.io/crates/{}", name)),
+            source_type: "crates.io".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compare() -> anyhow::Result<()> {
+        use DependencyKind::*;
+
+        let committed = vec![
+            dep("serde", &[Normal], "Serialization, framework"),
+            dep("tokio", &[Development], "Runtime"),
+            dep("failure", &[Normal], "Errors"),
+        ];
+        let current = vec![
+            dep("serde", &[Normal], "Serialization, framework\n"),
+            dep("tokio", &[Normal, Development], "Async runtime"),
+            dep("anyhow", &[Normal], "Errors"),
+        ];
+
+        for format in [
+            OutputFormat::MarkdownTable,
+            OutputFormat::MarkdownList,
+            OutputFormat::Csv,
+            OutputFormat::Json,
+            OutputFormat::Yaml,
+            OutputFormat::Toml,
+        ] {
+            let formatter = <dyn Formatter>::new(format)?;
+            let parsed = formatter.parse(&formatter.format(&committed)?)?;
+
+            assert!(compare(&committed, &parsed, true).is_empty(), "{}", format);
+            assert_eq!(
+                compare(&current, &parsed, false),
+                vec![
+                    Difference::Added {
+                        name: "anyhow".to_string(),
+                        kinds: Normal.into(),
+                    },
+                    Difference::Removed {
+                        name: "failure".to_string(),
+                        kinds: Normal.into(),
+                    },
+                    Difference::KindsChanged {
+                        name: "tokio".to_string(),
+                        committed: Development.into(),
+                        current: [Normal, Development].into_iter().collect(),
+                    },
+                ],
+                "{}",
+                format
+            );
+
+            let descriptions = compare(&current, &parsed, true);
+            assert_eq!(descriptions.len(), 4, "{}", format);
+            assert_eq!(
+                descriptions[3],
+                Difference::DescriptionChanged {
+                    name: "tokio".to_string(),
+                    committed: Some("Runtime".to_string()),
+                    current: Some("Async runtime".to_string()),
+                }
+            );
+        }
+
+        // `--update` 保留的已移除依赖不算差异
+        let mut committed = current.clone();
+        committed.push(DependencyInfo {
+            status: DependencyStatus::Removed,
+            ..dep("failure", &[Normal], "Errors")
+        });
+        assert!(compare(&current, &committed, true).is_empty());
+        Ok(())
+    }
+}
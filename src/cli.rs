@@ -5,7 +5,10 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use tracing::instrument;
 
-use crate::output::OutputFormat;
+use crate::{
+    output::OutputFormat,
+    sources::{CratesioClient, GitHubClient},
+};
 
 // Arg::new("language")
 //     .short('l')
@@ -97,12 +100,12 @@ impl clap::builder::TypedValueParser for OutputFormatParser {
         value: &std::ffi::OsStr,
     ) -> Result<Self::Value, clap::Error> {
         let input = value.to_string_lossy().to_lowercase();
-        Ok(OutputFormat::from_str(&input).map_err(|_| {
+        OutputFormat::from_str(&input).map_err(|_| {
             clap::Error::raw(
                 clap::error::ErrorKind::InvalidValue,
                 format!("{}", t!("cli.invalid_output_format", format = input)),
             )
-        })?)
+        })
     }
 }
 
@@ -130,7 +133,7 @@ fn build_global_args() -> [Arg; 2] {
     ]
 }
 
-fn build_thanku_args() -> [Arg; 8] {
+fn build_thanku_args() -> [Arg; 36] {
     [
         Arg::new("input")
             .short('i')
@@ -222,10 +225,241 @@ fn build_thanku_args() -> [Arg; 8] {
             .group("thanku")
             .value_parser(clap::value_parser!(u32))
             .default_value("3"),
+        Arg::new("depth")
+            .short('d')
+            .long("depth")
+            .help(format!("{}", t!("cli.depth_help")))
+            .display_order(8)
+            // .global(true)
+            .group("thanku")
+            .value_name("N|all")
+            .default_value("1"),
+        Arg::new("star")
+            .long("star")
+            .help(format!("{}", t!("cli.star_help")))
+            .display_order(9)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("dry-run")
+            .long("dry-run")
+            .help(format!("{}", t!("cli.dry_run_help")))
+            .display_order(10)
+            // .global(true)
+            .group("thanku")
+            .requires("star")
+            .action(ArgAction::SetTrue),
+        Arg::new("star-exclude")
+            .long("star-exclude")
+            .help(format!("{}", t!("cli.star_exclude_help")))
+            .display_order(11)
+            // .global(true)
+            .group("thanku")
+            .value_name("CRATE|OWNER/REPO")
+            .value_delimiter(',')
+            .requires("star")
+            .action(ArgAction::Append),
+        Arg::new("offline")
+            .long("offline")
+            .help(format!("{}", t!("cli.offline_help")))
+            .display_order(12)
+            // .global(true)
+            .group("thanku")
+            .conflicts_with_all(["refresh", "star"])
+            .action(ArgAction::SetTrue),
+        Arg::new("refresh")
+            .long("refresh")
+            .help(format!("{}", t!("cli.refresh_help")))
+            .display_order(13)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("cache-ttl")
+            .long("cache-ttl")
+            .help(format!("{}", t!("cli.cache_ttl_help")))
+            .display_order(14)
+            // .global(true)
+            .group("thanku")
+            .value_name("DURATION")
+            .default_value("24h"),
+        Arg::new("crates-io-api")
+            .long("crates-io-api")
+            .help(format!("{}", t!("cli.crates_io_api_help")))
+            .display_order(15)
+            // .global(true)
+            .group("thanku")
+            .env("CARGO_THANKU_CRATES_IO_API")
+            .value_name("URL")
+            .default_value(CratesioClient::DEFAULT_API_URL),
+        Arg::new("github-api")
+            .long("github-api")
+            .help(format!("{}", t!("cli.github_api_help")))
+            .display_order(16)
+            // .global(true)
+            .group("thanku")
+            .env("CARGO_THANKU_GITHUB_API")
+            .value_name("URL")
+            .default_value(GitHubClient::DEFAULT_API_URL),
+        Arg::new("package")
+            .short('p')
+            .long("package")
+            .help(format!("{}", t!("cli.package_help")))
+            .display_order(17)
+            // .global(true)
+            .group("thanku")
+            .value_name("SPEC")
+            .value_delimiter(',')
+            .action(ArgAction::Append),
+        Arg::new("exclude")
+            .long("exclude")
+            .help(format!("{}", t!("cli.exclude_help")))
+            .display_order(18)
+            // .global(true)
+            .group("thanku")
+            .value_name("SPEC")
+            .value_delimiter(',')
+            .action(ArgAction::Append),
+        Arg::new("per-package")
+            .long("per-package")
+            .help(format!("{}", t!("cli.per_package_help")))
+            .display_order(19)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("fail-on")
+            .long("fail-on")
+            .help(format!("{}", t!("cli.fail_on_help")))
+            .display_order(20)
+            // .global(true)
+            .group("thanku")
+            .value_name("STATUS")
+            .value_delimiter(',')
+            .value_parser(["yanked", "deprecated", "archived"])
+            .action(ArgAction::Append),
+        Arg::new("gitlab-token")
+            .long("gitlab-token")
+            // .global(true)
+            .group("thanku")
+            .env("GITLAB_TOKEN")
+            .help(format!("{}", t!("cli.gitlab_token_help")))
+            .display_order(21)
+            .action(ArgAction::Set),
+        Arg::new("gitlab-host")
+            .long("gitlab-host")
+            // .global(true)
+            .group("thanku")
+            .env("GITLAB_HOST")
+            .help(format!("{}", t!("cli.gitlab_host_help")))
+            .display_order(21)
+            .value_name("HOST")
+            .action(ArgAction::Set),
+        Arg::new("gitea-token")
+            .long("gitea-token")
+            // .global(true)
+            .group("thanku")
+            .env("GITEA_TOKEN")
+            .help(format!("{}", t!("cli.gitea_token_help")))
+            .display_order(22)
+            .action(ArgAction::Set),
+        Arg::new("gitea-host")
+            .long("gitea-host")
+            // .global(true)
+            .group("thanku")
+            .env("GITEA_HOST")
+            .help(format!("{}", t!("cli.gitea_host_help")))
+            .display_order(22)
+            .value_name("HOST")
+            .action(ArgAction::Set),
+        Arg::new("bitbucket-token")
+            .long("bitbucket-token")
+            // .global(true)
+            .group("thanku")
+            .env("BITBUCKET_TOKEN")
+            .help(format!("{}", t!("cli.bitbucket_token_help")))
+            .display_order(23)
+            .action(ArgAction::Set),
+        Arg::new("sponsorable-only")
+            .long("sponsorable-only")
+            .help(format!("{}", t!("cli.sponsorable_only_help")))
+            .display_order(24)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("maintainers")
+            .long("maintainers")
+            .help(format!("{}", t!("cli.maintainers_help")))
+            .display_order(25)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("contributors")
+            .long("contributors")
+            .value_name("N")
+            .help(format!("{}", t!("cli.contributors_help")))
+            .display_order(26)
+            // .global(true)
+            .group("thanku")
+            .value_parser(clap::value_parser!(usize)),
+        Arg::new("local-metadata")
+            .long("local-metadata")
+            .help(format!("{}", t!("cli.local_metadata_help")))
+            .display_order(27)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("append")
+            .long("append")
+            .help(format!("{}", t!("cli.append_help")))
+            .display_order(28)
+            // .global(true)
+            .group("thanku")
+            .action(ArgAction::SetTrue),
+        Arg::new("overwrite")
+            .long("overwrite")
+            .help(format!("{}", t!("cli.overwrite_help")))
+            .display_order(29)
+            // .global(true)
+            .group("thanku")
+            .conflicts_with("append")
+            .action(ArgAction::SetTrue),
+        Arg::new("update")
+            .long("update")
+            .help(format!("{}", t!("cli.update_help")))
+            .display_order(30)
+            // .global(true)
+            .group("thanku")
+            .conflicts_with_all(["append", "overwrite"])
+            .action(ArgAction::SetTrue),
+        Arg::new("inject")
+            .long("inject")
+            .help(format!("{}", t!("cli.inject_help")))
+            .display_order(31)
+            // .global(true)
+            .group("thanku")
+            .value_name("FILE")
+            .value_hint(clap::ValueHint::FilePath)
+            .value_parser(clap::value_parser!(PathBuf))
+            .conflicts_with_all(["output", "append", "overwrite", "update", "per-package"]),
+        Arg::new("check")
+            .long("check")
+            .help(format!("{}", t!("cli.check_help")))
+            .display_order(32)
+            // .global(true)
+            .group("thanku")
+            .conflicts_with_all(["star", "append", "overwrite", "update"])
+            .action(ArgAction::SetTrue),
+        Arg::new("check-descriptions")
+            .long("check-descriptions")
+            .help(format!("{}", t!("cli.check_descriptions_help")))
+            .display_order(33)
+            // .global(true)
+            .group("thanku")
+            .requires("check")
+            .action(ArgAction::SetTrue),
     ]
 }
 
-fn build_convert_args() -> [Arg; 2] {
+fn build_convert_args() -> [Arg; 3] {
     [
         Arg::new("input")
             .short('i')
@@ -256,13 +490,113 @@ fn build_convert_args() -> [Arg; 2] {
                 "markdown-list",
                 "markdown-table",
             ]),
+        Arg::new("depth")
+            .short('d')
+            .long("depth")
+            .group("convert")
+            .help(format!("{}", t!("cli.convert_depth_help")))
+            .display_order(2)
+            .value_name("N|all"),
     ]
 }
 
+/// 只需要 cargo metadata 的子命令复用 thanku 的参数，`visible` 之外的参数被隐藏
+///
+/// 这些子命令默认输出到标准输出
+fn reuse_thanku_args(thanku_args: &[Arg], visible: &[&str]) -> Vec<Arg> {
+    thanku_args
+        .iter()
+        .cloned()
+        .map(|arg| match arg.get_id().as_str() {
+            "output" => arg.default_value(None),
+            id if visible.contains(&id) => arg,
+            _ => arg.hide(true),
+        })
+        .collect()
+}
+
+/// check-licenses 的参数，违规的依赖通过格式化器输出
+fn build_check_licenses_args(thanku_args: &[Arg]) -> Vec<Arg> {
+    let mut args = reuse_thanku_args(
+        thanku_args,
+        &[
+            "input",
+            "format",
+            "source",
+            "no-relative-libs",
+            "depth",
+            "offline",
+            "package",
+            "exclude",
+            "per-package",
+        ],
+    );
+
+    args.push(
+        Arg::new("policy")
+            .long("policy")
+            .aliases(["config"])
+            .help(format!("{}", t!("cli.policy_help")))
+            .display_order(34)
+            .value_hint(clap::ValueHint::FilePath)
+            .value_parser(clap::value_parser!(PathBuf)),
+    );
+    args
+}
+
+/// notice 的参数，NOTICE 文档是纯文本，与输出格式无关
+fn build_notice_args(thanku_args: &[Arg]) -> Vec<Arg> {
+    reuse_thanku_args(
+        thanku_args,
+        &[
+            "input",
+            "no-relative-libs",
+            "depth",
+            "offline",
+            "package",
+            "exclude",
+        ],
+    )
+}
+
+/// outdated 的参数，比较结果通过格式化器输出
+fn build_outdated_args(thanku_args: &[Arg]) -> Vec<Arg> {
+    let mut args = reuse_thanku_args(
+        thanku_args,
+        &[
+            "input",
+            "format",
+            "no-relative-libs",
+            "concurrent",
+            "depth",
+            "offline",
+            "refresh",
+            "cache-ttl",
+            "crates-io-api",
+            "package",
+            "exclude",
+            "per-package",
+        ],
+    );
+
+    args.push(
+        Arg::new("all")
+            .long("all")
+            .short('a')
+            .help(format!("{}", t!("cli.outdated_all_help")))
+            .display_order(34)
+            .action(ArgAction::SetTrue),
+    );
+    args
+}
+
 pub fn build_cli() -> Command {
     let global_args = build_global_args();
     let thanku_args = build_thanku_args();
     let convert_args = build_convert_args();
+    let check_licenses_args = build_check_licenses_args(&thanku_args);
+    let notice_args = build_notice_args(&thanku_args);
+    let outdated_args = build_outdated_args(&thanku_args);
 
     let thanku_args_ids = thanku_args
         .iter()
@@ -299,6 +633,19 @@ pub fn build_cli() -> Command {
                 .aliases(["cvt", "conv", "convt"])
                 .about(format!("{}", t!("cli.convert_help")))
                 .args(&convert_args),
+            Command::new("check-licenses")
+                .aliases(["licenses", "check-license"])
+                .group(&thanku_group)
+                .about(format!("{}", t!("cli.check_licenses_about")))
+                .args(&check_licenses_args),
+            Command::new("notice")
+                .group(&thanku_group)
+                .about(format!("{}", t!("cli.notice_about")))
+                .args(&notice_args),
+            Command::new("outdated")
+                .group(&thanku_group)
+                .about(format!("{}", t!("cli.outdated_about")))
+                .args(&outdated_args),
             Command::new("completions")
                 .aliases(["comp", "completion"])
                 .about(format!("{}", t!("cli.completions_about")))
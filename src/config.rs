@@ -1,25 +1,31 @@
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::Duration;
 use tracing::instrument;
 
 use crate::errors::AppError;
-use crate::output::OutputFormat;
-
-#[derive(Debug, Clone)]
+use crate::output::{DependencyStatus, OutputFormat};
+use crate::sources::{
+    CratesioClient, GitHubClient,
+    cache::{Cache, CacheMode},
+};
+
+/// 输出中链接的来源偏好
+///
+/// - `GitHub`: 优先使用仓库链接（默认）
+/// - `CratesIo`: 所有链接都指向 crates.io
+/// - `LinkEmpty`: 不输出链接
+/// - `Other`: 使用主页或文档地址
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LinkSource {
+    #[default]
     GitHub,
     CratesIo,
     LinkEmpty,
     Other,
 }
 
-impl Default for LinkSource {
-    fn default() -> Self {
-        Self::GitHub
-    }
-}
-
 impl std::str::FromStr for LinkSource {
     type Err = AppError;
 
@@ -34,6 +40,114 @@ impl std::str::FromStr for LinkSource {
     }
 }
 
+/// 依赖遍历深度
+///
+/// - `Level(1)` 只包含直接依赖（默认）
+/// - `Level(n)` 包含解析图中距离工作区成员不超过 n 层的依赖
+/// - `All` 包含完整的传递依赖树
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthLimit {
+    Level(u32),
+    All,
+}
+
+impl Default for DepthLimit {
+    fn default() -> Self {
+        Self::Level(1)
+    }
+}
+
+impl DepthLimit {
+    /// 是否只需要直接依赖（无需解析完整依赖图）
+    pub fn is_direct(&self) -> bool {
+        matches!(self, Self::Level(1))
+    }
+
+    /// 给定深度的依赖是否在限制范围内
+    ///
+    /// 深度为 0 表示未知（例如从旧版本输出文件中解析得到），总是保留
+    pub fn allows(&self, depth: u32) -> bool {
+        match self {
+            Self::Level(max) => depth <= *max,
+            Self::All => true,
+        }
+    }
+}
+
+impl std::str::FromStr for DepthLimit {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "all" | "*" => Ok(Self::All),
+            n => match n.parse::<u32>() {
+                Ok(level) if level > 0 => Ok(Self::Level(level)),
+                _ => Err(AppError::InvalidDepth(s.to_string())),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for DepthLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Level(level) => write!(f, "{}", level),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
+/// 工作区成员的选择，与 cargo 的 `--package`/`--exclude` 含义一致
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageSelection {
+    /// 只包含这些成员，为空表示包含所有成员
+    pub packages: Vec<String>,
+    /// 排除这些成员
+    pub exclude: Vec<String>,
+}
+
+impl PackageSelection {
+    /// 给定名称的工作区成员是否被选中
+    pub fn selects(&self, name: &str) -> bool {
+        (self.packages.is_empty() || self.packages.iter().any(|p| p == name))
+            && !self.exclude.iter().any(|e| e == name)
+    }
+}
+
+/// 解析缓存有效期，例如 `3600`、`90s`、`30m`、`12h`、`7d`
+pub fn parse_ttl(s: &str) -> Result<Duration, AppError> {
+    let s = s.trim();
+    let (value, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => s.split_at(index),
+        None => (s, "s"),
+    };
+
+    let value = value
+        .parse::<u64>()
+        .map_err(|_| AppError::InvalidCacheTtl(s.to_string()))?;
+    let seconds = match unit {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 60 * 60,
+        "d" => value * 60 * 60 * 24,
+        _ => return Err(AppError::InvalidCacheTtl(s.to_string())),
+    };
+
+    Ok(Duration::from_secs(seconds))
+}
+
+/// 输出文件已存在时的写入方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteMode {
+    /// 追加到文件末尾
+    #[default]
+    Append,
+    /// 覆盖已有的内容
+    Overwrite,
+    /// 解析已有的内容并与新的结果合并，见 [`crate::output::merge_update`]
+    Update,
+}
+
 /// 输出目标枚举
 pub enum OutputWriter {
     Stdout(std::io::Stdout),
@@ -64,8 +178,45 @@ pub struct Config {
     pub format: OutputFormat,
     pub link_source: LinkSource,
     pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+    /// GitLab 令牌所属的实例，默认为 gitlab.com
+    pub gitlab_host: Option<String>,
+    /// Gitea/Forgejo（例如 Codeberg）的令牌
+    pub gitea_token: Option<String>,
+    /// Gitea 令牌所属的实例，默认为 codeberg.org
+    pub gitea_host: Option<String>,
+    pub bitbucket_token: Option<String>,
     // pub crates_token: Option<String>,
     pub no_relative_libs: bool,
+    pub depth: DepthLimit,
+    pub packages: PackageSelection,
+    pub per_package: bool,
+    /// 输出文件已存在时的写入方式
+    pub write_mode: WriteMode,
+    /// 将输出写入该文件的 `thanku:start`/`thanku:end` 标记之间，代替输出文件
+    pub inject: Option<PathBuf>,
+    /// 只检查输出文件（或注入的内容）是否与当前的依赖一致，不修改文件
+    pub check: bool,
+    /// 检查时同时比较依赖的描述
+    pub check_descriptions: bool,
+    pub star: bool,
+    pub dry_run: bool,
+    pub star_exclude: Vec<String>,
+    /// 出现这些状态的依赖时以非零状态码退出
+    pub fail_on: Vec<DependencyStatus>,
+    /// 只输出存在赞助链接的依赖
+    pub sponsorable_only: bool,
+    /// 获取 crate 的所有者，输出维护者列与致谢名单
+    pub maintainers: bool,
+    /// 同时获取仓库贡献最多的前 N 位贡献者，0 表示不获取
+    pub contributors: usize,
+    /// 只使用依赖包自身 Cargo.toml 中的信息，不查询 crates.io 与替代注册表，网络只用于获取统计信息
+    pub local_metadata: bool,
+    pub offline: bool,
+    pub refresh: bool,
+    pub cache_ttl: Duration,
+    pub crates_io_api: String,
+    pub github_api: String,
     pub language: String,
     pub verbose: bool,
     pub max_concurrent_requests: usize,
@@ -80,8 +231,33 @@ impl Default for Config {
             format: OutputFormat::default(),
             link_source: LinkSource::default(),
             github_token: None,
+            gitlab_token: None,
+            gitlab_host: None,
+            gitea_token: None,
+            gitea_host: None,
+            bitbucket_token: None,
             // crates_token: None,
             no_relative_libs: false,
+            depth: DepthLimit::default(),
+            packages: PackageSelection::default(),
+            per_package: false,
+            write_mode: WriteMode::default(),
+            inject: None,
+            check: false,
+            check_descriptions: false,
+            star: false,
+            dry_run: false,
+            star_exclude: vec![],
+            fail_on: vec![],
+            sponsorable_only: false,
+            maintainers: false,
+            contributors: 0,
+            local_metadata: false,
+            offline: false,
+            refresh: false,
+            cache_ttl: Cache::DEFAULT_TTL,
+            crates_io_api: CratesioClient::DEFAULT_API_URL.to_string(),
+            github_api: GitHubClient::DEFAULT_API_URL.to_string(),
             language: String::from("zh"),
             verbose: false,
             max_concurrent_requests: 5,
@@ -126,9 +302,80 @@ impl Config {
             .unwrap_or_default();
 
         let github_token = matches.get_one::<String>("token").cloned();
+        let gitlab_token = matches.get_one::<String>("gitlab-token").cloned();
+        let gitlab_host = matches.get_one::<String>("gitlab-host").cloned();
+        let gitea_token = matches.get_one::<String>("gitea-token").cloned();
+        let gitea_host = matches.get_one::<String>("gitea-host").cloned();
+        let bitbucket_token = matches.get_one::<String>("bitbucket-token").cloned();
         // let crates_token = matches.get_one::<String>("crates-token").cloned();
         let no_relative_libs = matches.get_flag("no-relative-libs");
 
+        let depth = matches
+            .get_one::<String>("depth")
+            .map(|d| d.parse::<DepthLimit>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let strings = |id: &str| {
+            matches
+                .get_many::<String>(id)
+                .unwrap_or_default()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let packages = PackageSelection {
+            packages: strings("package"),
+            exclude: strings("exclude"),
+        };
+        let per_package = matches.get_flag("per-package");
+        let write_mode = if matches.get_flag("update") {
+            WriteMode::Update
+        } else if matches.get_flag("overwrite") {
+            WriteMode::Overwrite
+        } else {
+            WriteMode::Append
+        };
+
+        let star = matches.get_flag("star");
+        let dry_run = matches.get_flag("dry-run");
+        let star_exclude = matches
+            .get_many::<String>("star-exclude")
+            .unwrap_or_default()
+            .cloned()
+            .collect();
+
+        let fail_on = matches
+            .get_many::<String>("fail-on")
+            .unwrap_or_default()
+            .map(|status| status.parse::<DependencyStatus>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 指定贡献者数量时同样需要获取维护者
+        let contributors = matches
+            .get_one::<usize>("contributors")
+            .copied()
+            .unwrap_or_default();
+        let maintainers = matches.get_flag("maintainers") || contributors > 0;
+
+        let offline = matches.get_flag("offline");
+        let refresh = matches.get_flag("refresh");
+        // 离线时无法查询 crates.io，crate 的信息总是来自本地
+        let local_metadata = matches.get_flag("local-metadata") || offline;
+        let cache_ttl = matches
+            .get_one::<String>("cache-ttl")
+            .map(|ttl| parse_ttl(ttl))
+            .transpose()?
+            .unwrap_or(Cache::DEFAULT_TTL);
+
+        let crates_io_api = matches
+            .get_one::<String>("crates-io-api")
+            .cloned()
+            .unwrap_or_else(|| CratesioClient::DEFAULT_API_URL.to_string());
+        let github_api = matches
+            .get_one::<String>("github-api")
+            .cloned()
+            .unwrap_or_else(|| GitHubClient::DEFAULT_API_URL.to_string());
+
         let language = matches
             .get_one::<String>("language")
             .cloned()
@@ -146,8 +393,33 @@ impl Config {
             format,
             link_source,
             github_token,
+            gitlab_token,
+            gitlab_host,
+            gitea_token,
+            gitea_host,
+            bitbucket_token,
             // crates_token,
             no_relative_libs,
+            depth,
+            packages,
+            per_package,
+            write_mode,
+            inject: matches.get_one::<PathBuf>("inject").cloned(),
+            check: matches.get_flag("check"),
+            check_descriptions: matches.get_flag("check-descriptions"),
+            star,
+            dry_run,
+            star_exclude,
+            fail_on,
+            sponsorable_only: matches.get_flag("sponsorable-only"),
+            maintainers,
+            contributors,
+            local_metadata,
+            offline,
+            refresh,
+            cache_ttl,
+            crates_io_api,
+            github_api,
             language,
             verbose,
             max_concurrent_requests,
@@ -173,59 +445,170 @@ impl Config {
         ));
     }
 
+    /// 获取 crates.io 与 GitHub 响应的磁盘缓存
+    ///
+    /// 找不到用户缓存目录时不使用缓存，但离线模式必须依赖缓存
+    pub fn get_cache(&self) -> Result<Option<Cache>> {
+        let mode = match (self.offline, self.refresh) {
+            (true, _) => CacheMode::Offline,
+            (false, true) => CacheMode::Refresh,
+            (false, false) => CacheMode::Normal,
+        };
+
+        match Cache::default_dir() {
+            Some(dir) => Ok(Some(Cache::new(dir, self.cache_ttl, mode))),
+            None if self.offline => anyhow::bail!(t!("config.cache_dir_not_found")),
+            None => Ok(None),
+        }
+    }
+
+    /// `--check` 是否可以不请求网络
+    ///
+    /// 检查依赖集合与依赖类型时 cargo metadata 中的信息就足够；
+    /// 比较描述时，只有使用本地元数据（包括 `--offline`）才能不请求 crates.io。
+    /// 注入模式同样只比较依赖，不比较 stars 等统计信息
+    pub fn checks_without_network(&self) -> bool {
+        self.check && (!self.check_descriptions || self.local_metadata)
+    }
+
+    /// 输出位置是否为文件（而不是标准输出）
+    pub fn has_output_file(&self) -> bool {
+        self.output
+            .as_ref()
+            .is_some_and(|path| path.as_os_str() != "-")
+    }
+
     /// 获取输出位置 (buffer)
     ///
-    /// - 如果输出位置是文件，则返回文件内容进行追加写入
+    /// - 如果输出位置是文件，则按照 [`WriteMode`] 追加写入或覆盖
     ///     - 如果文件不存在，则创建，然后返回文件内容进行写入
     /// - 如果输出位置是标准输出，则返回标准输出，进行写入
     pub fn get_output_writer(&self) -> Result<OutputWriter> {
         match &self.output {
             Some(path) if path.as_os_str() == "-" => Ok(OutputWriter::Stdout(std::io::stdout())),
-            Some(path) => {
-                if path.exists() {
-                    // 文件存在，则打开文件进行追加写入
-                    let file = std::fs::OpenOptions::new()
-                        .write(true)
-                        .append(true)
-                        .open(path)
-                        .map_err(|e| {
-                            anyhow::anyhow!(t!(
-                                "config.failed_to_open_output_file",
-                                path = path.display(),
-                                error = e.to_string()
-                            ))
-                        })?;
-                    Ok(OutputWriter::File(file))
-                } else {
-                    // 文件不存在，则创建文件并返回文件内容进行写入
-                    let file = std::fs::OpenOptions::new()
-                        .write(true)
-                        .create(true)
-                        .truncate(true)
-                        .open(path)
-                        .map_err(|e| {
-                            anyhow::anyhow!(t!(
-                                "config.failed_to_open_output_file",
-                                path = path.display(),
-                                error = e.to_string()
-                            ))
-                        })?;
-                    Ok(OutputWriter::File(file))
-                }
-            }
+            Some(path) => self.open_output_file(path),
             None => Ok(OutputWriter::Stdout(std::io::stdout())),
         }
     }
+
+    /// 工作区成员单独的输出文件，例如 `thanks.json` -> `thanks-app.json`
+    pub fn get_package_output_path(&self, member: &str) -> Option<PathBuf> {
+        let path = self.output.as_ref().filter(|_| self.has_output_file())?;
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = match path.extension() {
+            Some(ext) => format!("{}-{}.{}", stem, member, ext.to_string_lossy()),
+            None => format!("{}-{}", stem, member),
+        };
+        Some(path.with_file_name(file_name))
+    }
+
+    /// 获取工作区成员单独的输出位置，写入方式与 [`Config::get_output_writer`] 相同
+    pub fn get_package_output_writer(&self, member: &str) -> Result<OutputWriter> {
+        match self.get_package_output_path(member) {
+            Some(path) => self.open_output_file(&path),
+            None => Ok(OutputWriter::Stdout(std::io::stdout())),
+        }
+    }
+
+    fn open_output_file(&self, path: &Path) -> Result<OutputWriter> {
+        let mut options = std::fs::OpenOptions::new();
+        if path.exists() && self.write_mode == WriteMode::Append {
+            // 文件存在，则打开文件进行追加写入
+            options.append(true);
+        } else {
+            // 文件不存在或需要覆盖，则创建文件并返回文件内容进行写入
+            // `--update` 的合并在写入之前完成，这里同样覆盖
+            options.write(true).create(true).truncate(true);
+        }
+
+        let file = options.open(path).map_err(|e| {
+            anyhow::anyhow!(t!(
+                "config.failed_to_open_output_file",
+                path = path.display(),
+                error = e.to_string()
+            ))
+        })?;
+        Ok(OutputWriter::File(file))
+    }
+
+    /// 原子地替换输出文件：先写入同一目录下的临时文件，再重命名为目标文件
+    ///
+    /// 写入过程中出错时原有的文件保持不变
+    pub fn replace_output_file(path: &Path, content: &[u8]) -> Result<()> {
+        use std::io::Write;
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = path.with_file_name(format!(".{}.thanku-tmp", file_name));
+
+        let write = || -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(content)?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, path)
+        };
+        write().map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            anyhow::anyhow!(t!(
+                "config.failed_to_write_output_file",
+                path = path.display(),
+                error = e.to_string()
+            ))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_depth_limit_from_str() -> Result<()> {
+        assert_eq!("1".parse::<DepthLimit>()?, DepthLimit::Level(1));
+        assert_eq!("3".parse::<DepthLimit>()?, DepthLimit::Level(3));
+        assert_eq!("all".parse::<DepthLimit>()?, DepthLimit::All);
+        assert_eq!("ALL".parse::<DepthLimit>()?, DepthLimit::All);
+        assert!("0".parse::<DepthLimit>().is_err());
+        assert!("deep".parse::<DepthLimit>().is_err());
+
+        assert!(DepthLimit::Level(2).allows(2));
+        assert!(!DepthLimit::Level(2).allows(3));
+        assert!(DepthLimit::All.allows(42));
+        assert!(DepthLimit::default().is_direct());
+        Ok(())
+    }
+
+    #[test]
+    fn test_package_selection() {
+        let all = PackageSelection::default();
+        assert!(all.selects("app"));
+
+        let selection = PackageSelection {
+            packages: vec!["app".to_string(), "cli".to_string()],
+            exclude: vec!["cli".to_string()],
+        };
+        assert!(selection.selects("app"));
+        assert!(!selection.selects("cli"));
+        assert!(!selection.selects("core"));
+    }
+
+    #[test]
+    fn test_parse_ttl() -> Result<()> {
+        assert_eq!(parse_ttl("3600")?, Duration::from_secs(3600));
+        assert_eq!(parse_ttl("90s")?, Duration::from_secs(90));
+        assert_eq!(parse_ttl("30m")?, Duration::from_secs(30 * 60));
+        assert_eq!(parse_ttl("12h")?, Duration::from_secs(12 * 60 * 60));
+        assert_eq!(parse_ttl("7d")?, Duration::from_secs(7 * 24 * 60 * 60));
+        assert!(parse_ttl("1w").is_err());
+        assert!(parse_ttl("h").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_output_writer_stdout() -> Result<()> {
-        let mut config = Config::default();
-        config.output = Some(PathBuf::from("-"));
+        let config = Config {
+            output: Some(PathBuf::from("-")),
+            ..Default::default()
+        };
 
         match config.get_output_writer()? {
             OutputWriter::Stdout(_) => Ok(()),
@@ -236,8 +619,10 @@ mod tests {
     #[test]
     fn test_output_writer_file() -> Result<()> {
         let temp_file = assert_fs::NamedTempFile::new("test-output.md")?;
-        let mut config = Config::default();
-        config.output = Some(temp_file.path().to_path_buf());
+        let config = Config {
+            output: Some(temp_file.path().to_path_buf()),
+            ..Default::default()
+        };
 
         match config.get_output_writer()? {
             OutputWriter::File(_) => Ok(()),
@@ -245,6 +630,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_output_write_mode() -> Result<()> {
+        use assert_fs::prelude::*;
+        use std::io::Write;
+
+        let temp = assert_fs::TempDir::new()?;
+        let file = temp.child("thanks.md");
+        file.write_str("old\n")?;
+
+        let write = |write_mode: WriteMode, content: &str| -> Result<()> {
+            let config = Config {
+                output: Some(file.path().to_path_buf()),
+                write_mode,
+                ..Default::default()
+            };
+            config.get_output_writer()?.write_all(content.as_bytes())?;
+            Ok(())
+        };
+        write(WriteMode::Append, "new\n")?;
+        file.assert("old\nnew\n");
+        write(WriteMode::Overwrite, "only\n")?;
+        file.assert("only\n");
+
+        Config::replace_output_file(file.path(), b"replaced\n")?;
+        file.assert("replaced\n");
+        // 临时文件在替换后不再存在
+        assert_eq!(std::fs::read_dir(temp.path())?.count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_checks_without_network() {
+        let check = |check_descriptions: bool, local_metadata: bool, inject: bool| {
+            Config {
+                check: true,
+                check_descriptions,
+                local_metadata,
+                inject: inject.then(|| PathBuf::from("README.md")),
+                ..Default::default()
+            }
+            .checks_without_network()
+        };
+        assert!(check(false, false, false));
+        assert!(check(true, true, false));
+        assert!(check(false, false, true));
+        // 比较描述需要 crates.io 上的信息，注入模式也是如此
+        assert!(!check(true, false, false));
+        assert!(!check(true, false, true));
+        assert!(!Config::default().checks_without_network());
+    }
+
+    #[test]
+    fn test_package_output_path() {
+        let config = Config {
+            output: Some(PathBuf::from("docs/thanks.json")),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_package_output_path("app"),
+            Some(PathBuf::from("docs/thanks-app.json"))
+        );
+
+        let config = Config {
+            output: Some(PathBuf::from("-")),
+            ..Default::default()
+        };
+        assert_eq!(config.get_package_output_path("app"), None);
+    }
+
     #[test]
     fn test_output_writer_default() -> Result<()> {
         let config = Config::default();
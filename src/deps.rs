@@ -0,0 +1,1157 @@
+use anyhow::Result;
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, Package, PackageId};
+use rust_i18n::t;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+use url::Url;
+
+use crate::{
+    config::{DepthLimit, PackageSelection},
+    errors::AppError,
+    funding::{self, FundingLink},
+    license::LICENSE_FILE_PREFIX,
+    registries::{self, Registries},
+};
+
+/// 依赖的来源，决定从哪里获取依赖的信息
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DependencyOrigin {
+    /// 来自 crates.io，通过 crates.io 的 API 获取信息
+    #[default]
+    Registry,
+    /// 来自替代注册表，`name` 为 Cargo 配置中的注册表名称，未配置时为索引的主机名
+    AltRegistry { name: String, index: String },
+    /// 来自 git 仓库，`url` 为去掉 `git+` 前缀、查询参数与提交哈希的仓库地址
+    Git { url: String },
+    /// 本地路径依赖，信息只来自其自身的 Cargo.toml
+    Path,
+}
+
+impl DependencyOrigin {
+    /// 根据依赖声明与解析得到的包判断来源
+    ///
+    /// 解析得到的包的来源包含具体的提交，优先使用；没有时使用依赖声明中的来源
+    fn new(dependency: &cargo_metadata::Dependency, package: Option<&Package>) -> Self {
+        if dependency.path.is_some() {
+            return Self::Path;
+        }
+
+        let source = package
+            .and_then(|pkg| pkg.source.as_ref())
+            .map(|source| source.repr.as_str())
+            .or(dependency.source.as_deref());
+        if let Some(url) = source.and_then(git_repository_url) {
+            return Self::Git { url };
+        }
+
+        // 依赖声明中的 `registry` 为替代注册表的索引地址，crates.io 为空
+        let index = source
+            .filter(|source| source.starts_with("registry+") || source.starts_with("sparse+"))
+            .or(dependency.registry.as_deref())
+            .filter(|index| !registries::is_crates_io(index));
+        match index {
+            Some(index) => Self::AltRegistry {
+                name: registry_host(index),
+                index: index.to_string(),
+            },
+            None => Self::Registry,
+        }
+    }
+
+    /// 是否来自 crates.io
+    pub fn is_crates_io(&self) -> bool {
+        matches!(self, Self::Registry)
+    }
+
+    pub fn is_alt_registry(&self) -> bool {
+        matches!(self, Self::AltRegistry { .. })
+    }
+
+    /// 使用 Cargo 配置中的名称替换替代注册表的默认名称
+    fn name_registry(&mut self, registries: &Registries) {
+        if let Self::AltRegistry { name, index } = self
+            && let Some(registry) = registries.find_by_index(index)
+        {
+            *name = registry.name.clone();
+        }
+    }
+}
+
+/// 未在 Cargo 配置中找到的注册表使用索引的主机名作为名称
+fn registry_host(index: &str) -> String {
+    let url = index
+        .trim_start_matches("registry+")
+        .trim_start_matches("sparse+");
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// 去掉作者中的邮箱地址，例如 `David Tolnay <dtolnay@gmail.com>` → `David Tolnay`
+fn author_name(author: &str) -> String {
+    match author.split_once('<') {
+        Some((name, _)) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => author.trim().to_string(),
+    }
+}
+
+/// 从 `git+https://github.com/owner/repo?branch=main#<commit>` 形式的来源中取出仓库地址
+fn git_repository_url(source: &str) -> Option<String> {
+    let mut url = Url::parse(source.strip_prefix("git+")?).ok()?;
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.as_str().trim_end_matches(".git").to_string())
+}
+
+/// 从 cargo metadata 中收集到的依赖项
+///
+/// 除了依赖声明本身，还记录了它在依赖图中的位置：
+/// - `depth`: 距离工作区成员的层数，直接依赖为 1
+/// - `parents`: 引入该依赖的父级链路，从工作区成员开始，到直接父级结束
+/// - `members`: 使用该依赖的工作区成员，按名称排序
+///
+/// 同一个 crate 可能以多种方式被使用，`kinds` 与 `targets` 记录所有的依赖类型与目标平台
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+    pub dependency: cargo_metadata::Dependency,
+    pub depth: u32,
+    pub parents: Vec<String>,
+    pub members: Vec<String>,
+    /// Cargo.lock 中解析得到的所有版本，从旧到新排列
+    pub versions: Vec<String>,
+    /// 依赖包在 Cargo.toml 中声明的 SPDX 许可证表达式
+    pub license: Option<String>,
+    /// 依赖包的 Cargo.toml 路径，其所在目录即为包的源码目录
+    pub manifest_path: Option<PathBuf>,
+    /// 依赖包在 Cargo.toml 中通过 `license-file` 指定的许可证文件
+    pub license_file: Option<PathBuf>,
+    /// 依赖的来源：crates.io、替代注册表、git 仓库或本地路径
+    pub origin: DependencyOrigin,
+    /// 依赖包在 Cargo.toml 中声明的描述
+    pub description: Option<String>,
+    /// 依赖包在 Cargo.toml 中声明的仓库地址
+    pub repository: Option<String>,
+    pub homepage: Option<String>,
+    pub documentation: Option<String>,
+    /// 依赖包在 Cargo.toml 中声明的作者，去掉了邮箱地址
+    pub authors: Vec<String>,
+    /// 依赖包在 `[package.metadata.funding]` 中声明的赞助链接
+    pub funding: Vec<FundingLink>,
+    /// 所有的依赖类型，按首次出现的顺序
+    pub kinds: Vec<DependencyKind>,
+    /// 限定的目标平台，例如 `cfg(windows)`，为空表示在所有平台上使用
+    pub targets: Vec<String>,
+    /// 是否存在不限定目标平台的用法
+    all_targets: bool,
+}
+
+impl ResolvedDependency {
+    fn new(
+        dependency: cargo_metadata::Dependency,
+        package: Option<&Package>,
+        depth: u32,
+        parents: Vec<String>,
+    ) -> Self {
+        Self {
+            origin: DependencyOrigin::new(&dependency, package),
+            dependency,
+            depth,
+            parents,
+            members: vec![],
+            versions: package
+                .map(|pkg| vec![pkg.version.to_string()])
+                .unwrap_or_default(),
+            license: package.and_then(package_license),
+            manifest_path: package.map(|pkg| pkg.manifest_path.clone().into_std_path_buf()),
+            license_file: package
+                .and_then(|pkg| pkg.license_file())
+                .map(|file| file.into_std_path_buf()),
+            description: package.and_then(|pkg| pkg.description.clone()),
+            repository: package.and_then(|pkg| pkg.repository.clone()),
+            homepage: package.and_then(|pkg| pkg.homepage.clone()),
+            documentation: package.and_then(|pkg| pkg.documentation.clone()),
+            authors: package
+                .map(|pkg| {
+                    pkg.authors
+                        .iter()
+                        .map(|author| author_name(author))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            funding: package
+                .map(|pkg| funding::from_package_metadata(&pkg.metadata))
+                .unwrap_or_default(),
+            kinds: vec![],
+            targets: vec![],
+            all_targets: false,
+        }
+    }
+
+    fn add_member(&mut self, member: &str) {
+        if !self.members.iter().any(|m| m == member) {
+            self.members.push(member.to_string());
+            self.members.sort();
+        }
+    }
+
+    /// 记录一种用法，任意一种用法不限定目标平台时，依赖即在所有平台上使用
+    fn add_usage(&mut self, kind: DependencyKind, target: Option<impl ToString>) {
+        self.add_kind(kind);
+        self.add_target(target.map(|target| target.to_string()).as_deref());
+    }
+
+    /// 记录同一个 crate 的另一个解析版本
+    fn add_version(&mut self, version: &str) {
+        if !self.versions.iter().any(|v| v == version) {
+            self.versions.push(version.to_string());
+            self.versions
+                .sort_by_key(|v| cargo_metadata::semver::Version::parse(v).ok());
+        }
+    }
+
+    fn add_kind(&mut self, kind: DependencyKind) {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+    }
+
+    fn add_target(&mut self, target: Option<&str>) {
+        match target {
+            _ if self.all_targets => {}
+            None => {
+                self.all_targets = true;
+                self.targets.clear();
+            }
+            Some(target) => {
+                if !self.targets.iter().any(|t| t == target) {
+                    self.targets.push(target.to_string());
+                    self.targets.sort();
+                }
+            }
+        }
+    }
+
+    /// 合并另一个工作区成员中的同一个依赖，或同一个 crate 的另一个版本
+    fn merge(&mut self, other: &Self) {
+        for member in &other.members {
+            self.add_member(member);
+        }
+        for version in &other.versions {
+            self.add_version(version);
+        }
+        for kind in &other.kinds {
+            self.add_kind(*kind);
+        }
+        if other.all_targets {
+            self.add_target(None);
+        }
+        for target in &other.targets {
+            self.add_target(Some(target));
+        }
+    }
+}
+
+#[instrument(skip_all)]
+pub fn get_dependencies<P>(
+    cargo_toml_path: P,
+    depth: DepthLimit,
+    offline: bool,
+    selection: &PackageSelection,
+) -> Result<HashMap<String, ResolvedDependency>>
+where
+    P: AsRef<Path>,
+{
+    let mut command = MetadataCommand::new();
+    command.manifest_path(cargo_toml_path.as_ref());
+
+    if offline {
+        command.other_options(vec!["--offline".to_string()]);
+    }
+
+    // 即使只需要直接依赖也要解析依赖图，才能拿到依赖包本身的信息（例如许可证）
+    let metadata = command.exec().map_err(AppError::MetadataError)?;
+
+    let members = select_members(&metadata, selection)?;
+    let mut deps = if depth.is_direct() {
+        collect_direct_dependencies(&metadata, &members)
+    } else {
+        collect_resolved_dependencies(&metadata, depth, &members)?
+    };
+
+    if deps
+        .values()
+        .any(|resolved| resolved.origin.is_alt_registry())
+    {
+        let manifest_dir = cargo_toml_path.as_ref().parent().unwrap_or(Path::new(""));
+        let registries = Registries::load(manifest_dir);
+        for resolved in deps.values_mut() {
+            resolved.origin.name_registry(&registries);
+        }
+    }
+
+    debug!("{}", t!("main.found_dependencies", count = deps.len()));
+    Ok(deps)
+}
+
+/// 按照 `--package`/`--exclude` 选择工作区成员，结果按名称排序
+///
+/// `--package` 指定的成员不存在时报错，`--exclude` 指定的成员不存在时只给出警告
+fn select_members<'a>(
+    metadata: &'a Metadata,
+    selection: &PackageSelection,
+) -> Result<Vec<&'a Package>> {
+    let workspace = metadata.workspace_packages();
+    let is_member = |name: &str| workspace.iter().any(|pkg| pkg.name == name);
+
+    if let Some(name) = selection.packages.iter().find(|name| !is_member(name)) {
+        return Err(AppError::PackageNotFound(name.clone()).into());
+    }
+    for name in selection.exclude.iter().filter(|name| !is_member(name)) {
+        warn!("{}", t!("deps.excluded_package_not_found", name = name));
+    }
+
+    let mut members = workspace
+        .into_iter()
+        .filter(|pkg| selection.selects(&pkg.name))
+        .collect::<Vec<_>>();
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(members)
+}
+
+/// 只收集工作区成员在 Cargo.toml 中声明的依赖
+fn collect_direct_dependencies(
+    metadata: &Metadata,
+    members: &[&Package],
+) -> HashMap<String, ResolvedDependency> {
+    let packages: HashMap<&PackageId, &Package> =
+        metadata.packages.iter().map(|pkg| (&pkg.id, pkg)).collect();
+
+    let mut deps = HashMap::new();
+    for pkg in members {
+        for dep in &pkg.dependencies {
+            let package = find_resolved_package(metadata, &packages, pkg, dep);
+            let resolved = deps.entry(dep.name.clone()).or_insert_with(|| {
+                ResolvedDependency::new(dep.clone(), package, 1, vec![pkg.name.clone()])
+            });
+            // 同一个 crate 可能以不同的名称声明了多个版本
+            if let Some(package) = package {
+                resolved.add_version(&package.version.to_string());
+            }
+            resolved.add_member(&pkg.name);
+            resolved.add_usage(dep.kind, dep.target.as_ref());
+        }
+    }
+    deps
+}
+
+/// 分别遍历每个工作区成员的依赖图并合并
+///
+/// 合并时保留最短的引入链路，并记录所有使用该依赖的成员、依赖类型与目标平台
+fn collect_resolved_dependencies(
+    metadata: &Metadata,
+    depth: DepthLimit,
+    members: &[&Package],
+) -> Result<HashMap<String, ResolvedDependency>> {
+    let resolve = metadata
+        .resolve
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!(t!("deps.missing_resolve_graph")))?;
+
+    let packages: HashMap<&PackageId, &Package> =
+        metadata.packages.iter().map(|pkg| (&pkg.id, pkg)).collect();
+    let nodes: HashMap<&PackageId, &cargo_metadata::Node> =
+        resolve.nodes.iter().map(|node| (&node.id, node)).collect();
+
+    let mut deps: HashMap<String, ResolvedDependency> = HashMap::new();
+    for member in members {
+        // 同一个 crate 的多个版本按名称合并，遍历顺序保证先合并的是最短的引入链路
+        for (name, mut resolved) in walk_resolve_graph(&packages, &nodes, member, depth) {
+            resolved.add_member(&member.name);
+            match deps.get_mut(&name) {
+                Some(existing) if resolved.depth < existing.depth => {
+                    resolved.merge(existing);
+                    *existing = resolved;
+                }
+                Some(existing) => existing.merge(&resolved),
+                None => {
+                    deps.insert(name, resolved);
+                }
+            }
+        }
+    }
+
+    Ok(deps)
+}
+
+/// 从一个工作区成员出发，沿着 `metadata.resolve` 依赖图进行广度优先遍历
+///
+/// 广度优先保证每个依赖记录的是最短的引入链路，之后遇到的其它用法只补充依赖类型与目标平台。
+/// 开发/构建依赖所引入的传递依赖会继承父级的依赖类型。
+///
+/// 依赖按 `PackageId` 区分，同一个 crate 的不同版本分别遍历，结果按首次遇到的顺序排列。
+fn walk_resolve_graph(
+    packages: &HashMap<&PackageId, &Package>,
+    nodes: &HashMap<&PackageId, &cargo_metadata::Node>,
+    member: &Package,
+    depth: DepthLimit,
+) -> Vec<(String, ResolvedDependency)> {
+    // 按包与继承的依赖类型记录已遍历的节点
+    let mut visited: HashSet<(&PackageId, Option<DependencyKind>)> =
+        HashSet::from([(&member.id, None)]);
+    let mut queue = VecDeque::from([(&member.id, None, vec![member.name.clone()])]);
+
+    let mut deps: Vec<(String, ResolvedDependency)> = vec![];
+    let mut indices: HashMap<&PackageId, usize> = HashMap::new();
+    while let Some((id, inherited_kind, chain)) = queue.pop_front() {
+        // 子依赖的深度即为当前链路的长度
+        let current_depth = chain.len() as u32;
+        if !depth.allows(current_depth) {
+            continue;
+        }
+
+        let (Some(parent), Some(node)) = (packages.get(id), nodes.get(id)) else {
+            continue;
+        };
+
+        let mut children = node.deps.iter().collect::<Vec<_>>();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for child in children {
+            let Some(pkg) = packages.get(&child.pkg) else {
+                continue;
+            };
+
+            // 解析图中的边记录了 cargo 实际解析的依赖类型，同时存在多种类型时以普通依赖为准
+            let edge_kind = child
+                .dep_kinds
+                .iter()
+                .map(|info| info.kind)
+                .min_by_key(|kind| *kind != cargo_metadata::DependencyKind::Normal);
+
+            let Some(mut dependency) = find_declaration(parent, pkg, &child.name, edge_kind) else {
+                debug!(
+                    "{}",
+                    t!(
+                        "deps.declaration_not_found",
+                        name = pkg.name,
+                        parent = parent.name
+                    )
+                );
+                continue;
+            };
+
+            // 开发/构建依赖的传递依赖同样只在开发/构建阶段使用
+            if let Some(kind) = inherited_kind {
+                dependency.kind = kind;
+            }
+
+            // 解析图中记录了该依赖的所有用法，旧版本 cargo 没有时使用声明本身
+            let usages = match child.dep_kinds.as_slice() {
+                [] => vec![(dependency.kind, dependency.target.clone())],
+                dep_kinds => dep_kinds
+                    .iter()
+                    .map(|info| (inherited_kind.unwrap_or(info.kind), info.target.clone()))
+                    .collect(),
+            };
+
+            let index = *indices.entry(&pkg.id).or_insert_with(|| {
+                deps.push((
+                    pkg.name.clone(),
+                    ResolvedDependency::new(
+                        dependency.clone(),
+                        Some(pkg),
+                        current_depth,
+                        chain.clone(),
+                    ),
+                ));
+                deps.len() - 1
+            });
+            let resolved = &mut deps[index].1;
+            for (kind, target) in &usages {
+                resolved.add_usage(*kind, target.as_ref());
+            }
+            if dependency.kind == DependencyKind::Normal {
+                resolved.dependency.kind = DependencyKind::Normal;
+            }
+
+            // 先经由开发/构建依赖遇到的包，之后经由普通依赖遇到时需要再遍历一次，
+            // 其下的传递依赖才会记录普通依赖的用法
+            let next_kind = match dependency.kind {
+                DependencyKind::Normal => inherited_kind,
+                kind => Some(kind),
+            };
+            if !visited.contains(&(&pkg.id, None)) && visited.insert((&pkg.id, next_kind)) {
+                let mut next_chain = chain.clone();
+                next_chain.push(pkg.name.clone());
+                queue.push_back((&pkg.id, next_kind, next_chain));
+            }
+        }
+    }
+
+    deps
+}
+
+/// 包的许可证，只声明了 `license-file` 时使用 `SEE LICENSE IN <file>` 表示
+fn package_license(pkg: &Package) -> Option<String> {
+    pkg.license.clone().or_else(|| {
+        pkg.license_file.as_ref().map(|file| {
+            format!(
+                "{}{}",
+                LICENSE_FILE_PREFIX,
+                file.file_name().unwrap_or(file.as_str())
+            )
+        })
+    })
+}
+
+/// 找到依赖声明在解析图中对应的包
+///
+/// 没有解析图时按名称与版本要求在所有包中查找
+fn find_resolved_package<'a>(
+    metadata: &Metadata,
+    packages: &HashMap<&PackageId, &'a Package>,
+    parent: &Package,
+    dep: &cargo_metadata::Dependency,
+) -> Option<&'a Package> {
+    let matches = |pkg: &&'a Package| pkg.name == dep.name && dep.req.matches(&pkg.version);
+
+    let node = metadata
+        .resolve
+        .as_ref()
+        .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == parent.id));
+    match node {
+        Some(node) => node
+            .deps
+            .iter()
+            .filter_map(|child| packages.get(&child.pkg).copied())
+            .find(matches),
+        None => packages.values().copied().find(matches),
+    }
+}
+
+/// 在父级包的依赖声明中找到与解析图节点对应的声明
+///
+/// 解析图中的 `name` 是依赖在代码中的名称（可能被重命名，且 `-` 会被替换为 `_`）。
+/// 同一个 crate 可能同时被声明为普通依赖与开发依赖，给出 `kind` 时只匹配该类型的声明。
+fn find_declaration(
+    parent: &Package,
+    pkg: &Package,
+    extern_name: &str,
+    kind: Option<DependencyKind>,
+) -> Option<cargo_metadata::Dependency> {
+    let candidates = || {
+        parent.dependencies.iter().filter(|dep| {
+            dep.name == pkg.name
+                && dep.req.matches(&pkg.version)
+                && kind.is_none_or(|kind| dep.kind == kind)
+        })
+    };
+
+    candidates()
+        .find(|dep| dep.rename.as_deref().unwrap_or(&dep.name).replace('-', "_") == extern_name)
+        .or_else(|| candidates().next())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的包，`id` 为 `名称 版本`
+    fn package(name: &str, version: &str, deps: Vec<serde_json::Value>) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "version": version,
+            "id": format!("{} {}", name, version),
+            "license": (name != "diff").then_some("MIT OR Apache-2.0"),
+            "license_file": (name == "diff").then_some("LICENSE.txt"),
+            "description": null,
+            "source": null,
+            "dependencies": deps,
+            "targets": [],
+            "features": {},
+            "manifest_path": format!("/fixture/{}/Cargo.toml", name),
+            "authors": [],
+            "categories": [],
+            "keywords": [],
+            "readme": null,
+            "repository": null,
+            "homepage": null,
+            "documentation": null,
+            "edition": "2021",
+            "links": null,
+            "publish": null,
+            "rust_version": null
+        })
+    }
+
+    /// 测试用的依赖声明
+    fn declaration(
+        name: &str,
+        req: &str,
+        kind: Option<&str>,
+        target: Option<&str>,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "source": null,
+            "req": req,
+            "kind": kind,
+            "optional": false,
+            "uses_default_features": true,
+            "features": [],
+            "target": target,
+            "rename": null,
+            "registry": null,
+            "path": null
+        })
+    }
+
+    /// 测试用的解析图节点，每条边为依赖包的 `id` 与 cargo 解析得到的依赖类型
+    fn node(id: &str, deps: &[(&str, &[Option<&str>])]) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "deps": deps.iter().map(|(dep, kinds)| serde_json::json!({
+                "name": dep.split(' ').next().unwrap().replace('-', "_"),
+                "pkg": dep,
+                "dep_kinds": kinds.iter().map(|kind| serde_json::json!({ "kind": kind, "target": null })).collect::<Vec<_>>()
+            })).collect::<Vec<_>>(),
+            "dependencies": deps.iter().map(|(dep, _)| dep).collect::<Vec<_>>(),
+            "features": []
+        })
+    }
+
+    fn metadata(
+        packages: Vec<serde_json::Value>,
+        members: &[&str],
+        nodes: Vec<serde_json::Value>,
+    ) -> Metadata {
+        serde_json::from_value(serde_json::json!({
+            "packages": packages,
+            "workspace_members": members,
+            "workspace_default_members": members,
+            "resolve": {
+                "nodes": nodes,
+                "root": members[0]
+            },
+            "target_directory": "/fixture/target",
+            "workspace_root": "/fixture",
+            "version": 1,
+            "metadata": null
+        }))
+        .unwrap()
+    }
+
+    /// 一个最小化的工作区：
+    /// - app -> serde -> serde_derive, app -(dev)-> pretty_assertions -> diff
+    /// - app -(build, cfg(windows))-> serde
+    /// - tool -> serde, tool -(cfg(unix))-> diff
+    fn fixture() -> Metadata {
+        let package = |name: &str, deps: Vec<serde_json::Value>| package(name, "1.0.0", deps);
+        let target_dep = |name: &str, kind: Option<&str>, target: Option<&str>| {
+            declaration(name, "^1", kind, target)
+        };
+        let dep = |name: &str, kind: Option<&str>| target_dep(name, kind, None);
+        let node = |name: &str, deps: &[(&str, Option<&str>)]| {
+            let ids = deps
+                .iter()
+                .map(|(dep, kind)| (format!("{} 1.0.0", dep), [*kind]))
+                .collect::<Vec<_>>();
+            node(
+                &format!("{} 1.0.0", name),
+                &ids.iter()
+                    .map(|(id, kinds)| (id.as_str(), kinds.as_slice()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        metadata(
+            vec![
+                package(
+                    "app",
+                    vec![
+                        dep("serde", None),
+                        target_dep("serde", Some("build"), Some("cfg(windows)")),
+                        dep("pretty_assertions", Some("dev")),
+                    ],
+                ),
+                package("serde", vec![dep("serde_derive", None)]),
+                package("serde_derive", vec![]),
+                package("pretty_assertions", vec![dep("diff", None)]),
+                package("diff", vec![]),
+                package(
+                    "tool",
+                    vec![
+                        dep("serde", None),
+                        target_dep("diff", None, Some("cfg(unix)")),
+                    ],
+                ),
+            ],
+            &["app 1.0.0", "tool 1.0.0"],
+            vec![
+                node(
+                    "app",
+                    &[("serde", None), ("pretty_assertions", Some("dev"))],
+                ),
+                node("serde", &[("serde_derive", None)]),
+                node("serde_derive", &[]),
+                node("pretty_assertions", &[("diff", None)]),
+                node("diff", &[]),
+                node("tool", &[("serde", None), ("diff", None)]),
+            ],
+        )
+    }
+
+    /// 只选择 app 成员
+    fn app_only() -> PackageSelection {
+        PackageSelection {
+            packages: vec!["app".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dependency_origin() {
+        let dependency = |source: Option<&str>, path: Option<&str>| {
+            serde_json::from_value::<cargo_metadata::Dependency>(serde_json::json!({
+                "name": "foo",
+                "source": source,
+                "req": "*",
+                "kind": null,
+                "optional": false,
+                "uses_default_features": true,
+                "features": [],
+                "target": null,
+                "rename": null,
+                "registry": null,
+                "path": path
+            }))
+            .unwrap()
+        };
+
+        let registry = dependency(
+            Some("registry+https://github.com/rust-lang/crates.io-index"),
+            None,
+        );
+        assert_eq!(
+            DependencyOrigin::new(&registry, None),
+            DependencyOrigin::Registry
+        );
+
+        let git = dependency(
+            Some("git+https://github.com/owner/foo.git?branch=main"),
+            None,
+        );
+        assert_eq!(
+            DependencyOrigin::new(&git, None),
+            DependencyOrigin::Git {
+                url: "https://github.com/owner/foo".to_string()
+            }
+        );
+        assert_eq!(
+            git_repository_url("git+https://gitlab.com/group/foo?rev=abc#abcdef0123"),
+            Some("https://gitlab.com/group/foo".to_string())
+        );
+
+        let path = dependency(None, Some("/workspace/crates/foo"));
+        assert_eq!(DependencyOrigin::new(&path, None), DependencyOrigin::Path);
+
+        let sparse = dependency(Some("sparse+https://registry.example.com/index/"), None);
+        assert_eq!(
+            DependencyOrigin::new(&sparse, None),
+            DependencyOrigin::AltRegistry {
+                name: "registry.example.com".to_string(),
+                index: "sparse+https://registry.example.com/index/".to_string(),
+            }
+        );
+        assert!(
+            DependencyOrigin::new(
+                &dependency(Some("sparse+https://index.crates.io/"), None),
+                None
+            )
+            .is_crates_io()
+        );
+
+        // 没有来源时使用依赖声明中的注册表索引
+        let mut declared = dependency(None, None);
+        declared.registry = Some("https://git.example.com/index".to_string());
+        let mut origin = DependencyOrigin::new(&declared, None);
+        assert!(origin.is_alt_registry());
+
+        let temp = assert_fs::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp.path().join(".cargo/config.toml"),
+            "[registries.internal]\nindex = \"https://git.example.com/index/\"\n",
+        )
+        .unwrap();
+        origin.name_registry(&Registries::load(temp.path()));
+        assert_eq!(
+            origin,
+            DependencyOrigin::AltRegistry {
+                name: "internal".to_string(),
+                index: "https://git.example.com/index".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_author_name() {
+        assert_eq!(
+            author_name("David Tolnay <dtolnay@gmail.com>"),
+            "David Tolnay"
+        );
+        assert_eq!(
+            author_name(" The Rust Project Developers "),
+            "The Rust Project Developers"
+        );
+        assert_eq!(author_name("<ghost@example.com>"), "<ghost@example.com>");
+    }
+
+    #[test]
+    fn test_select_members() -> Result<()> {
+        let metadata = fixture();
+        let names = |selection: &PackageSelection| -> Result<Vec<String>> {
+            Ok(select_members(&metadata, selection)?
+                .iter()
+                .map(|pkg| pkg.name.clone())
+                .collect())
+        };
+
+        assert_eq!(names(&PackageSelection::default())?, vec!["app", "tool"]);
+        assert_eq!(names(&app_only())?, vec!["app"]);
+
+        let selection = PackageSelection {
+            exclude: vec!["app".to_string(), "missing".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&selection)?, vec!["tool"]);
+
+        let selection = PackageSelection {
+            packages: vec!["missing".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            select_members(&metadata, &selection)
+                .unwrap_err()
+                .downcast_ref::<AppError>(),
+            Some(AppError::PackageNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_direct_dependencies() -> Result<()> {
+        let metadata = fixture();
+        let deps = collect_direct_dependencies(&metadata, &select_members(&metadata, &app_only())?);
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps["serde"].depth, 1);
+        assert_eq!(deps["serde"].parents, vec!["app"]);
+        assert_eq!(deps["serde"].members, vec!["app"]);
+        assert_eq!(deps["serde"].license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(deps["serde"].versions, vec!["1.0.0"]);
+        assert_eq!(deps["serde"].dependency.req.to_string(), "^1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_direct_dependencies_workspace() -> Result<()> {
+        let metadata = fixture();
+        let deps = collect_direct_dependencies(
+            &metadata,
+            &select_members(&metadata, &PackageSelection::default())?,
+        );
+        assert_eq!(deps.len(), 3);
+        assert_eq!(deps["serde"].members, vec!["app", "tool"]);
+        assert_eq!(deps["pretty_assertions"].members, vec!["app"]);
+        assert_eq!(deps["diff"].members, vec!["tool"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_direct_dependencies_kinds_and_targets() -> Result<()> {
+        let metadata = fixture();
+        let deps = collect_direct_dependencies(
+            &metadata,
+            &select_members(&metadata, &PackageSelection::default())?,
+        );
+
+        // 同一个 crate 的所有用法都被记录，只要有一种用法不限定平台，就在所有平台上使用
+        let serde = &deps["serde"];
+        assert_eq!(
+            serde.kinds,
+            vec![
+                cargo_metadata::DependencyKind::Normal,
+                cargo_metadata::DependencyKind::Build
+            ]
+        );
+        assert!(serde.targets.is_empty());
+
+        let diff = &deps["diff"];
+        assert_eq!(diff.kinds, vec![cargo_metadata::DependencyKind::Normal]);
+        assert_eq!(diff.targets, vec!["cfg(unix)"]);
+        assert_eq!(diff.license.as_deref(), Some("SEE LICENSE IN LICENSE.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_all() -> Result<()> {
+        let metadata = fixture();
+        let members = select_members(&metadata, &app_only())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::All, &members)?;
+        assert_eq!(deps.len(), 4);
+
+        let serde_derive = &deps["serde_derive"];
+        assert_eq!(serde_derive.depth, 2);
+        assert_eq!(serde_derive.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(serde_derive.versions, vec!["1.0.0"]);
+        assert_eq!(serde_derive.parents, vec!["app", "serde"]);
+        assert_eq!(
+            serde_derive.dependency.kind,
+            cargo_metadata::DependencyKind::Normal
+        );
+
+        // 开发依赖的传递依赖继承开发依赖类型
+        let diff = &deps["diff"];
+        assert_eq!(diff.depth, 2);
+        assert_eq!(diff.parents, vec!["app", "pretty_assertions"]);
+        assert_eq!(
+            diff.dependency.kind,
+            cargo_metadata::DependencyKind::Development
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_limited() -> Result<()> {
+        let metadata = fixture();
+        let members = select_members(&metadata, &app_only())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::Level(1), &members)?;
+        assert_eq!(deps.len(), 2);
+        assert!(deps.values().all(|dep| dep.depth == 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_workspace() -> Result<()> {
+        let metadata = fixture();
+        let members = select_members(&metadata, &PackageSelection::default())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::All, &members)?;
+        assert_eq!(deps.len(), 4);
+
+        // 保留最短的引入链路，并记录所有使用该依赖的成员
+        let diff = &deps["diff"];
+        assert_eq!(diff.depth, 1);
+        assert_eq!(diff.parents, vec!["tool"]);
+        assert_eq!(diff.members, vec!["app", "tool"]);
+        assert_eq!(diff.dependency.kind, cargo_metadata::DependencyKind::Normal);
+        // app 中作为开发依赖的传递依赖，tool 中作为普通依赖
+        assert_eq!(
+            diff.kinds,
+            vec![
+                cargo_metadata::DependencyKind::Normal,
+                cargo_metadata::DependencyKind::Development
+            ]
+        );
+
+        assert_eq!(deps["serde_derive"].members, vec!["app", "tool"]);
+        assert_eq!(deps["pretty_assertions"].members, vec!["app"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_edge_kinds() -> Result<()> {
+        // client 不是工作区成员，同时把 tower 声明为开发依赖与普通依赖，
+        // 非成员的开发依赖不会被解析，解析图中只有普通依赖的边
+        let metadata = metadata(
+            vec![
+                package(
+                    "app",
+                    "1.0.0",
+                    vec![declaration("client", "^1", None, None)],
+                ),
+                package(
+                    "client",
+                    "1.0.0",
+                    vec![
+                        declaration("tower", "^1", Some("dev"), None),
+                        declaration("tower", "^1", None, None),
+                    ],
+                ),
+                package(
+                    "tower",
+                    "1.0.0",
+                    vec![declaration("tower-layer", "^1", None, None)],
+                ),
+                package("tower-layer", "1.0.0", vec![]),
+            ],
+            &["app 1.0.0"],
+            vec![
+                node("app 1.0.0", &[("client 1.0.0", &[None])]),
+                node("client 1.0.0", &[("tower 1.0.0", &[None])]),
+                node("tower 1.0.0", &[("tower-layer 1.0.0", &[None])]),
+                node("tower-layer 1.0.0", &[]),
+            ],
+        );
+        let members = select_members(&metadata, &PackageSelection::default())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::All, &members)?;
+
+        for name in ["client", "tower", "tower-layer"] {
+            assert_eq!(
+                deps[name].dependency.kind,
+                cargo_metadata::DependencyKind::Normal,
+                "{}",
+                name
+            );
+            assert_eq!(
+                deps[name].kinds,
+                vec![cargo_metadata::DependencyKind::Normal],
+                "{}",
+                name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_versions() -> Result<()> {
+        // rand 的两个版本各自依赖不同版本的 getrandom，只有旧版本的 getrandom 依赖 libc
+        let metadata = metadata(
+            vec![
+                package(
+                    "app",
+                    "1.0.0",
+                    vec![
+                        declaration("rand", "^0.8", None, None),
+                        declaration("legacy", "^1", None, None),
+                    ],
+                ),
+                package(
+                    "legacy",
+                    "1.0.0",
+                    vec![declaration("rand", "^0.7", None, None)],
+                ),
+                package(
+                    "rand",
+                    "0.8.5",
+                    vec![declaration("getrandom", "^0.2", None, None)],
+                ),
+                package(
+                    "rand",
+                    "0.7.3",
+                    vec![declaration("getrandom", "^0.1", None, None)],
+                ),
+                package("getrandom", "0.2.15", vec![]),
+                package(
+                    "getrandom",
+                    "0.1.16",
+                    vec![declaration("libc", "^0.2", None, None)],
+                ),
+                package("libc", "0.2.169", vec![]),
+            ],
+            &["app 1.0.0"],
+            vec![
+                node(
+                    "app 1.0.0",
+                    &[("legacy 1.0.0", &[None]), ("rand 0.8.5", &[None])],
+                ),
+                node("legacy 1.0.0", &[("rand 0.7.3", &[None])]),
+                node("rand 0.8.5", &[("getrandom 0.2.15", &[None])]),
+                node("rand 0.7.3", &[("getrandom 0.1.16", &[None])]),
+                node("getrandom 0.2.15", &[]),
+                node("getrandom 0.1.16", &[("libc 0.2.169", &[None])]),
+                node("libc 0.2.169", &[]),
+            ],
+        );
+        let members = select_members(&metadata, &PackageSelection::default())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::All, &members)?;
+
+        assert_eq!(deps.len(), 4);
+        // 保留最短的引入链路
+        assert_eq!(deps["rand"].depth, 1);
+        assert_eq!(deps["rand"].parents, vec!["app"]);
+        assert_eq!(deps["getrandom"].depth, 2);
+        assert_eq!(deps["getrandom"].parents, vec!["app", "rand"]);
+        // 记录所有解析得到的版本，从旧到新排列
+        assert_eq!(deps["rand"].versions, vec!["0.7.3", "0.8.5"]);
+        assert_eq!(deps["getrandom"].versions, vec!["0.1.16", "0.2.15"]);
+        // 第二个版本的依赖同样被遍历
+        assert_eq!(deps["libc"].depth, 4);
+        assert_eq!(
+            deps["libc"].parents,
+            vec!["app", "legacy", "rand", "getrandom"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_resolved_dependencies_upgraded_kind() -> Result<()> {
+        // 按名称排序时先经由开发依赖 checker 遇到 common，之后才经由普通依赖 runtime 遇到
+        let metadata = metadata(
+            vec![
+                package(
+                    "app",
+                    "1.0.0",
+                    vec![
+                        declaration("checker", "^1", Some("dev"), None),
+                        declaration("runtime", "^1", None, None),
+                    ],
+                ),
+                package(
+                    "checker",
+                    "1.0.0",
+                    vec![declaration("common", "^1", None, None)],
+                ),
+                package(
+                    "runtime",
+                    "1.0.0",
+                    vec![declaration("common", "^1", None, None)],
+                ),
+                package(
+                    "common",
+                    "1.0.0",
+                    vec![declaration("leaf", "^1", None, None)],
+                ),
+                package("leaf", "1.0.0", vec![]),
+            ],
+            &["app 1.0.0"],
+            vec![
+                node(
+                    "app 1.0.0",
+                    &[
+                        ("checker 1.0.0", &[Some("dev")]),
+                        ("runtime 1.0.0", &[None]),
+                    ],
+                ),
+                node("checker 1.0.0", &[("common 1.0.0", &[None])]),
+                node("runtime 1.0.0", &[("common 1.0.0", &[None])]),
+                node("common 1.0.0", &[("leaf 1.0.0", &[None])]),
+                node("leaf 1.0.0", &[]),
+            ],
+        );
+        let members = select_members(&metadata, &PackageSelection::default())?;
+        let deps = collect_resolved_dependencies(&metadata, DepthLimit::All, &members)?;
+
+        assert_eq!(deps["common"].parents, vec!["app", "checker"]);
+        for name in ["common", "leaf"] {
+            assert_eq!(
+                deps[name].dependency.kind,
+                cargo_metadata::DependencyKind::Normal,
+                "{}",
+                name
+            );
+            assert_eq!(
+                deps[name].kinds,
+                vec![
+                    cargo_metadata::DependencyKind::Development,
+                    cargo_metadata::DependencyKind::Normal
+                ],
+                "{}",
+                name
+            );
+        }
+        Ok(())
+    }
+}
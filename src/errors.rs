@@ -1,3 +1,4 @@
+use rust_i18n::t;
 use thiserror::Error;
 
 #[allow(clippy::enum_variant_names, dead_code)]
@@ -24,6 +25,39 @@ pub enum AppError {
     #[error("Invalid link source: {0}")]
     InvalidLinkSource(String),
 
+    #[error("Invalid depth: {0}")]
+    InvalidDepth(String),
+
+    #[error("Invalid cache TTL: {0}")]
+    InvalidCacheTtl(String),
+
+    #[error("Package not found in workspace: {0}")]
+    PackageNotFound(String),
+
+    #[error("Invalid license expression: {0}")]
+    InvalidLicenseExpression(String),
+
+    #[error("Invalid version lag: {0}")]
+    InvalidVersionLag(String),
+
+    #[error("Not cached (offline): {0}")]
+    NotCached(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 
@@ -50,6 +84,45 @@ pub enum AppError {
 
     #[error("Invalid table line: {0}")]
     InvalidTableLine(String),
+
+    #[error("Registry not configured: {0}")]
+    UnknownRegistry(String),
+
+    #[error("Unsupported registry index: {0}")]
+    UnsupportedRegistry(String),
+}
+
+impl AppError {
+    /// 临时性的错误，稍后重试可能成功
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited(_) | Self::Timeout(_) | Self::Network(_)
+        )
+    }
+
+    /// 请求失败的简短原因，显示在输出的状态列中
+    pub fn short_reason(&self) -> Option<String> {
+        let reason = match self {
+            Self::NotFound(_) => t!("errors.not_found"),
+            Self::RateLimited(_) => t!("errors.rate_limited"),
+            Self::Unauthorized(_) => t!("errors.unauthorized"),
+            Self::Timeout(_) => t!("errors.timeout"),
+            Self::Network(_) => t!("errors.network"),
+            Self::UnknownRegistry(_) => t!("errors.unknown_registry"),
+            Self::UnsupportedRegistry(_) => t!("errors.unsupported_registry"),
+            _ => return None,
+        };
+        Some(reason.to_string())
+    }
+}
+
+/// 错误的简短描述，无法归类的错误使用完整的错误信息
+pub fn short_reason(error: &anyhow::Error) -> String {
+    error
+        .downcast_ref::<AppError>()
+        .and_then(AppError::short_reason)
+        .unwrap_or_else(|| error.to_string())
 }
 
 impl From<String> for AppError {
@@ -57,3 +130,25 @@ impl From<String> for AppError {
         Self::Unknown(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        assert!(AppError::Timeout("url".to_string()).is_transient());
+        assert!(AppError::Network("url".to_string()).is_transient());
+        assert!(AppError::RateLimited("GitHub".to_string()).is_transient());
+        assert!(!AppError::NotFound("url".to_string()).is_transient());
+        assert!(!AppError::Unauthorized("url".to_string()).is_transient());
+        assert!(!AppError::NotCached("key".to_string()).is_transient());
+
+        let error = anyhow::Error::from(AppError::NotFound("https://crates.io".to_string()));
+        assert_eq!(short_reason(&error), t!("errors.not_found"));
+        assert!(!short_reason(&error).contains("crates.io"));
+
+        let error = anyhow::anyhow!("something else");
+        assert_eq!(short_reason(&error), "something else");
+    }
+}
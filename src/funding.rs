@@ -0,0 +1,306 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// 赞助平台，与 GitHub GraphQL `FundingPlatform` 的取值一致，
+/// 对应的键为 `FUNDING.yml` 中使用的名称，前缀为赞助页面地址的前缀
+const PLATFORMS: [(&str, &str, &str, &str); 13] = [
+    (
+        "GITHUB",
+        "github",
+        "GitHub Sponsors",
+        "https://github.com/sponsors/",
+    ),
+    ("PATREON", "patreon", "Patreon", "https://www.patreon.com/"),
+    (
+        "OPEN_COLLECTIVE",
+        "open_collective",
+        "Open Collective",
+        "https://opencollective.com/",
+    ),
+    ("KO_FI", "ko_fi", "Ko-fi", "https://ko-fi.com/"),
+    (
+        "TIDELIFT",
+        "tidelift",
+        "Tidelift",
+        "https://tidelift.com/funding/github/",
+    ),
+    (
+        "COMMUNITY_BRIDGE",
+        "community_bridge",
+        "LFX Mentorship",
+        "https://funding.communitybridge.org/projects/",
+    ),
+    (
+        "LIBERAPAY",
+        "liberapay",
+        "Liberapay",
+        "https://liberapay.com/",
+    ),
+    (
+        "ISSUEHUNT",
+        "issuehunt",
+        "IssueHunt",
+        "https://issuehunt.io/r/",
+    ),
+    (
+        "LFX_CROWDFUNDING",
+        "lfx_crowdfunding",
+        "LFX Crowdfunding",
+        "https://crowdfunding.lfx.linuxfoundation.org/projects/",
+    ),
+    ("POLAR", "polar", "Polar", "https://polar.sh/"),
+    (
+        "BUY_ME_A_COFFEE",
+        "buy_me_a_coffee",
+        "Buy Me a Coffee",
+        "https://www.buymeacoffee.com/",
+    ),
+    (
+        "THANKS_DEV",
+        "thanks_dev",
+        "thanks.dev",
+        "https://thanks.dev/",
+    ),
+    ("CUSTOM", "custom", "", ""),
+];
+
+/// 赞助链接，来自仓库的 `.github/FUNDING.yml` 或 crate 的 `[package.metadata.funding]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingLink {
+    /// 赞助平台，例如 `GITHUB`、`OPEN_COLLECTIVE`、`CUSTOM`
+    pub platform: String,
+    pub url: String,
+}
+
+impl FundingLink {
+    pub fn custom(url: &str) -> Self {
+        Self {
+            platform: "CUSTOM".to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    /// 显示的名称，自定义链接使用主机名，例如 `GitHub Sponsors`、`example.com`
+    pub fn label(&self) -> String {
+        match PLATFORMS
+            .iter()
+            .find(|(platform, ..)| *platform == self.platform)
+        {
+            Some((_, _, label, _)) if !label.is_empty() => label.to_string(),
+            _ => Url::parse(&self.url)
+                .ok()
+                .and_then(|url| {
+                    url.host_str()
+                        .map(|host| host.trim_start_matches("www.").to_string())
+                })
+                .unwrap_or_else(|| self.url.clone()),
+        }
+    }
+
+    /// 根据显示的名称还原赞助平台，无法识别的名称视为自定义链接
+    fn from_label(label: &str, url: &str) -> Self {
+        let platform = PLATFORMS
+            .iter()
+            .find(|(_, _, name, _)| !name.is_empty() && *name == label)
+            .map_or("CUSTOM", |(platform, ..)| platform);
+        Self {
+            platform: platform.to_string(),
+            url: url.to_string(),
+        }
+    }
+}
+
+/// `FUNDING.yml` 中的值可以是单个账号或账号列表
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Accounts {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Accounts {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(account) => vec![account],
+            Self::Many(accounts) => accounts,
+        }
+    }
+}
+
+/// 将 `FUNDING.yml` 形式的键值转换为赞助链接，未知的键被忽略
+fn links_from_accounts(accounts: Vec<(String, Accounts)>) -> Vec<FundingLink> {
+    let mut links = vec![];
+    for (key, value) in accounts {
+        let Some((platform, _, _, prefix)) = PLATFORMS.iter().find(|(_, name, ..)| *name == key)
+        else {
+            continue;
+        };
+
+        for account in value.into_vec() {
+            let account = account.trim();
+            if account.is_empty() {
+                continue;
+            }
+            let url = match *platform {
+                "CUSTOM" => account.to_string(),
+                _ => format!("{}{}", prefix, account),
+            };
+            push_unique(
+                &mut links,
+                FundingLink {
+                    platform: platform.to_string(),
+                    url,
+                },
+            );
+        }
+    }
+    links
+}
+
+fn push_unique(links: &mut Vec<FundingLink>, link: FundingLink) {
+    if !links.iter().any(|existing| existing.url == link.url) {
+        links.push(link);
+    }
+}
+
+/// 解析仓库的 `.github/FUNDING.yml`，空值（`~` 或空字符串）被忽略
+pub fn from_funding_yml(content: &str) -> Result<Vec<FundingLink>> {
+    let accounts = serde_yaml::from_str::<serde_yaml::Mapping>(content)?
+        .into_iter()
+        .filter_map(|(key, value)| {
+            Some((
+                key.as_str()?.to_string(),
+                serde_yaml::from_value(value).ok()?,
+            ))
+        })
+        .collect();
+
+    Ok(links_from_accounts(accounts))
+}
+
+/// 读取 crate 的 `[package.metadata.funding]`
+///
+/// 可以是单个地址、地址列表，或者与 `FUNDING.yml` 相同的键值表
+pub fn from_package_metadata(metadata: &serde_json::Value) -> Vec<FundingLink> {
+    match &metadata["funding"] {
+        serde_json::Value::String(url) => vec![FundingLink::custom(url)],
+        serde_json::Value::Array(urls) => urls
+            .iter()
+            .filter_map(|url| url.as_str())
+            .map(FundingLink::custom)
+            .collect(),
+        serde_json::Value::Object(accounts) => links_from_accounts(
+            accounts
+                .iter()
+                .filter_map(|(key, value)| {
+                    Some((key.clone(), serde_json::from_value(value.clone()).ok()?))
+                })
+                .collect(),
+        ),
+        _ => vec![],
+    }
+}
+
+/// 合并两组赞助链接，按地址去重并保持先后顺序
+pub fn merge(links: &mut Vec<FundingLink>, others: Vec<FundingLink>) {
+    for link in others {
+        push_unique(links, link);
+    }
+}
+
+const SEPARATOR: &str = ", ";
+
+/// 输出中的赞助列，例如 `[GitHub Sponsors](https://github.com/sponsors/dtolnay)`
+pub fn to_sponsor_string(links: &[FundingLink]) -> String {
+    links
+        .iter()
+        .map(|link| format!("[{}]({})", link.label(), link.url))
+        .collect::<Vec<_>>()
+        .join(SEPARATOR)
+}
+
+/// 解析 [`to_sponsor_string`] 的输出，无法识别的部分被忽略
+pub fn parse_sponsor_string(s: &str) -> Vec<FundingLink> {
+    s.split(SEPARATOR)
+        .filter_map(|link| {
+            let (label, url) = link.trim().strip_prefix('[')?.split_once("](")?;
+            Some(FundingLink::from_label(label, url.strip_suffix(')')?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_funding_yml() -> Result<()> {
+        let links = from_funding_yml(
+            "# These are supported funding model platforms\n\
+             github: [dtolnay, dtolnay]\n\
+             patreon: # Replace with a single Patreon username\n\
+             open_collective: serde\n\
+             unknown: someone\n\
+             custom: [\"https://example.com/donate\"]\n",
+        )?;
+
+        assert_eq!(
+            links,
+            vec![
+                FundingLink {
+                    platform: "GITHUB".to_string(),
+                    url: "https://github.com/sponsors/dtolnay".to_string(),
+                },
+                FundingLink {
+                    platform: "OPEN_COLLECTIVE".to_string(),
+                    url: "https://opencollective.com/serde".to_string(),
+                },
+                FundingLink::custom("https://example.com/donate"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_package_metadata() {
+        let metadata = serde_json::json!({ "funding": "https://example.com/donate" });
+        assert_eq!(
+            from_package_metadata(&metadata),
+            vec![FundingLink::custom("https://example.com/donate")]
+        );
+
+        let metadata = serde_json::json!({ "funding": { "github": ["a", "b"], "liberapay": "c" } });
+        let links = from_package_metadata(&metadata);
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[2].url, "https://liberapay.com/c");
+
+        assert!(from_package_metadata(&serde_json::Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_sponsor_string() {
+        let links = vec![
+            FundingLink {
+                platform: "GITHUB".to_string(),
+                url: "https://github.com/sponsors/dtolnay".to_string(),
+            },
+            FundingLink::custom("https://www.example.com/donate"),
+        ];
+
+        let s = to_sponsor_string(&links);
+        assert_eq!(
+            s,
+            "[GitHub Sponsors](https://github.com/sponsors/dtolnay), [example.com](https://www.example.com/donate)"
+        );
+        assert_eq!(parse_sponsor_string(&s), links);
+        assert!(parse_sponsor_string("").is_empty());
+
+        let mut merged = links.clone();
+        merge(
+            &mut merged,
+            vec![FundingLink::custom("https://www.example.com/donate")],
+        );
+        assert_eq!(merged, links);
+    }
+}
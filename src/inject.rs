@@ -0,0 +1,144 @@
+use anyhow::Result;
+use rust_i18n::t;
+
+use crate::output::OutputFormat;
+
+/// 标记名称，包裹在各格式的注释中
+const START_MARKER: &str = "thanku:start";
+const END_MARKER: &str = "thanku:end";
+
+/// 各输出格式的起止标记
+///
+/// - Markdown：`<!-- thanku:start -->` / `<!-- thanku:end -->`
+/// - TOML/YAML：`# thanku:start` / `# thanku:end`
+/// - JSON 与 CSV 没有注释语法，无法注入
+pub fn markers(format: OutputFormat) -> Result<(String, String)> {
+    match format {
+        OutputFormat::MarkdownTable | OutputFormat::MarkdownList => Ok((
+            format!("<!-- {} -->", START_MARKER),
+            format!("<!-- {} -->", END_MARKER),
+        )),
+        OutputFormat::Toml | OutputFormat::Yaml => {
+            Ok((format!("# {}", START_MARKER), format!("# {}", END_MARKER)))
+        }
+        OutputFormat::Json | OutputFormat::Csv => {
+            anyhow::bail!(t!("inject.unsupported_format", format = format))
+        }
+    }
+}
+
+/// 标记之间内容的字节范围，不包含标记所在的行
+///
+/// 标记需要单独占一行，允许有缩进；只使用第一对标记
+fn section_range(document: &str, format: OutputFormat) -> Result<std::ops::Range<usize>> {
+    let (start_marker, end_marker) = markers(format)?;
+
+    let mut offset = 0;
+    let mut start = None;
+    for line in document.split_inclusive('\n') {
+        let end = offset + line.len();
+        match (start, line.trim()) {
+            (None, trimmed) if trimmed == start_marker => start = Some(end),
+            (Some(start), trimmed) if trimmed == end_marker => return Ok(start..offset),
+            _ => {}
+        }
+        offset = end;
+    }
+
+    anyhow::bail!(t!(
+        "inject.markers_not_found",
+        start = start_marker,
+        end = end_marker
+    ))
+}
+
+/// 标记之间现有的内容
+pub fn section(document: &str, format: OutputFormat) -> Result<&str> {
+    Ok(&document[section_range(document, format)?])
+}
+
+/// 用新的内容替换标记之间的部分，文档的其它部分保持不变
+pub fn inject(document: &str, format: OutputFormat, content: &str) -> Result<String> {
+    let range = section_range(document, format)?;
+    let content = content.trim_matches('\n');
+
+    let mut injected = String::with_capacity(document.len() + content.len());
+    injected.push_str(&document[..range.start]);
+    if !content.is_empty() {
+        injected.push_str(content);
+        injected.push('\n');
+    }
+    injected.push_str(&document[range.end..]);
+    Ok(injected)
+}
+
+/// 现有内容与新内容之间不同的行，已有的行以 `-` 开头，新的行以 `+` 开头
+///
+/// 只按行比较，不计算最小编辑距离，足以说明哪些内容过期
+pub fn drift<'a>(current: &'a str, expected: &'a str) -> Vec<String> {
+    let lines = |s: &'a str| {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+    };
+    let (current, expected) = (lines(current), lines(expected));
+
+    let removed = current
+        .iter()
+        .filter(|line| !expected.contains(line))
+        .map(|line| format!("- {}", line));
+    let added = expected
+        .iter()
+        .filter(|line| !current.contains(line))
+        .map(|line| format!("+ {}", line));
+    removed.chain(added).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const README: &str =
+        "# Project\n\nIntro\n\n<!-- thanku:start -->\nold\n<!-- thanku:end -->\n\n## License\n";
+
+    #[test]
+    fn test_inject_markdown() -> Result<()> {
+        let injected = inject(README, OutputFormat::MarkdownTable, "\n| new |\n")?;
+        assert_eq!(
+            injected,
+            "# Project\n\nIntro\n\n<!-- thanku:start -->\n| new |\n<!-- thanku:end -->\n\n## License\n"
+        );
+        assert_eq!(section(&injected, OutputFormat::MarkdownList)?, "| new |\n");
+
+        // 再次注入相同的内容时文档不变
+        assert_eq!(
+            inject(&injected, OutputFormat::MarkdownTable, "| new |")?,
+            injected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_inject_comment_styles() -> Result<()> {
+        let document = "name: app\n  # thanku:start\n  # thanku:end\nversion: 1\n";
+        assert_eq!(
+            inject(document, OutputFormat::Yaml, "dependencies: []\n")?,
+            "name: app\n  # thanku:start\ndependencies: []\n  # thanku:end\nversion: 1\n"
+        );
+        assert!(inject(document, OutputFormat::Toml, "").is_ok());
+
+        // Markdown 的标记不适用于 YAML，JSON 没有注释语法
+        assert!(inject(README, OutputFormat::Yaml, "").is_err());
+        assert!(inject(README, OutputFormat::Json, "").is_err());
+        // 只有起始标记
+        assert!(inject("<!-- thanku:start -->\n", OutputFormat::MarkdownTable, "").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_drift() {
+        assert!(drift("a\nb\n", "\na\nb").is_empty());
+        assert_eq!(drift("a\nb\n", "a\nc\n"), vec!["- b", "+ c"]);
+    }
+}
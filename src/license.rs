@@ -0,0 +1,439 @@
+use anyhow::Result;
+use rust_i18n::t;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::{errors::AppError, output::DependencyInfo};
+
+/// SPDX 许可证表达式，例如 `MIT OR Apache-2.0`、`Apache-2.0 WITH LLVM-exception`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpr {
+    /// 单个许可证，`exception` 为 `WITH` 之后的例外条款
+    License {
+        id: String,
+        exception: Option<String>,
+    },
+    And(Box<LicenseExpr>, Box<LicenseExpr>),
+    Or(Box<LicenseExpr>, Box<LicenseExpr>),
+}
+
+impl LicenseExpr {
+    /// 解析 SPDX 表达式，运算符不区分大小写
+    ///
+    /// 兼容旧版本 crates 中使用 `/` 表示 `OR` 的写法，例如 `MIT/Apache-2.0`
+    pub fn parse(s: &str) -> Result<Self> {
+        let tokens = s
+            .replace('/', " OR ")
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        let mut parser = ExprParser { tokens, pos: 0 };
+        let expr = parser.parse_or();
+        match (expr, parser.peek()) {
+            (Some(expr), None) => Ok(expr),
+            _ => Err(AppError::InvalidLicenseExpression(s.to_string()).into()),
+        }
+    }
+
+    /// 表达式中出现的所有许可证，`WITH` 例外条款包含在内
+    pub fn licenses(&self) -> Vec<String> {
+        match self {
+            Self::License { .. } => vec![self.to_string()],
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                let mut licenses = lhs.licenses();
+                licenses.extend(rhs.licenses());
+                licenses
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for LicenseExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::License {
+                id,
+                exception: Some(exception),
+            } => write!(f, "{} WITH {}", id, exception),
+            Self::License { id, .. } => write!(f, "{}", id),
+            Self::And(lhs, rhs) => write!(f, "({} AND {})", lhs, rhs),
+            Self::Or(lhs, rhs) => write!(f, "({} OR {})", lhs, rhs),
+        }
+    }
+}
+
+/// 递归下降解析器，`AND` 的优先级高于 `OR`
+struct ExprParser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|t| t.eq_ignore_ascii_case(keyword)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Option<LicenseExpr> {
+        let mut expr = self.parse_and()?;
+        while self.eat("OR") {
+            expr = LicenseExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Some(expr)
+    }
+
+    fn parse_and(&mut self) -> Option<LicenseExpr> {
+        let mut expr = self.parse_primary()?;
+        while self.eat("AND") {
+            expr = LicenseExpr::And(Box::new(expr), Box::new(self.parse_primary()?));
+        }
+        Some(expr)
+    }
+
+    fn parse_primary(&mut self) -> Option<LicenseExpr> {
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            return self.eat(")").then_some(expr);
+        }
+
+        let id = self.next().filter(|id| !is_operator(id))?;
+        let exception = match self.eat("WITH") {
+            true => Some(self.next().filter(|e| !is_operator(e))?),
+            false => None,
+        };
+        Some(LicenseExpr::License { id, exception })
+    }
+}
+
+fn is_operator(token: &str) -> bool {
+    ["AND", "OR", "WITH", "(", ")"]
+        .iter()
+        .any(|op| token.eq_ignore_ascii_case(op))
+}
+
+/// 许可证策略，从配置文件的 `[licenses]` 表中读取：
+///
+/// ```toml
+/// [licenses]
+/// allow = ["MIT", "Apache-2.0"]
+/// deny = ["GPL-3.0-only"]
+/// ```
+///
+/// - `allow` 为空时允许所有未被拒绝的许可证
+/// - `allow` 不为空时，没有许可证信息的依赖视为违规
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LicensePolicy {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// 未指定策略文件时，在 Cargo.toml 同目录下查找的文件名
+pub const DEFAULT_POLICY_FILE: &str = "thanku.toml";
+
+/// 只声明了 `license-file` 的包使用 `SEE LICENSE IN <file>` 作为许可证
+pub const LICENSE_FILE_PREFIX: &str = "SEE LICENSE IN ";
+
+/// 配置文件，目前只包含许可证策略
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    #[serde(default)]
+    licenses: LicensePolicy,
+}
+
+/// 依赖不符合许可证策略的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseViolation {
+    /// 没有许可证信息
+    Unknown,
+    /// 无法解析的 SPDX 表达式
+    Invalid(String),
+    /// 只有许可证文件的自定义许可证，无法判断是否在允许列表中
+    LicenseFile(String),
+    /// 所有可选的许可证组合中都包含被拒绝的许可证
+    Denied(Vec<String>),
+    /// 所有可选的许可证组合中都包含不在允许列表中的许可证
+    NotAllowed(Vec<String>),
+}
+
+impl std::fmt::Display for LicenseViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "{}", t!("license.violation_unknown")),
+            Self::Invalid(expr) => write!(f, "{}", t!("license.violation_invalid", expr = expr)),
+            Self::LicenseFile(file) => {
+                write!(f, "{}", t!("license.violation_license_file", file = file))
+            }
+            Self::Denied(licenses) => write!(
+                f,
+                "{}",
+                t!("license.violation_denied", licenses = licenses.join(", "))
+            ),
+            Self::NotAllowed(licenses) => write!(
+                f,
+                "{}",
+                t!(
+                    "license.violation_not_allowed",
+                    licenses = licenses.join(", ")
+                )
+            ),
+        }
+    }
+}
+
+impl LicensePolicy {
+    /// 从配置文件中读取策略
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!(t!(
+                "license.policy_read_failed",
+                path = path.display(),
+                error = e.to_string()
+            ))
+        })?;
+        Self::from_toml(&content).map_err(|e| {
+            anyhow::anyhow!(t!(
+                "license.policy_invalid",
+                path = path.display(),
+                error = e.to_string()
+            ))
+        })
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let file = toml::from_str::<PolicyFile>(content)?;
+        Ok(file.licenses)
+    }
+
+    /// 单个许可证是否被拒绝，`WITH` 例外条款可以整体匹配，也可以只匹配许可证本身
+    fn is_denied(&self, id: &str, full: &str) -> bool {
+        contains(&self.deny, full) || contains(&self.deny, id)
+    }
+
+    fn is_allowed(&self, id: &str, full: &str) -> bool {
+        !self.is_denied(id, full)
+            && (self.allow.is_empty() || contains(&self.allow, full) || contains(&self.allow, id))
+    }
+
+    /// 表达式是否满足策略：`OR` 只需要满足任意一边，`AND` 需要两边都满足
+    fn satisfies(&self, expr: &LicenseExpr) -> bool {
+        match expr {
+            LicenseExpr::License { id, .. } => self.is_allowed(id, &expr.to_string()),
+            LicenseExpr::And(lhs, rhs) => self.satisfies(lhs) && self.satisfies(rhs),
+            LicenseExpr::Or(lhs, rhs) => self.satisfies(lhs) || self.satisfies(rhs),
+        }
+    }
+
+    /// 检查一个许可证表达式，符合策略时返回 `None`
+    pub fn check(&self, license: Option<&str>) -> Option<LicenseViolation> {
+        let Some(license) = license else {
+            return (!self.allow.is_empty()).then_some(LicenseViolation::Unknown);
+        };
+        // 自定义许可证与未知许可证一样，只有存在允许列表时才视为违规
+        if let Some(file) = license.strip_prefix(LICENSE_FILE_PREFIX) {
+            return (!self.allow.is_empty())
+                .then(|| LicenseViolation::LicenseFile(file.to_string()));
+        }
+        let Ok(expr) = LicenseExpr::parse(license) else {
+            return Some(LicenseViolation::Invalid(license.to_string()));
+        };
+        if self.satisfies(&expr) {
+            return None;
+        }
+
+        let (denied, not_allowed): (Vec<_>, Vec<_>) = expr
+            .licenses()
+            .into_iter()
+            .filter(|full| {
+                let id = full.split(" WITH ").next().unwrap_or(full);
+                !self.is_allowed(id, full)
+            })
+            .partition(|full| {
+                let id = full.split(" WITH ").next().unwrap_or(full);
+                self.is_denied(id, full)
+            });
+
+        match denied.is_empty() {
+            true => Some(LicenseViolation::NotAllowed(not_allowed)),
+            false => Some(LicenseViolation::Denied(denied)),
+        }
+    }
+}
+
+fn contains(list: &[String], license: &str) -> bool {
+    list.iter().any(|l| l.eq_ignore_ascii_case(license))
+}
+
+/// 找出不符合策略的依赖，并将原因记录在状态中
+pub fn check_dependencies(deps: &[DependencyInfo], policy: &LicensePolicy) -> Vec<DependencyInfo> {
+    deps.iter()
+        .filter_map(|dep| {
+            let violation = policy.check(dep.license.as_deref())?;
+            Some(DependencyInfo {
+                failed: true,
+                error_message: Some(violation.to_string()),
+                ..dep.clone()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn license(id: &str) -> LicenseExpr {
+        LicenseExpr::License {
+            id: id.to_string(),
+            exception: None,
+        }
+    }
+
+    fn policy(allow: &[&str], deny: &[&str]) -> LicensePolicy {
+        LicensePolicy {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_license_expr() -> Result<()> {
+        assert_eq!(LicenseExpr::parse("MIT")?, license("MIT"));
+        assert_eq!(
+            LicenseExpr::parse("MIT OR Apache-2.0")?,
+            LicenseExpr::Or(Box::new(license("MIT")), Box::new(license("Apache-2.0")))
+        );
+        // 旧写法与小写运算符
+        assert_eq!(
+            LicenseExpr::parse("MIT/Apache-2.0")?,
+            LicenseExpr::parse("MIT or Apache-2.0")?
+        );
+        // AND 的优先级高于 OR
+        assert_eq!(
+            LicenseExpr::parse("MIT OR Apache-2.0 AND BSD-3-Clause")?.to_string(),
+            "(MIT OR (Apache-2.0 AND BSD-3-Clause))"
+        );
+        assert_eq!(
+            LicenseExpr::parse("(MIT OR Apache-2.0) AND Unicode-3.0")?.to_string(),
+            "((MIT OR Apache-2.0) AND Unicode-3.0)"
+        );
+        assert_eq!(
+            LicenseExpr::parse("Apache-2.0 WITH LLVM-exception OR MIT")?.licenses(),
+            vec!["Apache-2.0 WITH LLVM-exception", "MIT"]
+        );
+
+        for invalid in ["", "MIT OR", "(MIT", "MIT Apache-2.0", "AND", "MIT WITH"] {
+            assert!(LicenseExpr::parse(invalid).is_err(), "{}", invalid);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_from_toml() -> Result<()> {
+        let policy = LicensePolicy::from_toml(
+            r#"
+            [licenses]
+            allow = ["MIT", "Apache-2.0"]
+            deny = ["GPL-3.0-only"]
+            "#,
+        )?;
+        assert_eq!(policy.allow, vec!["MIT", "Apache-2.0"]);
+        assert_eq!(policy.deny, vec!["GPL-3.0-only"]);
+
+        assert_eq!(LicensePolicy::from_toml("")?, LicensePolicy::default());
+        assert!(LicensePolicy::from_toml("[licenses]\nallowed = []").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_check() {
+        let policy = policy(&["MIT", "Apache-2.0"], &["GPL-3.0-only"]);
+
+        assert_eq!(policy.check(Some("MIT")), None);
+        assert_eq!(policy.check(Some("MIT OR GPL-3.0-only")), None);
+        assert_eq!(policy.check(Some("Apache-2.0 WITH LLVM-exception")), None);
+        assert_eq!(
+            policy.check(Some("MIT AND GPL-3.0-only")),
+            Some(LicenseViolation::Denied(vec!["GPL-3.0-only".to_string()]))
+        );
+        assert_eq!(
+            policy.check(Some("BSD-3-Clause OR Zlib")),
+            Some(LicenseViolation::NotAllowed(vec![
+                "BSD-3-Clause".to_string(),
+                "Zlib".to_string()
+            ]))
+        );
+        assert_eq!(policy.check(None), Some(LicenseViolation::Unknown));
+        assert_eq!(
+            policy.check(Some("MIT OR")),
+            Some(LicenseViolation::Invalid("MIT OR".to_string()))
+        );
+
+        // 只有拒绝列表时，其它许可证与未知许可证都是允许的
+        let policy = self::policy(&[], &["GPL-3.0-only"]);
+        assert_eq!(policy.check(Some("BSD-3-Clause")), None);
+        assert_eq!(policy.check(None), None);
+        assert!(policy.check(Some("gpl-3.0-only")).is_some());
+    }
+
+    #[test]
+    fn test_policy_check_license_file() {
+        let license = format!("{}LICENSE.txt", LICENSE_FILE_PREFIX);
+
+        // 只有拒绝列表时与没有许可证信息一样允许
+        let policy = policy(&[], &["GPL-3.0-only"]);
+        assert_eq!(policy.check(Some(&license)), None);
+
+        let policy = self::policy(&["MIT"], &[]);
+        let violation = policy.check(Some(&license));
+        assert_eq!(
+            violation,
+            Some(LicenseViolation::LicenseFile("LICENSE.txt".to_string()))
+        );
+        assert!(violation.unwrap().to_string().contains("LICENSE.txt"));
+    }
+
+    #[test]
+    fn test_check_dependencies() {
+        let dep = |name: &str, license: Option<&str>| DependencyInfo {
+            name: name.to_string(),
+            license: license.map(str::to_string),
+            ..Default::default()
+        };
+        let deps = vec![
+            dep("serde", Some("MIT OR Apache-2.0")),
+            dep("readline", Some("GPL-3.0-only")),
+            dep("ring", None),
+        ];
+
+        let violations = check_dependencies(&deps, &policy(&["MIT"], &["GPL-3.0-only"]));
+        assert_eq!(
+            violations
+                .iter()
+                .map(|dep| dep.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["readline", "ring"]
+        );
+        assert!(violations.iter().all(|dep| dep.failed));
+        assert!(violations.iter().all(|dep| dep.error_message.is_some()));
+    }
+}
@@ -1,30 +1,54 @@
+mod check;
 mod cli;
 mod config;
+mod deps;
 mod errors;
+mod funding;
+mod inject;
+mod license;
+mod maintainers;
+mod notice;
+mod outdated;
 mod output;
+mod registries;
 mod sources;
+mod star;
 mod travert;
 
 use anyhow::Result;
-use cargo_metadata::MetadataCommand;
-use output::{DependencyKind, OutputFormat};
+use output::{DependencyKind, DependencyKinds, OutputFormat};
 use rust_i18n::t;
-use tracing::{Level, debug, info, instrument};
+use tracing::{Level, debug, info, instrument, warn};
 use travert::Converter;
 use url::Url;
 
 use futures::future::join_all;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use std::{collections::HashMap, path::PathBuf};
 use tokio::sync::Semaphore;
 
 use crate::{
+    check::Difference,
     cli::{build_cli, generate_completions},
-    config::Config,
+    config::{Config, DepthLimit, WriteMode},
+    deps::{DependencyOrigin, ResolvedDependency, get_dependencies},
     errors::AppError,
-    output::{DependencyInfo, DependencyStats, OutputManager},
-    sources::{CratesioClient, GitHubClient},
+    license::LicensePolicy,
+    maintainers::Maintainer,
+    notice::{Notice, NoticeCrate},
+    outdated::OutdatedInfo,
+    output::{
+        DependencyInfo, DependencyStats, DependencyStatus, FormatOptions, Formatter, OutputManager,
+    },
+    registries::Registries,
+    sources::{
+        ApiSource, CrateInfo, CratesioClient, GitHubClient, MetadataSource, RepositoryInfo,
+        hosts::{BitbucketClient, GitLabClient, GiteaClient, RepositoryRef},
+        registry::RegistryClient,
+    },
 };
 
 #[macro_use]
@@ -54,29 +78,47 @@ async fn main() -> Result<()> {
     init_log(if verbose { Level::DEBUG } else { Level::INFO })?;
 
     // Initialize global config
-    let config = Config::from_matches(&matches)?;
+    // 通过 `cargo thanku` 调用时，参数位于隐藏的 thanku 子命令中
+    // check-licenses、notice 与 outdated 子命令复用 thanku 的参数
+    let thanku_matches = match matches.subcommand() {
+        Some(("thanku" | "check-licenses" | "notice" | "outdated", sub_matches)) => sub_matches,
+        _ => &matches,
+    };
+    let config = Config::from_matches(thanku_matches)?;
     Config::init(config)?;
 
     // Handle subcommand
     if let Some(matches) = matches.subcommand_matches("completions") {
-        handle_completions(&matches)?;
+        handle_completions(matches)?;
         return Ok(());
     }
 
     #[cfg(debug_assertions)]
     {
         if let Some(matches) = matches.subcommand_matches("test") {
-            handle_test(&matches)?;
+            handle_test(matches)?;
             return Ok(());
         }
     }
 
     if let Some(matches) = matches.subcommand_matches("convert") {
-        handle_convert(&matches)?;
+        handle_convert(matches)?;
         return Ok(());
     }
 
-    process_dependencies().await
+    if let Some(matches) = matches.subcommand_matches("check-licenses") {
+        return handle_check_licenses(matches);
+    }
+
+    if matches.subcommand_matches("notice").is_some() {
+        return handle_notice();
+    }
+
+    if let Some(matches) = matches.subcommand_matches("outdated") {
+        return handle_outdated(matches).await;
+    }
+
+    handle_thanku().await
 }
 
 fn handle_convert(matches: &clap::ArgMatches) -> Result<()> {
@@ -119,7 +161,6 @@ fn handle_convert(matches: &clap::ArgMatches) -> Result<()> {
     let outputs = matches
         .get_many::<String>("outputs")
         .unwrap_or_default()
-        .into_iter()
         .map(|format| format.parse::<OutputFormat>().unwrap_or_default())
         .collect::<Vec<_>>();
 
@@ -131,16 +172,25 @@ fn handle_convert(matches: &clap::ArgMatches) -> Result<()> {
                 name.trim_end_matches(['_']),
                 format.to_identifier()
             );
-            let path = output_dir.join(format!(
+
+            output_dir.join(format!(
                 "{}.{}",
                 file_name.trim_end_matches(['_']),
                 format.to_extension()
-            ));
-            path
+            ))
         })
         .collect();
 
-    let converter = Converter::new(input, &outputs)?;
+    let depth = matches
+        .get_one::<String>("depth")
+        .map(|d| d.parse::<DepthLimit>())
+        .transpose()?;
+
+    let mut converter = Converter::new(input, &outputs)?;
+    converter.options = FormatOptions {
+        depth,
+        ..Default::default()
+    };
 
     converter.convert()?;
 
@@ -204,91 +254,444 @@ fn handle_test(matches: &clap::ArgMatches) -> Result<()> {
     return Ok(());
 }
 
+/// 生成致谢文档，并在需要时为依赖所在的仓库点赞
 #[instrument(skip_all)]
-fn get_dependencies<P>(cargo_toml_path: P) -> Result<HashMap<String, cargo_metadata::Dependency>>
-where
-    P: AsRef<std::path::Path>,
-{
-    // Get cargo metadata
-    let metadata = MetadataCommand::new()
-        .manifest_path(cargo_toml_path.as_ref())
-        .no_deps() // 只获取当前包的依赖
-        .exec()
-        .map_err(AppError::MetadataError)?;
+async fn handle_thanku() -> Result<()> {
+    let config = Config::global()?;
 
-    // Collect all dependencies
-    let mut deps = HashMap::new();
-    for pkg in &metadata.packages {
-        for dep in &pkg.dependencies {
-            deps.entry(dep.name.clone()).or_insert_with(|| dep.clone());
-        }
+    if config.checks_without_network() {
+        return generate_output(&local_dependencies(config)?, &config.format);
     }
 
-    debug!("{}", t!("main.found_dependencies", count = deps.len()));
-    Ok(deps)
+    // Initialize clients
+    let cache = config.get_cache()?;
+    let crates_io_client = CratesioClient::new()?
+        .with_base_url(&config.crates_io_api)
+        .with_cache(cache.clone());
+    let github_client = match (&config.github_token, config.offline) {
+        (Some(token), _) => Some(GitHubClient::new(Some(token))?),
+        // 离线时不需要令牌也可以读取缓存的仓库信息
+        (None, true) => Some(GitHubClient::new(None)?),
+        (None, false) => None,
+    }
+    .map(|client| {
+        client
+            .with_base_url(&config.github_api)
+            .with_cache(cache.clone())
+    });
+
+    // 其它托管平台的公共 API 不需要令牌
+    let gitlab_client = GitLabClient::new(
+        config.gitlab_token.as_deref(),
+        config.gitlab_host.as_deref(),
+    )?
+    .with_cache(cache.clone());
+    let gitea_client =
+        GiteaClient::new(config.gitea_token.as_deref(), config.gitea_host.as_deref())?
+            .with_cache(cache.clone());
+    let bitbucket_client =
+        BitbucketClient::new(config.bitbucket_token.as_deref())?.with_cache(cache.clone());
+
+    // Cargo 配置中的替代注册表，令牌来自 Cargo 的凭据文件
+    let manifest_path = config.get_cargo_toml_path()?;
+    let registry_clients = Registries::load(manifest_path.parent().unwrap_or(Path::new("")))
+        .iter()
+        .map(|registry| Ok(RegistryClient::new(registry)?.with_cache(cache.clone())))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 点赞需要 GitHub 令牌，在请求之前尽早失败
+    if config.star && !config.dry_run && github_client.is_none() {
+        anyhow::bail!(t!("star.token_required"));
+    }
+
+    let source = Arc::new(
+        ApiSource::new(crates_io_client, github_client)
+            .with_gitlab(Some(gitlab_client))
+            .with_gitea(Some(gitea_client))
+            .with_bitbucket(Some(bitbucket_client))
+            .with_registries(registry_clients),
+    );
+    let results = process_dependencies(config, Arc::clone(&source)).await?;
+
+    // Generate output
+    generate_output(&results, &config.format)?;
+
+    if config.star {
+        let (targets, excluded) = star::collect_targets(&results, &config.star_exclude);
+        let mut report = star::star_repositories(source.github(), &targets, config.dry_run).await;
+        report.excluded = excluded;
+        report.log();
+    }
+    source.log_rate_limits();
+
+    check_fail_on(&results, &config.fail_on)
 }
 
-/// TODO: Check the concurrent requests
+/// 根据许可证策略检查依赖的许可证，违规的依赖通过格式化器输出
+///
+/// 许可证信息来自 cargo metadata，不需要请求网络
 #[instrument(skip_all)]
-async fn process_dependencies() -> Result<()> {
+fn handle_check_licenses(matches: &clap::ArgMatches) -> Result<()> {
     let config = Config::global()?;
+    let manifest_path = config.get_cargo_toml_path()?;
 
-    // Get cargo metadata
-    let mut deps = get_dependencies(&config.get_cargo_toml_path()?)?;
+    let policy_path = match matches.get_one::<PathBuf>("policy") {
+        Some(path) => path.clone(),
+        None => manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(license::DEFAULT_POLICY_FILE),
+    };
+    let policy = LicensePolicy::from_file(&policy_path)?;
+
+    let mut deps = local_dependencies(config)?;
+    deps.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let violations = license::check_dependencies(&deps, &policy);
+    if violations.is_empty() {
+        info!("{}", t!("license.check_passed", count = deps.len()));
+        return Ok(());
+    }
+
+    generate_output(&violations, &config.format)?;
+    anyhow::bail!(t!("license.check_failed", count = violations.len()));
+}
+
+/// 生成包含所有依赖许可证全文的 NOTICE 文档
+///
+/// 许可证文件来自各个包的源码目录，不需要请求网络
+#[instrument(skip_all)]
+fn handle_notice() -> Result<()> {
+    let config = Config::global()?;
+
+    let crates = resolve_dependencies(config)?
+        .into_iter()
+        .map(|(name, resolved)| NoticeCrate {
+            name,
+            license: resolved.license,
+            manifest_path: resolved.manifest_path,
+            license_file: resolved.license_file,
+        })
+        .collect::<Vec<_>>();
+    let notice = Notice::collect(&crates)?;
+
+    let mut output = config.get_output_writer()?;
+    output.write_all(notice.render().as_bytes())?;
+    output.flush()?;
+    Ok(())
+}
+
+/// 读取 cargo metadata 中的依赖，按配置过滤本地路径依赖
+fn resolve_dependencies(config: &Config) -> Result<HashMap<String, ResolvedDependency>> {
+    let mut deps = get_dependencies(
+        &config.get_cargo_toml_path()?,
+        config.depth,
+        config.offline,
+        &config.packages,
+    )?;
     debug!("{}", t!("main.found_dependencies", count = deps.len()));
 
     if config.no_relative_libs {
         debug!("{}", t!("main.filtering_relative_libs"));
-        deps.retain(|_, dep| dep.path.is_none());
+        deps.retain(|_, resolved| resolved.dependency.path.is_none());
     }
+    Ok(deps)
+}
 
-    // Initialize clients
-    let crates_io_client = Arc::new(CratesioClient::new());
-    let github_client = if let Some(token) = &config.github_token {
-        Some(Arc::new(GitHubClient::new(token)?))
-    } else {
-        None
+/// 只使用 cargo metadata 与依赖包自身 Cargo.toml 中的信息，不请求网络
+fn local_dependencies(config: &Config) -> Result<Vec<DependencyInfo>> {
+    Ok(resolve_dependencies(config)?
+        .into_iter()
+        .map(|(name, resolved)| local_dependency_info(&name, resolved))
+        .collect())
+}
+
+/// 将解析得到的依赖版本与 crates.io 上的最新稳定版本比较，结果通过格式化器输出
+///
+/// 默认只输出已过期、已撤回或无法获取发布信息的依赖，`--all` 时输出全部依赖
+#[instrument(skip_all)]
+async fn handle_outdated(matches: &clap::ArgMatches) -> Result<()> {
+    let config = Config::global()?;
+
+    let crates_io_client = CratesioClient::new()?
+        .with_base_url(&config.crates_io_api)
+        .with_cache(config.get_cache()?);
+    let source = Arc::new(ApiSource::new(crates_io_client, None));
+    let results = process_outdated(config, Arc::clone(&source)).await?;
+    source.log_rate_limits();
+
+    let outdated = |dep: &DependencyInfo| {
+        dep.outdated
+            .as_ref()
+            .is_some_and(OutdatedInfo::needs_attention)
+    };
+    info!(
+        "{}",
+        t!(
+            "outdated.summary",
+            count = results.iter().filter(|dep| outdated(dep)).count(),
+            total = results.len()
+        )
+    );
+
+    let results = match matches.get_flag("all") {
+        true => results,
+        false => results
+            .into_iter()
+            .filter(|dep| dep.failed || outdated(dep))
+            .collect(),
+    };
+
+    generate_output(&results, &config.format)
+}
+
+/// 获取每个依赖在 crates.io 上的发布信息，并与解析得到的版本比较
+///
+/// 替代注册表、git 与本地路径依赖没有 crates.io 上的发布信息，总是被跳过
+#[instrument(skip_all)]
+async fn process_outdated<S: MetadataSource>(
+    config: &Config,
+    source: Arc<S>,
+) -> Result<Vec<DependencyInfo>> {
+    let deps = resolve_dependencies(config)?;
+
+    let semaphore = Arc::new(Semaphore::new(config.max_concurrent_requests));
+    let tasks = deps
+        .into_iter()
+        .filter(|(_, resolved)| resolved.origin.is_crates_io())
+        .map(|(name, resolved)| {
+            let local = local_dependency_info(&name, resolved);
+            let source = Arc::clone(&source);
+            let semaphore = Arc::clone(&semaphore);
+
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire().await.unwrap();
+                match source.get_crate_releases(&name).await {
+                    // 解析得到多个版本时与最旧的版本比较
+                    Ok(releases) => DependencyInfo {
+                        outdated: Some(OutdatedInfo::from_releases(
+                            local.versions.first().map(String::as_str),
+                            &releases,
+                        )),
+                        ..local
+                    },
+                    Err(e) => {
+                        debug!(
+                            "{}",
+                            t!(
+                                "outdated.releases_failed",
+                                name = name,
+                                error = e.to_string()
+                            )
+                        );
+                        DependencyInfo {
+                            failed: true,
+                            error_message: Some(errors::short_reason(&e)),
+                            ..local
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = join_all(tasks)
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(dep) => Some(dep),
+            Err(e) => {
+                debug!("{}", t!("main.task_failed", error = e));
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(results)
+}
+
+/// 只使用 cargo metadata 中的信息构建依赖项，不请求网络
+///
+/// 替代注册表、git 与本地路径依赖没有 crates.io 页面，来源类型分别为注册表名称、`Git` 与 `Path`，
+/// 来源链接使用 git 仓库或 Cargo.toml 中声明的仓库地址
+fn local_dependency_info(name: &str, resolved: ResolvedDependency) -> DependencyInfo {
+    let mut registry = None;
+    let (crate_url, source_type, source_url) = match resolved.origin {
+        DependencyOrigin::Registry => {
+            let crate_url = CratesioClient::get_crate_url(name);
+            let (source_type, source_url) = match repository_source(resolved.repository.as_deref())
+            {
+                Some((source_type, url)) => (source_type, url),
+                None => ("crates.io".to_string(), crate_url.clone()),
+            };
+            (Some(crate_url), source_type, Some(source_url))
+        }
+        DependencyOrigin::AltRegistry { name, .. } => {
+            registry = Some(name.clone());
+            (
+                None,
+                name,
+                resolved.repository.clone().or(resolved.homepage.clone()),
+            )
+        }
+        DependencyOrigin::Git { url } => (None, DependencyInfo::GIT_SOURCE.to_string(), Some(url)),
+        DependencyOrigin::Path => (
+            None,
+            DependencyInfo::PATH_SOURCE.to_string(),
+            resolved.repository.or(resolved.homepage.clone()),
+        ),
+    };
+
+    DependencyInfo {
+        name: name.to_string(),
+        description: resolved.description,
+        dependency_kind: resolved
+            .kinds
+            .iter()
+            .map(|kind| DependencyKind::from(*kind))
+            .collect::<DependencyKinds>(),
+        crate_url,
+        source_type,
+        source_url,
+        homepage: resolved.homepage,
+        documentation: resolved.documentation,
+        versions: resolved.versions,
+        version_req: Some(resolved.dependency.req.to_string()),
+        depth: resolved.depth,
+        parents: resolved.parents,
+        used_by: resolved.members,
+        targets: resolved.targets,
+        license: resolved.license,
+        funding: resolved.funding,
+        registry,
+        authors: resolved.authors,
+        ..Default::default()
+    }
+}
+
+/// 根据 Cargo.toml 中声明的仓库地址确定来源类型与来源链接，地址无效时返回 `None`
+///
+/// 已知托管平台上的仓库使用平台名称，例如 `GitHub`，其它地址为 `Source`
+fn repository_source(repository: Option<&str>) -> Option<(String, String)> {
+    let url = Url::parse(repository?).ok()?;
+    let source_type = match RepositoryRef::from_url(&url) {
+        Some(repo_ref) => repo_ref.source_type().to_string(),
+        None => "Source".to_string(),
     };
+    Some((source_type, url.to_string()))
+}
+
+/// TODO: Check the concurrent requests
+#[instrument(skip_all)]
+async fn process_dependencies<S: MetadataSource>(
+    config: &Config,
+    source: Arc<S>,
+) -> Result<Vec<DependencyInfo>> {
+    // Get cargo metadata
+    let deps = resolve_dependencies(config)?;
+
+    // 按 Cargo.toml 中声明的仓库地址与 git 依赖的仓库批量获取仓库信息，离线时只使用缓存
+    if !config.offline {
+        let repos = deps
+            .values()
+            .filter_map(|resolved| match &resolved.origin {
+                DependencyOrigin::Registry | DependencyOrigin::AltRegistry { .. } => {
+                    resolved.repository.as_deref()
+                }
+                DependencyOrigin::Git { url } => Some(url.as_str()),
+                DependencyOrigin::Path => None,
+            })
+            .filter_map(|repository| Url::parse(repository).ok())
+            .filter_map(|url| RepositoryRef::from_url(&url))
+            .collect::<Vec<_>>();
+        source.prefetch_repositories(&repos).await;
+    }
 
     // Create semaphore to limit concurrent requests
     let semaphore = Arc::new(Semaphore::new(config.max_concurrent_requests));
     // Create tasks
-    let mut tasks: Vec<tokio::task::JoinHandle<Result<(String, DependencyInfo), AppError>>> =
-        Vec::new();
-    for (name, dep) in deps {
-        let name = name.clone();
-        let dep_kind = dep.kind;
-        let crates_io_client = Arc::clone(&crates_io_client);
-        let github_client = github_client.as_ref().map(Arc::clone);
+    let mut tasks: Vec<tokio::task::JoinHandle<Result<DependencyInfo, AppError>>> = Vec::new();
+    for (name, resolved) in deps {
+        let local = local_dependency_info(&name, resolved);
+        let source = Arc::clone(&source);
         let semaphore = Arc::clone(&semaphore);
         let max_retries = config.max_retries;
+        let maintainers = config.maintainers.then_some(config.contributors);
+        let local_metadata = config.local_metadata;
+        let check_yanked = config.fail_on.contains(&DependencyStatus::Yanked);
 
         let task = tokio::spawn(async move {
             let _permit = semaphore.acquire().await.unwrap();
+            if local.is_unregistered() || local_metadata {
+                return Ok(process_local_dependency(
+                    local,
+                    maintainers,
+                    check_yanked,
+                    source.as_ref(),
+                )
+                .await);
+            }
+
             let mut last_error = None;
 
             for retry in 0..=max_retries {
-                match process_dependency(
-                    &name,
-                    dep_kind.into(),
-                    &crates_io_client,
-                    github_client.as_deref(),
-                )
-                .await
-                {
-                    Ok(info) => {
+                let version = local.latest_version();
+                let result = match &local.registry {
+                    Some(registry) => {
+                        process_registry_dependency(&local, registry, source.as_ref()).await
+                    }
+                    None => {
+                        process_dependency(
+                            &name,
+                            version,
+                            local.dependency_kind.clone(),
+                            source.as_ref(),
+                        )
+                        .await
+                    }
+                };
+                match result {
+                    Ok(mut info) => {
+                        info.versions = local.versions;
+                        info.version_req = local.version_req;
+                        info.depth = local.depth;
+                        info.parents = local.parents;
+                        info.used_by = local.used_by;
+                        info.targets = local.targets;
+                        info.license = local.license;
+                        info.authors = local.authors;
+                        // crate 自身声明的赞助链接在前，仓库中的在后
+                        let repository_funding = std::mem::take(&mut info.funding);
+                        info.funding = local.funding;
+                        funding::merge(&mut info.funding, repository_funding);
+                        if let Some(contributors) = maintainers {
+                            info.maintainers =
+                                dependency_maintainers(&info, contributors, source.as_ref()).await;
+                        }
                         if retry > 0 {
                             debug!(
                                 "{}",
                                 t!("main.retry_succeeded", name = name, attempt = retry + 1)
                             );
                         }
-                        return Ok((name, info));
+                        return Ok(info);
                     }
                     Err(e) => {
+                        let error = e.downcast_ref::<AppError>();
+                        // 只重试临时性的错误，例如 crate 不存在或离线时缓存未命中，重试也无济于事
+                        let transient = error.is_some_and(AppError::is_transient);
+                        // 被限流时由共享的限流器暂停所有请求，不需要额外等待
+                        let rate_limited = matches!(error, Some(AppError::RateLimited(_)));
                         last_error = Some(e);
+                        if !transient {
+                            break;
+                        }
                         if retry < max_retries {
-                            let delay = Duration::from_secs(2u64.pow(retry));
+                            let delay = match rate_limited {
+                                true => Duration::ZERO,
+                                false => Duration::from_secs(2u64.pow(retry)),
+                            };
                             debug!(
                                 "{}",
                                 t!(
@@ -306,30 +709,22 @@ async fn process_dependencies() -> Result<()> {
                 }
             }
 
-            // 创建一个表示失败的 DependencyInfo
-            let error_msg = last_error.unwrap().to_string();
+            // 创建一个表示失败的 DependencyInfo，状态列只显示简短的原因
+            let last_error = last_error.unwrap();
             debug!(
                 "{}",
-                t!("main.max_retries_exceeded", name = name, error = error_msg)
+                t!(
+                    "main.max_retries_exceeded",
+                    name = name,
+                    error = last_error.to_string()
+                )
             );
 
-            Ok((
-                name.clone(),
-                DependencyInfo {
-                    name: name.clone(),
-                    dependency_kind: dep_kind.into(),
-                    description: None,
-                    crate_url: Some(CratesioClient::get_crate_url(&name)),
-                    source_type: "Unknown".to_string(),
-                    source_url: None,
-                    stats: DependencyStats {
-                        stars: None,
-                        downloads: None,
-                    },
-                    failed: true,
-                    error_message: Some(error_msg),
-                },
-            ))
+            Ok(DependencyInfo {
+                failed: true,
+                error_message: Some(errors::short_reason(&last_error)),
+                ..local
+            })
         });
 
         tasks.push(task);
@@ -352,86 +747,42 @@ async fn process_dependencies() -> Result<()> {
         })
         .collect();
 
-    // Generate output
-    let format = config.format;
-    generate_output(&results, &format)?;
-
-    Ok(())
+    Ok(results)
 }
 
-#[instrument(skip(crates_io_client, github_client))]
-async fn process_dependency(
+#[instrument(skip(source))]
+async fn process_dependency<S: MetadataSource>(
     name: &str,
-    dep_kind: DependencyKind,
-    crates_io_client: &CratesioClient,
-    github_client: Option<&GitHubClient>,
+    version: Option<&str>,
+    dep_kinds: DependencyKinds,
+    source: &S,
 ) -> Result<DependencyInfo> {
-    // Get crate information from crates.io
-    let crate_info = crates_io_client.get_crate_info(name).await?;
+    // Get crate information from crates.io, for the resolved version if known
+    let crate_info = source.get_crate_info(name, version).await?;
+    let mut status = dependency_status(name, version, &crate_info, source).await;
 
     // Get repository URL if available
+    let mut funding = vec![];
     let (source_type, source_url, stats) = if let Some(repo) = crate_info.repository.as_ref() {
         if let Ok(url) = Url::parse(repo) {
-            if url.host_str() == Some("github.com") {
-                // Extract owner and repo from GitHub URL
-                let path_segments: Vec<&str> = url
-                    .path_segments()
-                    .map(|segments| segments.collect())
-                    .unwrap_or_default();
-
-                if path_segments.len() >= 2 {
-                    let owner = path_segments[0];
-                    let repo = path_segments[1];
-
-                    // Get GitHub information if client is available
-                    if let Some(client) = github_client {
-                        match client.get_repository_info(owner, repo).await {
-                            Ok(repo_info) => {
-                                // Try to star the repository
-                                let _ = client.star_repository(owner, repo).await;
-                                info!("💖 {} {}", name, repo_info.html_url);
-
-                                (
-                                    "GitHub".to_string(),
-                                    Some(url.to_string()),
-                                    DependencyStats {
-                                        stars: Some(repo_info.stargazers_count),
-                                        downloads: None,
-                                    },
-                                )
-                            }
-                            Err(e) => {
-                                debug!("{}", t!("main.github_api_error", error = e.to_string()));
-                                (
-                                    "GitHub".to_string(),
-                                    Some(url.to_string()),
-                                    DependencyStats {
-                                        stars: None,
-                                        downloads: None,
-                                    },
-                                )
-                            }
-                        }
-                    } else {
-                        (
-                            "GitHub".to_string(),
-                            Some(url.to_string()),
-                            DependencyStats {
-                                stars: None,
-                                downloads: None,
-                            },
-                        )
-                    }
-                } else {
-                    (
-                        "Source".to_string(),
-                        Some(url.to_string()),
-                        DependencyStats {
-                            stars: None,
-                            downloads: None,
-                        },
-                    )
-                }
+            if let Some(repo_ref) = RepositoryRef::from_url(&url) {
+                // Get repository information from the hosting service if available
+                let stars = repository_info(name, &repo_ref, source)
+                    .await
+                    .map(|repo_info| {
+                        status = status.or_archived(repo_info.archived);
+                        funding = repo_info.funding_links;
+                        repo_info.stargazers_count
+                    });
+
+                (
+                    repo_ref.source_type().to_string(),
+                    Some(url.to_string()),
+                    DependencyStats {
+                        stars,
+                        downloads: None,
+                    },
+                )
             } else {
                 (
                     "Source".to_string(),
@@ -466,7 +817,7 @@ async fn process_dependency(
 
     Ok(DependencyInfo {
         name: name.to_string(),
-        dependency_kind: dep_kind,
+        dependency_kind: dep_kinds,
         description: crate_info.description,
         crate_url: Some(CratesioClient::get_crate_url(name)),
         source_type,
@@ -474,23 +825,1399 @@ async fn process_dependency(
         stats,
         failed: false,
         error_message: None,
+        status,
+        homepage: crate_info.homepage,
+        documentation: crate_info.documentation,
+        funding,
+        ..Default::default()
+    })
+}
+
+/// 替代注册表中的依赖通过该注册表的 API 或稀疏索引获取信息，不查询 crates.io
+///
+/// 注册表只提供索引时没有描述与仓库地址，使用依赖包自身 Cargo.toml 中声明的信息
+async fn process_registry_dependency<S: MetadataSource>(
+    local: &DependencyInfo,
+    registry: &str,
+    source: &S,
+) -> Result<DependencyInfo> {
+    let name = &local.name;
+    let crate_info = source
+        .get_registry_crate_info(registry, name, local.latest_version())
+        .await?;
+
+    let description = crate_info.description.or(local.description.clone());
+    let status = if crate_info.yanked {
+        DependencyStatus::Yanked
+    } else if description
+        .as_deref()
+        .is_some_and(DependencyStatus::is_deprecated_description)
+    {
+        DependencyStatus::Deprecated
+    } else {
+        DependencyStatus::Ok
+    };
+
+    let source_url = crate_info.repository.or(local.source_url.clone());
+    let repo_ref = source_url
+        .as_deref()
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| RepositoryRef::from_url(&url));
+    let repo_info = match repo_ref {
+        Some(repo_ref) => repository_info(name, &repo_ref, source).await,
+        None => None,
+    };
+    let status = status.or_archived(repo_info.as_ref().is_some_and(|info| info.archived));
+
+    Ok(DependencyInfo {
+        name: name.clone(),
+        dependency_kind: local.dependency_kind.clone(),
+        description,
+        crate_url: None,
+        source_type: registry.to_string(),
+        source_url,
+        stats: DependencyStats {
+            stars: repo_info
+                .as_ref()
+                .map(|repo_info| repo_info.stargazers_count),
+            // 只有索引的注册表没有下载量
+            downloads: (crate_info.downloads > 0).then_some(crate_info.downloads),
+        },
+        status,
+        homepage: crate_info.homepage.or(local.homepage.clone()),
+        documentation: crate_info.documentation.or(local.documentation.clone()),
+        funding: repo_info
+            .map(|repo_info| repo_info.funding_links)
+            .unwrap_or_default(),
+        registry: Some(registry.to_string()),
+        ..Default::default()
     })
 }
 
+/// 获取仓库的统计信息，没有可用的来源或获取失败时返回 `None`，失败只记录日志
+async fn repository_info<S: MetadataSource>(
+    name: &str,
+    repo_ref: &RepositoryRef,
+    source: &S,
+) -> Option<RepositoryInfo> {
+    match source.get_repository_info(repo_ref).await {
+        Ok(Some(repo_info)) => {
+            info!("💖 {} {}", name, repo_info.html_url);
+            Some(repo_info)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            debug!(
+                "{}",
+                t!(
+                    "main.repository_api_error",
+                    host = repo_ref.source_type(),
+                    error = e.to_string()
+                )
+            );
+            None
+        }
+    }
+}
+
+/// 描述等信息来自依赖包自身的 Cargo.toml，而不是 crates.io 与替代注册表
+///
+/// 用于 git 与本地路径依赖，以及 `--local-metadata`/`--offline` 时的所有依赖。
+/// 仓库在已知的托管平台上时补充 stars、赞助链接与贡献者，本地路径依赖不请求网络。
+/// 注册表中的 crate 仍然查询撤回状态与下载量，离线时只使用缓存；
+/// 无法得知撤回状态且 `--fail-on` 包含 `yanked` 时标记为失败，而不是当作检查通过
+async fn process_local_dependency<S: MetadataSource>(
+    mut local: DependencyInfo,
+    maintainers: Option<usize>,
+    check_yanked: bool,
+    source: &S,
+) -> DependencyInfo {
+    // Cargo.toml 中的描述同样可以说明 crate 已弃用
+    if local
+        .description
+        .as_deref()
+        .is_some_and(DependencyStatus::is_deprecated_description)
+    {
+        local.status = DependencyStatus::Deprecated;
+    }
+
+    if local.source_type == DependencyInfo::PATH_SOURCE {
+        return local;
+    }
+
+    let mut downloads = None;
+    if !local.is_unregistered() {
+        let version = local.latest_version();
+        let crate_info = match &local.registry {
+            Some(registry) => {
+                source
+                    .get_registry_crate_info(registry, &local.name, version)
+                    .await
+            }
+            None => source.get_crate_info(&local.name, version).await,
+        };
+        match crate_info {
+            Ok(crate_info) => {
+                if crate_info.yanked {
+                    local.status = DependencyStatus::Yanked;
+                }
+                downloads = Some(crate_info.downloads);
+            }
+            Err(e) if check_yanked => {
+                local.failed = true;
+                local.error_message = Some(errors::short_reason(&e));
+            }
+            Err(e) => debug!(
+                "{}",
+                t!(
+                    "main.local_crate_info_failed",
+                    name = local.name,
+                    error = e.to_string()
+                )
+            ),
+        }
+    }
+
+    let repo_ref = local
+        .source_url
+        .as_deref()
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| RepositoryRef::from_url(&url));
+    if let Some(repo_ref) = repo_ref
+        && let Some(repo_info) = repository_info(&local.name, &repo_ref, source).await
+    {
+        local.stats.stars = Some(repo_info.stargazers_count);
+        local.status = local.status.or_archived(repo_info.archived);
+        funding::merge(&mut local.funding, repo_info.funding_links);
+    }
+    // 与 crates.io 的结果一致，没有 stars 时才显示下载量
+    if local.stats.stars.is_none() {
+        local.stats.downloads = downloads;
+    }
+
+    if let Some(contributors) = maintainers {
+        local.maintainers = dependency_maintainers(&local, contributors, source).await;
+    }
+    local
+}
+
+/// 根据撤回状态、描述与 README 判断依赖的状态
+///
+/// 只有在前两者都没有结论时才请求 README，获取 README 失败不影响结果
+async fn dependency_status<S: MetadataSource>(
+    name: &str,
+    version: Option<&str>,
+    crate_info: &CrateInfo,
+    source: &S,
+) -> DependencyStatus {
+    if crate_info.yanked {
+        return DependencyStatus::Yanked;
+    }
+
+    let deprecated_description = crate_info
+        .description
+        .as_deref()
+        .is_some_and(DependencyStatus::is_deprecated_description);
+    if deprecated_description {
+        return DependencyStatus::Deprecated;
+    }
+
+    let Some(version) = version else {
+        return DependencyStatus::Ok;
+    };
+    match source.get_readme(name, version).await {
+        Ok(Some(readme)) if DependencyStatus::is_deprecated_readme(&readme) => {
+            DependencyStatus::Deprecated
+        }
+        Ok(_) => DependencyStatus::Ok,
+        Err(e) => {
+            debug!(
+                "{}",
+                t!(
+                    "main.readme_fetch_failed",
+                    name = name,
+                    error = e.to_string()
+                )
+            );
+            DependencyStatus::Ok
+        }
+    }
+}
+
+/// 获取 crate 的所有者，以及仓库贡献最多的前 `contributors` 位贡献者
+///
+/// 所有者在前，同时是贡献者的人只出现一次；替代注册表、git 与本地路径依赖没有 crates.io 上的所有者。
+/// 获取失败时只记录日志，不影响依赖的其它信息
+async fn dependency_maintainers<S: MetadataSource>(
+    dep: &DependencyInfo,
+    contributors: usize,
+    source: &S,
+) -> Vec<Maintainer> {
+    let name = &dep.name;
+    let owners = match dep.is_crates_io_crate() {
+        true => source.get_crate_owners(name).await,
+        false => Ok(vec![]),
+    };
+    let mut maintainers = owners.unwrap_or_else(|e| {
+        debug!(
+            "{}",
+            t!(
+                "main.maintainers_fetch_failed",
+                name = name,
+                error = e.to_string()
+            )
+        );
+        vec![]
+    });
+
+    let repo = dep
+        .source_url
+        .as_deref()
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| RepositoryRef::from_url(&url));
+    if let Some(repo) = repo.filter(|_| contributors > 0) {
+        match source.get_contributors(&repo, contributors).await {
+            Ok(repo_contributors) => maintainers::merge(&mut maintainers, repo_contributors),
+            Err(e) => debug!(
+                "{}",
+                t!(
+                    "main.maintainers_fetch_failed",
+                    name = name,
+                    error = e.to_string()
+                )
+            ),
+        }
+    }
+    maintainers
+}
+
+/// `--fail-on` 指定的状态出现时返回错误，列出对应的依赖
+///
+/// 使用 `--fail-on` 时，获取信息失败、无法检查状态的依赖同样返回错误
+fn check_fail_on(deps: &[DependencyInfo], fail_on: &[DependencyStatus]) -> Result<()> {
+    if fail_on.is_empty() {
+        return Ok(());
+    }
+
+    let blocked = deps
+        .iter()
+        .filter(|dep| !dep.failed && fail_on.contains(&dep.status))
+        .map(|dep| format!("{} ({})", dep.name, dep.status))
+        .collect::<Vec<_>>();
+    // 获取信息失败的依赖无法得知状态，不能当作检查通过
+    let unchecked = deps
+        .iter()
+        .filter(|dep| dep.failed)
+        .map(|dep| {
+            format!(
+                "{} ({})",
+                dep.name,
+                dep.error_message.as_deref().unwrap_or("Failed")
+            )
+        })
+        .collect::<Vec<_>>();
+    let unchecked_message = t!(
+        "main.fail_on_unchecked",
+        count = unchecked.len(),
+        crates = unchecked.join(", ")
+    );
+
+    if !blocked.is_empty() {
+        if !unchecked.is_empty() {
+            warn!("{}", unchecked_message);
+        }
+        anyhow::bail!(t!(
+            "main.fail_on_triggered",
+            count = blocked.len(),
+            crates = blocked.join(", ")
+        ));
+    }
+    if !unchecked.is_empty() {
+        anyhow::bail!(unchecked_message);
+    }
+    Ok(())
+}
+
 // 在 main.rs 中使用
-#[instrument(skip(results))]
-fn generate_output(results: &[(String, DependencyInfo)], format: &OutputFormat) -> Result<()> {
+#[instrument(skip(deps))]
+fn generate_output(deps: &[DependencyInfo], format: &OutputFormat) -> Result<()> {
     let config = Config::global()?;
 
-    let deps: Vec<DependencyInfo> = results
-        .iter()
-        .map(|(_, dep_info)| dep_info.clone())
-        .collect();
+    let options = FormatOptions {
+        link_source: config.link_source,
+        sponsorable_only: config.sponsorable_only,
+        ..Default::default()
+    };
+
+    if let Some(path) = &config.inject {
+        let check = config.check.then_some(config.check_descriptions);
+        return inject_output(path, deps, format, &options, check);
+    }
+
+    if config.check {
+        return check_output(config, deps, format, &options);
+    }
+
+    // 合并时需要先读取已有的文件，不能提前打开输出文件
+    if config.write_mode == WriteMode::Update && config.has_output_file() {
+        return update_output(config, deps, format, &options);
+    }
 
     // 根据配置选择输出目标
     let output = config.get_output_writer()?;
-    let mut manager = OutputManager::new(*format, output);
-    manager.write(&deps)?;
 
+    if !config.per_package {
+        let mut manager = OutputManager::new_with_options(*format, output, options);
+        manager.write(deps)?;
+        return Ok(());
+    }
+
+    // Markdown 在同一个文档中为每个成员输出一节，其它格式为每个成员输出一个文件
+    if format.is_markdown() {
+        let mut manager = OutputManager::new_with_options(*format, output, options);
+        manager.write_per_package(deps)?;
+        return Ok(());
+    }
+
+    if !config.has_output_file() {
+        anyhow::bail!(t!("main.per_package_requires_output", format = format));
+    }
+
+    OutputManager::new_with_options(*format, output, options.clone()).write(deps)?;
+    for (member, member_deps) in output::split_by_member(deps) {
+        let output = config.get_package_output_writer(&member)?;
+        OutputManager::new_with_options(*format, output, options.clone()).write(&member_deps)?;
+    }
+
+    Ok(())
+}
+
+/// `--update`：与已有的输出文件合并后原子地替换，每个输出文件单独合并
+fn update_output(
+    config: &Config,
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+) -> Result<()> {
+    let Some(path) = config.output.as_deref() else {
+        return Ok(());
+    };
+
+    if !config.per_package {
+        return update_output_file(path, deps, format, options, false);
+    }
+
+    if format.is_markdown() {
+        return update_output_file(path, deps, format, options, true);
+    }
+
+    update_output_file(path, deps, format, options, false)?;
+    for (member, member_deps) in output::split_by_member(deps) {
+        if let Some(path) = config.get_package_output_path(&member) {
+            update_output_file(&path, &member_deps, format, options, false)?;
+        }
+    }
     Ok(())
 }
+
+/// 解析已有的输出文件并合并新的结果，文件不存在或为空时直接写入新的结果
+///
+/// 已有的文件无法解析时返回错误且不修改文件，避免覆盖手动编辑的内容
+fn update_output_file(
+    path: &Path,
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+    per_package: bool,
+) -> Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let previous = match content.trim().is_empty() {
+        true => vec![],
+        false => <dyn Formatter>::with_options(*format, options)
+            .parse(&content)
+            .map_err(|e| {
+                anyhow::anyhow!(t!(
+                    "main.update_parse_failed",
+                    path = path.display(),
+                    error = e.to_string()
+                ))
+            })?,
+    };
+    let deps = output::merge_update(previous, deps);
+
+    let content = render_output(&deps, format, options, per_package)?;
+    Config::replace_output_file(path, content.as_bytes())
+}
+
+/// `--inject`：替换文件中 `thanku:start`/`thanku:end` 标记之间的内容，文件的其它部分保持不变
+///
+/// `check` 为 `Some(descriptions)` 时不修改文件，与 `--check` 一样解析标记之间的内容并比较依赖，
+/// 每次运行都会变化的 stars 与下载量不算差异；内容过期则列出差异与不同的行并返回错误
+fn inject_output(
+    path: &Path,
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+    check: Option<bool>,
+) -> Result<()> {
+    let document = std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!(t!(
+            "config.failed_to_open_output_file",
+            path = path.display(),
+            error = e.to_string()
+        ))
+    })?;
+    let content = render_output(deps, format, options, false)?;
+
+    if let Some(descriptions) = check {
+        let section = inject::section(&document, *format)?;
+        let differences = compare_committed(
+            path,
+            section,
+            &options.filter(deps),
+            format,
+            options,
+            descriptions,
+        )?;
+        if differences.is_empty() {
+            info!("{}", t!("inject.up_to_date", path = path.display()));
+            return Ok(());
+        }
+
+        for difference in &differences {
+            info!("{}", difference);
+        }
+        for line in inject::drift(section, &content) {
+            info!("{}", line);
+        }
+        let (added, removed, changed) = count_differences(&differences);
+        anyhow::bail!(t!(
+            "inject.drift",
+            path = path.display(),
+            added = added,
+            removed = removed,
+            changed = changed
+        ));
+    }
+
+    let injected = inject::inject(&document, *format, &content)?;
+    if injected != document {
+        Config::replace_output_file(path, injected.as_bytes())?;
+    }
+    info!("{}", t!("inject.injected", path = path.display()));
+    Ok(())
+}
+
+/// `--check`：解析已有的输出文件并与当前的依赖比较，不一致时列出差异并返回错误
+///
+/// 只比较输出文件本身，`--per-package` 时的成员文件不参与比较
+fn check_output(
+    config: &Config,
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+) -> Result<()> {
+    let Some(path) = config
+        .output
+        .as_deref()
+        .filter(|_| config.has_output_file())
+    else {
+        anyhow::bail!(t!("check.requires_output_file"));
+    };
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!(t!(
+            "config.failed_to_open_output_file",
+            path = path.display(),
+            error = e.to_string()
+        ))
+    })?;
+    let deps = options.filter(deps);
+    let differences = compare_committed(
+        path,
+        &content,
+        &deps,
+        format,
+        options,
+        config.check_descriptions,
+    )?;
+    if differences.is_empty() {
+        info!(
+            "{}",
+            t!(
+                "check.up_to_date",
+                path = path.display(),
+                count = deps.len()
+            )
+        );
+        return Ok(());
+    }
+
+    for difference in &differences {
+        info!("{}", difference);
+    }
+    let (added, removed, changed) = count_differences(&differences);
+    anyhow::bail!(t!(
+        "check.mismatch",
+        path = path.display(),
+        added = added,
+        removed = removed,
+        changed = changed
+    ));
+}
+
+/// 解析已提交的内容（整个输出文件或注入的部分）并与当前的依赖比较
+fn compare_committed(
+    path: &Path,
+    content: &str,
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+    descriptions: bool,
+) -> Result<Vec<Difference>> {
+    let committed = <dyn Formatter>::with_options(*format, options)
+        .parse(content)
+        .map_err(|e| {
+            anyhow::anyhow!(t!(
+                "check.parse_failed",
+                path = path.display(),
+                error = e.to_string()
+            ))
+        })?;
+    Ok(check::compare(deps, &committed, descriptions))
+}
+
+/// 差异中新增、移除与变更的依赖数量
+fn count_differences(differences: &[Difference]) -> (usize, usize, usize) {
+    let count =
+        |matches: fn(&Difference) -> bool| differences.iter().filter(|d| matches(d)).count();
+    (
+        count(|d| matches!(d, Difference::Added { .. })),
+        count(|d| matches!(d, Difference::Removed { .. })),
+        count(|d| {
+            matches!(
+                d,
+                Difference::KindsChanged { .. } | Difference::DescriptionChanged { .. }
+            )
+        }),
+    )
+}
+
+/// 将依赖格式化为输出内容，`per_package` 时为每个工作区成员附加一节（只适用于 Markdown）
+fn render_output(
+    deps: &[DependencyInfo],
+    format: &OutputFormat,
+    options: &FormatOptions,
+    per_package: bool,
+) -> Result<String> {
+    let mut content = Vec::new();
+    let mut manager = OutputManager::new_with_options(*format, &mut content, options.clone());
+    match per_package {
+        true => manager.write_per_package(deps)?,
+        false => manager.write(deps)?,
+    }
+    Ok(String::from_utf8(content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::{CrateReleases, ReleaseInfo};
+
+    /// 内存中的元数据来源，未知的 crate 返回错误
+    #[derive(Default)]
+    struct FixtureSource {
+        crates: HashMap<String, CrateInfo>,
+        readmes: HashMap<String, String>,
+        releases: HashMap<String, CrateReleases>,
+        repositories: HashMap<String, RepositoryInfo>,
+        owners: HashMap<String, Vec<Maintainer>>,
+        /// 替代注册表中的 crate，键为 `registry/name`
+        registry_crates: HashMap<String, CrateInfo>,
+        /// 仓库的贡献者，键为 `owner/repo`
+        contributors: HashMap<String, Vec<Maintainer>>,
+        /// 获取 crate 信息时返回的错误
+        errors: HashMap<String, fn() -> AppError>,
+        /// 每个 crate 获取信息的次数
+        attempts: std::sync::Mutex<HashMap<String, u32>>,
+    }
+
+    impl FixtureSource {
+        fn with_crate(mut self, name: &str, repository: Option<&str>) -> Self {
+            self.crates.insert(
+                name.to_string(),
+                CrateInfo {
+                    name: name.to_string(),
+                    description: Some(format!("{} description", name)),
+                    repository: repository.map(str::to_string),
+                    homepage: None,
+                    documentation: None,
+                    downloads: 100,
+                    version: None,
+                    yanked: false,
+                },
+            );
+            self
+        }
+
+        /// 只有索引信息（版本与撤回状态）的替代注册表 crate
+        fn with_registry_crate(mut self, registry: &str, name: &str, yanked: bool) -> Self {
+            self.registry_crates.insert(
+                format!("{}/{}", registry, name),
+                CrateInfo {
+                    name: name.to_string(),
+                    description: None,
+                    repository: None,
+                    homepage: None,
+                    documentation: None,
+                    downloads: 0,
+                    version: None,
+                    yanked,
+                },
+            );
+            self
+        }
+
+        fn with_error(mut self, name: &str, error: fn() -> AppError) -> Self {
+            self.errors.insert(name.to_string(), error);
+            self
+        }
+
+        fn attempts(&self, name: &str) -> u32 {
+            self.attempts
+                .lock()
+                .unwrap()
+                .get(name)
+                .copied()
+                .unwrap_or(0)
+        }
+
+        fn with_readme(mut self, name: &str, readme: &str) -> Self {
+            self.readmes.insert(name.to_string(), readme.to_string());
+            self
+        }
+
+        /// 只有一个版本 `latest` 的发布信息
+        fn with_release(mut self, name: &str, latest: &str) -> Self {
+            self.releases.insert(
+                name.to_string(),
+                CrateReleases {
+                    name: name.to_string(),
+                    max_stable_version: Some(latest.to_string()),
+                    max_version: latest.to_string(),
+                    versions: vec![ReleaseInfo {
+                        num: latest.to_string(),
+                        yanked: false,
+                        created_at: "2025-03-09T17:09:56.371603+00:00".to_string(),
+                    }],
+                },
+            );
+            self
+        }
+
+        fn with_repository(mut self, full_name: &str, stars: u32) -> Self {
+            self.repositories.insert(
+                full_name.to_string(),
+                RepositoryInfo {
+                    full_name: full_name.to_string(),
+                    description: None,
+                    stargazers_count: stars,
+                    html_url: format!("https://github.com/{}", full_name),
+                    archived: false,
+                    ..Default::default()
+                },
+            );
+            self
+        }
+
+        fn with_owners(mut self, name: &str, logins: &[&str]) -> Self {
+            self.owners.insert(name.to_string(), people(logins));
+            self
+        }
+
+        fn with_contributors(mut self, full_name: &str, logins: &[&str]) -> Self {
+            self.contributors
+                .insert(full_name.to_string(), people(logins));
+            self
+        }
+    }
+
+    fn people(logins: &[&str]) -> Vec<Maintainer> {
+        logins
+            .iter()
+            .map(|login| Maintainer {
+                login: login.to_string(),
+                name: None,
+                url: Some(format!("https://github.com/{}", login)),
+            })
+            .collect()
+    }
+
+    impl MetadataSource for FixtureSource {
+        async fn get_crate_info(&self, name: &str, _version: Option<&str>) -> Result<CrateInfo> {
+            *self
+                .attempts
+                .lock()
+                .unwrap()
+                .entry(name.to_string())
+                .or_default() += 1;
+            if let Some(error) = self.errors.get(name) {
+                return Err(error().into());
+            }
+
+            self.crates
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("crate {} not found", name))
+        }
+
+        async fn get_readme(&self, name: &str, _version: &str) -> Result<Option<String>> {
+            Ok(self.readmes.get(name).cloned())
+        }
+
+        async fn get_crate_releases(&self, name: &str) -> Result<CrateReleases> {
+            self.releases
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("crate {} not found", name))
+        }
+
+        async fn get_repository_info(
+            &self,
+            repo: &RepositoryRef,
+        ) -> Result<Option<RepositoryInfo>> {
+            Ok(self.repositories.get(&repo.full_name()).cloned())
+        }
+
+        async fn get_registry_crate_info(
+            &self,
+            registry: &str,
+            name: &str,
+            _version: Option<&str>,
+        ) -> Result<CrateInfo> {
+            self.registry_crates
+                .get(&format!("{}/{}", registry, name))
+                .cloned()
+                .ok_or_else(|| AppError::UnknownRegistry(registry.to_string()).into())
+        }
+
+        async fn get_crate_owners(&self, name: &str) -> Result<Vec<Maintainer>> {
+            self.owners
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("crate {} not found", name))
+        }
+
+        async fn get_contributors(
+            &self,
+            repo: &RepositoryRef,
+            limit: usize,
+        ) -> Result<Vec<Maintainer>> {
+            let mut contributors = self
+                .contributors
+                .get(&repo.full_name())
+                .cloned()
+                .unwrap_or_default();
+            contributors.truncate(limit);
+            Ok(contributors)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_dependency_github() -> Result<()> {
+        let source = FixtureSource::default()
+            .with_crate("serde", Some("https://github.com/serde-rs/serde"))
+            .with_repository("serde-rs/serde", 9000);
+
+        let info =
+            process_dependency("serde", None, DependencyKind::Normal.into(), &source).await?;
+        assert_eq!(info.source_type, "GitHub");
+        assert_eq!(
+            info.source_url.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+        assert_eq!(info.stats.stars, Some(9000));
+        assert_eq!(info.description.as_deref(), Some("serde description"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependency_other_sources() -> Result<()> {
+        let source = FixtureSource::default()
+            .with_crate("gitlab-crate", Some("https://gitlab.com/group/sub/repo"))
+            .with_crate("codeberg-crate", Some("https://codeberg.org/owner/repo"))
+            .with_crate("other-crate", Some("https://example.com/owner/repo"))
+            .with_crate("no-repo", None)
+            .with_repository("group/sub/repo", 42);
+
+        let info = process_dependency("gitlab-crate", None, DependencyKind::Normal.into(), &source)
+            .await?;
+        assert_eq!(info.source_type, "GitLab");
+        assert_eq!(info.stats.stars, Some(42));
+
+        // 托管平台没有仓库信息时仍然显示来源，只是没有统计
+        let info = process_dependency(
+            "codeberg-crate",
+            None,
+            DependencyKind::Normal.into(),
+            &source,
+        )
+        .await?;
+        assert_eq!(info.source_type, "Codeberg");
+        assert_eq!(info.stats.stars, None);
+
+        let info =
+            process_dependency("other-crate", None, DependencyKind::Normal.into(), &source).await?;
+        assert_eq!(info.source_type, "Source");
+        assert_eq!(info.stats.stars, None);
+
+        let info =
+            process_dependency("no-repo", None, DependencyKind::Normal.into(), &source).await?;
+        assert_eq!(info.source_type, "crates.io");
+        assert_eq!(info.stats.downloads, Some(100));
+
+        assert!(
+            process_dependency("missing", None, DependencyKind::Normal.into(), &source)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependencies_hermetic() -> Result<()> {
+        let config = Config {
+            input: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            max_retries: 0,
+            ..Default::default()
+        };
+        let source = FixtureSource::default()
+            .with_crate("serde", Some("https://github.com/serde-rs/serde"))
+            .with_repository("serde-rs/serde", 9000);
+
+        let results = process_dependencies(&config, Arc::new(source)).await?;
+
+        let serde = results.iter().find(|dep| dep.name == "serde").unwrap();
+        assert!(!serde.failed);
+        assert_eq!(serde.stats.stars, Some(9000));
+        assert_eq!(serde.depth, 1);
+        assert!(!serde.versions.is_empty());
+        assert!(serde.version_req.is_some());
+
+        // 没有 fixture 的依赖被标记为失败，而不是中断整个流程
+        let anyhow = results.iter().find(|dep| dep.name == "anyhow").unwrap();
+        assert!(anyhow.failed);
+        assert!(anyhow.error_message.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependencies_path_dependency() -> Result<()> {
+        use assert_fs::prelude::*;
+
+        let temp = assert_fs::TempDir::new()?;
+        temp.child("Cargo.toml").write_str(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nfoo = { path = \"foo\" }\n",
+        )?;
+        temp.child("src/main.rs").write_str("fn main() {}\n")?;
+        temp.child("foo/Cargo.toml").write_str(
+            "[package]\nname = \"foo\"\nversion = \"0.2.0\"\nedition = \"2021\"\n\
+             description = \"A local helper\"\nrepository = \"https://github.com/owner/foo\"\n",
+        )?;
+        temp.child("foo/src/lib.rs").write_str("")?;
+
+        let config = Config {
+            input: temp.child("Cargo.toml").to_path_buf(),
+            offline: true,
+            max_retries: 0,
+            ..Default::default()
+        };
+        // 同名的 crates.io crate 不应被查询
+        let source = Arc::new(FixtureSource::default().with_crate("foo", None));
+        let results = process_dependencies(&config, Arc::clone(&source)).await?;
+
+        let foo = results.iter().find(|dep| dep.name == "foo").unwrap();
+        assert!(!foo.failed);
+        assert_eq!(foo.source_type, DependencyInfo::PATH_SOURCE);
+        assert_eq!(foo.description.as_deref(), Some("A local helper"));
+        assert_eq!(
+            foo.source_url.as_deref(),
+            Some("https://github.com/owner/foo")
+        );
+        assert_eq!(foo.crate_url, None);
+        assert_eq!(source.attempts("foo"), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependencies_local_metadata() -> Result<()> {
+        let config = Config {
+            input: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            local_metadata: true,
+            max_retries: 0,
+            ..Default::default()
+        };
+        let source = Arc::new(FixtureSource::default().with_repository("serde-rs/serde", 9000));
+
+        let results = process_dependencies(&config, Arc::clone(&source)).await?;
+        assert!(results.iter().all(|dep| !dep.failed));
+
+        // 描述、仓库、许可证与作者来自 serde 自身的 Cargo.toml，stars、撤回状态与下载量来自网络
+        let serde = results.iter().find(|dep| dep.name == "serde").unwrap();
+        assert!(serde.description.is_some());
+        assert!(serde.license.is_some());
+        assert!(!serde.authors.is_empty());
+        assert_eq!(serde.source_type, "GitHub");
+        assert_eq!(serde.stats.stars, Some(9000));
+        assert_eq!(
+            serde.crate_url.as_deref(),
+            Some("https://crates.io/crates/serde")
+        );
+        assert_eq!(source.attempts("serde"), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependencies_retry_transient() -> Result<()> {
+        let config = Config {
+            input: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            max_retries: 2,
+            ..Default::default()
+        };
+        // 被限流时不额外等待，重试不会拖慢测试
+        let source = Arc::new(
+            FixtureSource::default()
+                .with_error("serde", || AppError::RateLimited("GitHub".to_string()))
+                .with_error("anyhow", || AppError::NotFound("anyhow".to_string())),
+        );
+
+        let results = process_dependencies(&config, Arc::clone(&source)).await?;
+        let reason = |name: &str| {
+            let dep = results.iter().find(|dep| dep.name == name).unwrap();
+            assert!(dep.failed);
+            dep.error_message.clone().unwrap()
+        };
+
+        assert_eq!(source.attempts("serde"), 3);
+        assert_eq!(reason("serde"), t!("errors.rate_limited"));
+        // 不存在的 crate 不重试
+        assert_eq!(source.attempts("anyhow"), 1);
+        assert_eq!(reason("anyhow"), t!("errors.not_found"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_outdated_hermetic() -> Result<()> {
+        let config = Config {
+            input: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            ..Default::default()
+        };
+        let source = FixtureSource::default()
+            .with_release("serde", "999.0.0")
+            .with_release("anyhow", "0.0.1");
+
+        let results = process_outdated(&config, Arc::new(source)).await?;
+
+        let serde = results.iter().find(|dep| dep.name == "serde").unwrap();
+        let outdated = serde.outdated.as_ref().unwrap();
+        assert_eq!(outdated.latest_version.as_deref(), Some("999.0.0"));
+        assert_eq!(outdated.lag, outdated::VersionLag::Major);
+        assert_eq!(outdated.latest_released_at.as_deref(), Some("2025-03-09"));
+        assert!(outdated.needs_attention());
+
+        let anyhow = results.iter().find(|dep| dep.name == "anyhow").unwrap();
+        assert_eq!(
+            anyhow.outdated.as_ref().map(|outdated| outdated.lag),
+            Some(outdated::VersionLag::UpToDate)
+        );
+
+        // 没有发布信息的依赖被标记为失败
+        let clap = results.iter().find(|dep| dep.name == "clap").unwrap();
+        assert!(clap.failed);
+        assert!(clap.outdated.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dependency_maintainers() {
+        let source = FixtureSource::default()
+            .with_owners("serde", &["dtolnay"])
+            .with_contributors("serde-rs/serde", &["DTolnay", "oli-obk", "jonasbb"]);
+        let logins = |maintainers: Vec<Maintainer>| {
+            maintainers
+                .into_iter()
+                .map(|maintainer| maintainer.login)
+                .collect::<Vec<_>>()
+        };
+        let dep = |name: &str, source_type: &str, source_url: &str| DependencyInfo {
+            name: name.to_string(),
+            source_type: source_type.to_string(),
+            source_url: Some(source_url.to_string()),
+            ..Default::default()
+        };
+        let serde = dep("serde", "GitHub", "https://github.com/serde-rs/serde");
+
+        // 所有者在前，同时是贡献者的人只出现一次
+        let maintainers = dependency_maintainers(&serde, 2, &source).await;
+        assert_eq!(logins(maintainers), vec!["dtolnay", "oli-obk"]);
+
+        let maintainers = dependency_maintainers(&serde, 0, &source).await;
+        assert_eq!(logins(maintainers), vec!["dtolnay"]);
+
+        // 获取所有者失败时仍然保留贡献者
+        let missing = dep("missing", "GitHub", "https://github.com/serde-rs/serde");
+        let maintainers = dependency_maintainers(&missing, 3, &source).await;
+        assert_eq!(logins(maintainers), vec!["DTolnay", "oli-obk", "jonasbb"]);
+
+        let crates_io = dep("serde", "crates.io", "https://crates.io/crates/serde");
+        let maintainers = dependency_maintainers(&crates_io, 3, &source).await;
+        assert_eq!(logins(maintainers), vec!["dtolnay"]);
+
+        // git 依赖没有 crates.io 上的所有者
+        let git = dep("serde", "Git", "https://github.com/serde-rs/serde");
+        let maintainers = dependency_maintainers(&git, 1, &source).await;
+        assert_eq!(logins(maintainers), vec!["DTolnay"]);
+    }
+
+    #[tokio::test]
+    async fn test_process_local_dependency() {
+        // 同名的 crates.io crate 不应被查询
+        let source = FixtureSource::default()
+            .with_crate("foo", Some("https://github.com/unrelated/foo"))
+            .with_repository("owner/foo", 42);
+        let local = |source_type: &str, source_url: &str| DependencyInfo {
+            name: "foo".to_string(),
+            description: Some("local description".to_string()),
+            source_type: source_type.to_string(),
+            source_url: Some(source_url.to_string()),
+            ..Default::default()
+        };
+
+        let info = process_local_dependency(
+            local(DependencyInfo::GIT_SOURCE, "https://github.com/owner/foo"),
+            None,
+            true,
+            &source,
+        )
+        .await;
+        assert_eq!(info.stats.stars, Some(42));
+        assert_eq!(info.description.as_deref(), Some("local description"));
+        assert_eq!(info.crate_url, None);
+        assert_eq!(source.attempts("foo"), 0);
+
+        // 本地路径依赖不请求网络
+        let info = process_local_dependency(
+            local(DependencyInfo::PATH_SOURCE, "https://github.com/owner/foo"),
+            Some(3),
+            true,
+            &source,
+        )
+        .await;
+        assert_eq!(info.stats.stars, None);
+        assert!(info.maintainers.is_empty());
+
+        // `--local-metadata` 时 crates.io 上的 crate 只查询撤回状态与下载量，描述仍来自本地
+        let crates_io = |description: &str| DependencyInfo {
+            crate_url: Some(CratesioClient::get_crate_url("foo")),
+            description: Some(description.to_string()),
+            ..local("GitHub", "https://github.com/owner/foo")
+        };
+        let info =
+            process_local_dependency(crates_io("local description"), None, true, &source).await;
+        assert_eq!(info.stats.stars, Some(42));
+        assert_eq!(info.description.as_deref(), Some("local description"));
+        assert_eq!(info.status, DependencyStatus::Ok);
+        assert_eq!(source.attempts("foo"), 1);
+
+        // 本地的描述同样用于判断是否已弃用
+        let info = process_local_dependency(
+            crates_io("DEPRECATED: use bar instead"),
+            None,
+            false,
+            &source,
+        )
+        .await;
+        assert_eq!(info.status, DependencyStatus::Deprecated);
+
+        let mut yanked = FixtureSource::default().with_crate("foo", None);
+        yanked.crates.get_mut("foo").unwrap().yanked = true;
+        let info =
+            process_local_dependency(crates_io("local description"), None, true, &yanked).await;
+        assert_eq!(info.status, DependencyStatus::Yanked);
+        assert_eq!(info.stats.downloads, Some(100));
+
+        // 无法得知撤回状态时，只有 `--fail-on yanked` 才标记为失败
+        let missing = FixtureSource::default();
+        let info =
+            process_local_dependency(crates_io("local description"), None, false, &missing).await;
+        assert!(!info.failed);
+        let info =
+            process_local_dependency(crates_io("local description"), None, true, &missing).await;
+        assert!(info.failed);
+        assert!(info.error_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_process_registry_dependency() -> Result<()> {
+        // 同名的 crates.io crate 不应被查询
+        let source = FixtureSource::default()
+            .with_crate("foo", Some("https://github.com/unrelated/foo"))
+            .with_registry_crate("internal", "foo", true)
+            .with_repository("owner/foo", 42);
+        let local = DependencyInfo {
+            name: "foo".to_string(),
+            description: Some("local description".to_string()),
+            source_type: "internal".to_string(),
+            source_url: Some("https://github.com/owner/foo".to_string()),
+            versions: vec!["0.1.0".to_string()],
+            registry: Some("internal".to_string()),
+            ..Default::default()
+        };
+        assert!(!local.is_crates_io_crate());
+        assert!(!local.is_unregistered());
+
+        let info = process_registry_dependency(&local, "internal", &source).await?;
+        assert_eq!(info.source_type, "internal");
+        assert_eq!(info.registry.as_deref(), Some("internal"));
+        assert_eq!(info.description.as_deref(), Some("local description"));
+        assert_eq!(info.status, DependencyStatus::Yanked);
+        assert_eq!(info.stats.stars, Some(42));
+        assert_eq!(info.stats.downloads, None);
+        assert_eq!(info.crate_url, None);
+        assert_eq!(source.attempts("foo"), 0);
+
+        let error = process_registry_dependency(&local, "unknown", &source)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::UnknownRegistry(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_process_dependency_status() -> Result<()> {
+        let mut source = FixtureSource::default()
+            .with_crate("serde", None)
+            .with_crate("failure", None)
+            .with_crate("tempdir", None)
+            .with_readme(
+                "failure",
+                "<h1>failure</h1><p>This crate is deprecated.</p>",
+            );
+        source.crates.get_mut("serde").unwrap().yanked = true;
+        source.crates.get_mut("tempdir").unwrap().description =
+            Some("DEPRECATED: use tempfile instead".to_string());
+        let mut source = source
+            .with_crate("atty", Some("https://github.com/softprops/atty"))
+            .with_crate("tempdir-fork", Some("https://github.com/softprops/atty"))
+            .with_repository("softprops/atty", 800);
+        source
+            .repositories
+            .get_mut("softprops/atty")
+            .unwrap()
+            .archived = true;
+        source.crates.get_mut("tempdir-fork").unwrap().description =
+            Some("Deprecated: use tempfile instead".to_string());
+
+        let status = |name: &'static str, version: Option<&'static str>| {
+            let source = &source;
+            async move {
+                process_dependency(name, version, DependencyKind::Normal.into(), source)
+                    .await
+                    .map(|info| info.status)
+            }
+        };
+        assert_eq!(
+            status("serde", Some("1.0.0")).await?,
+            DependencyStatus::Yanked
+        );
+        assert_eq!(
+            status("failure", Some("0.1.8")).await?,
+            DependencyStatus::Deprecated
+        );
+        // 没有版本时不检查 README
+        assert_eq!(status("failure", None).await?, DependencyStatus::Ok);
+        assert_eq!(status("tempdir", None).await?, DependencyStatus::Deprecated);
+        // 仓库已归档，弃用时仍然显示为弃用
+        assert_eq!(status("atty", None).await?, DependencyStatus::Archived);
+        assert_eq!(
+            status("tempdir-fork", None).await?,
+            DependencyStatus::Deprecated
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_fail_on() {
+        let dep = |name: &str, status: DependencyStatus, failed: bool| DependencyInfo {
+            name: name.to_string(),
+            status,
+            failed,
+            ..Default::default()
+        };
+        let deps = vec![
+            dep("serde", DependencyStatus::Ok, false),
+            dep("failure", DependencyStatus::Deprecated, false),
+            dep("yanked", DependencyStatus::Yanked, false),
+        ];
+
+        assert!(check_fail_on(&deps, &[]).is_ok());
+        assert!(check_fail_on(&deps[..2], &[DependencyStatus::Yanked]).is_ok());
+
+        let error = check_fail_on(
+            &deps,
+            &[DependencyStatus::Yanked, DependencyStatus::Deprecated],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("failure"));
+        assert!(error.contains("yanked"));
+        assert!(!error.contains("serde"));
+
+        // 获取信息失败的依赖无法检查，只在使用 `--fail-on` 时阻止通过
+        let mut deps = deps[..1].to_vec();
+        deps.push(DependencyInfo {
+            error_message: Some("not cached".to_string()),
+            ..dep("ring", DependencyStatus::Ok, true)
+        });
+        assert!(check_fail_on(&deps, &[]).is_ok());
+        let error = check_fail_on(&deps, &[DependencyStatus::Yanked])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("ring (not cached)"));
+        assert!(!error.contains("serde"));
+    }
+
+    #[test]
+    fn test_check_output() -> Result<()> {
+        use assert_fs::prelude::*;
+
+        let temp = assert_fs::TempDir::new()?;
+        temp.child("Cargo.toml").write_str(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nfoo = { path = \"foo\" }\n",
+        )?;
+        temp.child("src/main.rs").write_str("fn main() {}\n")?;
+        temp.child("foo/Cargo.toml").write_str(
+            "[package]\nname = \"foo\"\nversion = \"0.2.0\"\nedition = \"2021\"\n\
+             description = \"A local helper\"\n",
+        )?;
+        temp.child("foo/src/lib.rs").write_str("")?;
+        let file = temp.child("thanks.json");
+
+        let config = Config {
+            input: temp.child("Cargo.toml").to_path_buf(),
+            output: Some(file.to_path_buf()),
+            check: true,
+            check_descriptions: true,
+            local_metadata: true,
+            offline: true,
+            ..Default::default()
+        };
+        // 使用本地元数据比较描述时不请求网络
+        assert!(config.checks_without_network());
+        let deps = local_dependencies(&config)?;
+        assert_eq!(deps[0].description.as_deref(), Some("A local helper"));
+
+        let format = OutputFormat::Json;
+        let options = FormatOptions::default();
+        let formatter = <dyn Formatter>::new(format)?;
+
+        // 输出到标准输出时没有可以比较的文件
+        for output in [None, Some(PathBuf::from("-"))] {
+            let config = Config {
+                output,
+                ..config.clone()
+            };
+            let error = check_output(&config, &deps, &format, &options).unwrap_err();
+            assert_eq!(error.to_string(), t!("check.requires_output_file"));
+        }
+
+        file.write_str(&formatter.format(&deps)?)?;
+        check_output(&config, &deps, &format, &options)?;
+
+        // `--update` 标记为已移除的依赖不算差异
+        let mut committed = deps.clone();
+        committed.push(DependencyInfo {
+            name: "legacy".to_string(),
+            status: DependencyStatus::Removed,
+            ..deps[0].clone()
+        });
+        file.write_str(&formatter.format(&committed)?)?;
+        check_output(&config, &deps, &format, &options)?;
+
+        // 描述的差异只在 `--check-descriptions` 时报告
+        committed[0].description = Some("An edited description".to_string());
+        file.write_str(&formatter.format(&committed)?)?;
+        assert!(check_output(&config, &deps, &format, &options).is_err());
+        let config = Config {
+            check_descriptions: false,
+            ..config
+        };
+        check_output(&config, &deps, &format, &options)?;
+
+        // 文件中缺少的依赖
+        file.write_str(&formatter.format(&[])?)?;
+        assert!(check_output(&config, &deps, &format, &options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_inject_output_check() -> Result<()> {
+        use assert_fs::prelude::*;
+
+        let dep = |name: &str, stars: u32| DependencyInfo {
+            name: name.to_string(),
+            description: Some(format!("{} description", name)),
+            source_type: "GitHub".to_string(),
+            source_url: Some(format!("https://github.com/owner/{}", name)),
+            stats: DependencyStats {
+                stars: Some(stars),
+                downloads: None,
+            },
+            ..Default::default()
+        };
+        let deps = vec![dep("serde", 9000), dep("tokio", 25000)];
+
+        let temp = assert_fs::TempDir::new()?;
+        let readme = temp.child("README.md");
+        readme.write_str("# App\n\n<!-- thanku:start -->\n<!-- thanku:end -->\n")?;
+        let format = OutputFormat::MarkdownTable;
+        let options = FormatOptions::default();
+        inject_output(readme.path(), &deps, &format, &options, None)?;
+
+        // stars 的变化不算过期
+        let mut current = deps.clone();
+        current[0].stats.stars = Some(9001);
+        inject_output(readme.path(), &current, &format, &options, Some(false))?;
+
+        // 描述只在 `--check-descriptions` 时比较
+        current[1].description = Some("An edited description".to_string());
+        inject_output(readme.path(), &current, &format, &options, Some(false))?;
+        assert!(inject_output(readme.path(), &current, &format, &options, Some(true)).is_err());
+
+        current.push(dep("anyhow", 5000));
+        let error = inject_output(readme.path(), &current, &format, &options, Some(false))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            t!(
+                "inject.drift",
+                path = readme.path().display(),
+                added = 1,
+                removed = 0,
+                changed = 0
+            )
+        );
+        Ok(())
+    }
+}
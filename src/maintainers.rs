@@ -0,0 +1,228 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// 需要致谢的人，来自 crates.io 上的 crate 所有者或 GitHub 仓库的贡献者
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Maintainer {
+    /// 登录名，团队所有者为 `组织/团队`，例如 `rust-lang/libs`
+    pub login: String,
+    /// 显示名称，只在 JSON/TOML/YAML 中保存
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 个人主页，例如 `https://github.com/dtolnay`
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Maintainer {
+    fn same_person(&self, other: &Self) -> bool {
+        self.login.eq_ignore_ascii_case(&other.login)
+    }
+}
+
+/// `/crates/{name}/owners` 返回的所有者
+#[derive(Debug, Deserialize)]
+struct Owner {
+    login: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+/// `/repos/{owner}/{repo}/contributors` 返回的贡献者
+#[derive(Debug, Deserialize)]
+struct Contributor {
+    login: String,
+    #[serde(default)]
+    html_url: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+}
+
+/// 解析 crates.io 的 `/crates/{name}/owners` 响应
+///
+/// 团队所有者的登录名形如 `github:rust-lang:libs`，转换为 `rust-lang/libs`
+pub fn from_owners(response: &serde_json::Value) -> Result<Vec<Maintainer>> {
+    let owners: Vec<Owner> = serde_json::from_value(response["users"].clone())?;
+    Ok(owners
+        .into_iter()
+        .map(|owner| {
+            let login = match owner.login.strip_prefix("github:") {
+                Some(team) => team.replacen(':', "/", 1),
+                None => owner.login,
+            };
+            Maintainer {
+                login,
+                name: owner.name.filter(|name| !name.is_empty()),
+                url: owner.url.filter(|url| !url.is_empty()),
+            }
+        })
+        .collect())
+}
+
+/// 解析 GitHub 的 `/repos/{owner}/{repo}/contributors` 响应，按贡献数量排序，机器人账号被忽略
+pub fn from_contributors(response: &serde_json::Value) -> Result<Vec<Maintainer>> {
+    let contributors: Vec<Contributor> = serde_json::from_value(response.clone())?;
+    Ok(contributors
+        .into_iter()
+        .filter(|contributor| {
+            contributor.kind.as_deref() != Some("Bot") && !contributor.login.ends_with("[bot]")
+        })
+        .map(|contributor| Maintainer {
+            login: contributor.login,
+            name: None,
+            url: contributor.html_url,
+        })
+        .collect())
+}
+
+/// 合并两组维护者，按登录名（不区分大小写）去重并保持先后顺序
+pub fn merge(maintainers: &mut Vec<Maintainer>, others: Vec<Maintainer>) {
+    for maintainer in others {
+        if !maintainers
+            .iter()
+            .any(|existing| existing.same_person(&maintainer))
+        {
+            maintainers.push(maintainer);
+        }
+    }
+}
+
+const SEPARATOR: &str = ", ";
+
+/// 单个维护者，有主页时为链接，例如 `[dtolnay](https://github.com/dtolnay)`
+pub fn to_maintainer_string(maintainer: &Maintainer) -> String {
+    match &maintainer.url {
+        Some(url) => format!("[{}]({})", maintainer.login, url),
+        None => maintainer.login.clone(),
+    }
+}
+
+/// 输出中的维护者列
+pub fn to_maintainers_string(maintainers: &[Maintainer]) -> String {
+    maintainers
+        .iter()
+        .map(to_maintainer_string)
+        .collect::<Vec<_>>()
+        .join(SEPARATOR)
+}
+
+/// 解析 [`to_maintainers_string`] 的输出，显示名称不会被保存
+pub fn parse_maintainers_string(s: &str) -> Vec<Maintainer> {
+    s.split(SEPARATOR)
+        .map(str::trim)
+        .filter(|maintainer| !maintainer.is_empty())
+        .map(|maintainer| {
+            let link = maintainer
+                .strip_prefix('[')
+                .and_then(|link| link.strip_suffix(')'))
+                .and_then(|link| link.split_once("]("));
+            match link {
+                Some((login, url)) => Maintainer {
+                    login: login.to_string(),
+                    name: None,
+                    url: Some(url.to_string()),
+                },
+                None => Maintainer {
+                    login: maintainer.to_string(),
+                    name: None,
+                    url: None,
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_owners() -> Result<()> {
+        let response = serde_json::json!({
+            "users": [
+                {
+                    "id": 3618,
+                    "login": "dtolnay",
+                    "kind": "user",
+                    "name": "David Tolnay",
+                    "url": "https://github.com/dtolnay",
+                    "avatar": "https://avatars.githubusercontent.com/u/1940490?v=4"
+                },
+                {
+                    "id": 42,
+                    "login": "github:rust-lang:libs",
+                    "kind": "team",
+                    "name": "libs",
+                    "url": "https://github.com/rust-lang"
+                }
+            ]
+        });
+
+        let owners = from_owners(&response)?;
+        assert_eq!(
+            owners,
+            vec![
+                Maintainer {
+                    login: "dtolnay".to_string(),
+                    name: Some("David Tolnay".to_string()),
+                    url: Some("https://github.com/dtolnay".to_string()),
+                },
+                Maintainer {
+                    login: "rust-lang/libs".to_string(),
+                    name: Some("libs".to_string()),
+                    url: Some("https://github.com/rust-lang".to_string()),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_contributors() -> Result<()> {
+        let response = serde_json::json!([
+            { "login": "dtolnay", "html_url": "https://github.com/dtolnay", "type": "User", "contributions": 3000 },
+            { "login": "dependabot[bot]", "html_url": "https://github.com/apps/dependabot", "type": "Bot" },
+            { "login": "oli-obk", "html_url": "https://github.com/oli-obk", "type": "User", "contributions": 200 }
+        ]);
+
+        let contributors = from_contributors(&response)?;
+        assert_eq!(
+            contributors
+                .iter()
+                .map(|contributor| contributor.login.as_str())
+                .collect::<Vec<_>>(),
+            vec!["dtolnay", "oli-obk"]
+        );
+
+        let mut maintainers = from_owners(&serde_json::json!({
+            "users": [{ "login": "DTolnay", "name": "David Tolnay" }]
+        }))?;
+        merge(&mut maintainers, contributors);
+        assert_eq!(maintainers.len(), 2);
+        assert_eq!(maintainers[0].name.as_deref(), Some("David Tolnay"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_maintainers_string() {
+        let maintainers = vec![
+            Maintainer {
+                login: "dtolnay".to_string(),
+                name: None,
+                url: Some("https://github.com/dtolnay".to_string()),
+            },
+            Maintainer {
+                login: "rust-lang/libs".to_string(),
+                name: None,
+                url: None,
+            },
+        ];
+
+        let s = to_maintainers_string(&maintainers);
+        assert_eq!(s, "[dtolnay](https://github.com/dtolnay), rust-lang/libs");
+        assert_eq!(parse_maintainers_string(&s), maintainers);
+        assert!(parse_maintainers_string(" ").is_empty());
+    }
+}
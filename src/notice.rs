@@ -0,0 +1,343 @@
+use anyhow::Result;
+use rust_i18n::t;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// 生成 NOTICE 文档所需的 crate 信息
+#[derive(Debug, Clone, Default)]
+pub struct NoticeCrate {
+    pub name: String,
+    /// SPDX 许可证表达式，用于分组
+    pub license: Option<String>,
+    /// 包的 Cargo.toml 路径，许可证文件在其所在目录中查找
+    pub manifest_path: Option<PathBuf>,
+    /// Cargo.toml 中通过 `license-file` 指定的许可证文件
+    pub license_file: Option<PathBuf>,
+}
+
+/// 一份许可证全文，以及使用它的 crates
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoticeText {
+    pub crates: Vec<String>,
+    /// 首次找到该全文时的文件名
+    pub file_name: String,
+    pub text: String,
+}
+
+/// 使用同一个许可证表达式的 crates
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoticeGroup {
+    pub license: String,
+    pub texts: Vec<NoticeText>,
+    /// 找不到许可证文件的 crates
+    pub missing: Vec<String>,
+}
+
+/// 按许可证分组的第三方许可证全文，相同的全文只保留一份
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Notice {
+    pub groups: Vec<NoticeGroup>,
+}
+
+/// 判断文件名是否为许可证文件，例如 `LICENSE`、`LICENSE-MIT`、`COPYING.txt`
+fn is_license_file_name(name: &str) -> bool {
+    let name = name.to_uppercase();
+    ["LICENSE", "LICENCE", "COPYING"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// 查找包源码目录中的许可证文件，按文件名排序
+///
+/// `license-file` 指定的文件不符合命名规则时也会被包含。
+/// 跟随符号链接，工作区中的包常用 `LICENSE-MIT -> ../LICENSE-MIT` 共享许可证
+pub fn find_license_files(krate: &NoticeCrate) -> Vec<PathBuf> {
+    let mut files = krate
+        .manifest_path
+        .as_deref()
+        .and_then(Path::parent)
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| is_license_file_name(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+
+    if let Some(file) = &krate.license_file
+        && file.is_file()
+        && !files.contains(file)
+    {
+        files.push(file.clone());
+    }
+
+    files.sort();
+    files
+}
+
+/// 统一换行符并去掉首尾空白，避免格式差异导致无法去重
+fn normalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n").trim().to_string()
+}
+
+impl Notice {
+    /// 读取所有 crates 的许可证文件并按许可证分组
+    ///
+    /// 分组按许可证名称排序，没有许可证信息的分组位于最后
+    pub fn collect(crates: &[NoticeCrate]) -> Result<Self> {
+        let mut crates = crates.iter().collect::<Vec<_>>();
+        crates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut groups: BTreeMap<(bool, String), NoticeGroup> = BTreeMap::new();
+        for krate in crates {
+            let license = krate.license.clone();
+            let key = (license.is_none(), license.clone().unwrap_or_default());
+            let group = groups.entry(key).or_insert_with(|| NoticeGroup {
+                license: license.unwrap_or_else(|| t!("output.license_unknown").to_string()),
+                texts: vec![],
+                missing: vec![],
+            });
+
+            let files = find_license_files(krate);
+            if files.is_empty() {
+                debug!("{}", t!("notice.license_file_not_found", name = krate.name));
+                group.missing.push(krate.name.clone());
+                continue;
+            }
+
+            for file in files {
+                // 个别许可证文件不是 UTF-8 编码时替换无效字符，而不是放弃整个文档
+                let text = normalize_text(&String::from_utf8_lossy(&std::fs::read(&file)?));
+                match group.texts.iter_mut().find(|t| t.text == text) {
+                    Some(existing) if existing.crates.contains(&krate.name) => {}
+                    Some(existing) => existing.crates.push(krate.name.clone()),
+                    None => group.texts.push(NoticeText {
+                        crates: vec![krate.name.clone()],
+                        file_name: file
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .to_string(),
+                        text,
+                    }),
+                }
+            }
+        }
+
+        Ok(Self {
+            groups: groups.into_values().collect(),
+        })
+    }
+
+    /// 渲染为纯文本的 NOTICE 文档
+    pub fn render(&self) -> String {
+        let heavy = "=".repeat(80);
+        let light = "-".repeat(80);
+
+        let mut output = format!("{}\n\n{}\n", t!("notice.title"), t!("notice.intro"));
+        for group in &self.groups {
+            output.push_str(&format!("\n{}\n{}\n{}\n", heavy, group.license, heavy));
+
+            for text in &group.texts {
+                output.push_str(&format!(
+                    "\n{}: {}\n{}: {}\n{}\n\n{}\n",
+                    t!("notice.used_by"),
+                    text.crates.join(", "),
+                    t!("notice.file"),
+                    text.file_name,
+                    light,
+                    text.text
+                ));
+            }
+
+            if !group.missing.is_empty() {
+                output.push_str(&format!(
+                    "\n{}: {}\n",
+                    t!("notice.missing"),
+                    group.missing.join(", ")
+                ));
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    const MIT: &str = "MIT License\n\nPermission is hereby granted...";
+    const APACHE: &str = "Apache License\nVersion 2.0, January 2004";
+
+    /// 在临时目录中创建一个包，`files` 为许可证文件名与内容
+    fn package(
+        dir: &TempDir,
+        name: &str,
+        license: Option<&str>,
+        files: &[(&str, &str)],
+    ) -> NoticeCrate {
+        let package_dir = dir.child(name);
+        package_dir.child("Cargo.toml").touch().unwrap();
+        package_dir.child("README.md").write_str("readme").unwrap();
+        for (file, content) in files {
+            package_dir.child(file).write_str(content).unwrap();
+        }
+        NoticeCrate {
+            name: name.to_string(),
+            license: license.map(str::to_string),
+            manifest_path: Some(package_dir.child("Cargo.toml").to_path_buf()),
+            license_file: None,
+        }
+    }
+
+    #[test]
+    fn test_is_license_file_name() {
+        for name in [
+            "LICENSE",
+            "LICENSE-MIT",
+            "license.txt",
+            "LICENCE",
+            "COPYING.md",
+        ] {
+            assert!(is_license_file_name(name), "{}", name);
+        }
+        for name in ["README.md", "Cargo.toml", "NOTICE"] {
+            assert!(!is_license_file_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_find_license_files() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut krate = package(
+            &dir,
+            "serde",
+            None,
+            &[("LICENSE-MIT", MIT), ("LICENSE-APACHE", APACHE)],
+        );
+        dir.child("serde").child("COPYRIGHT").write_str("(c)")?;
+
+        let names = |files: Vec<PathBuf>| {
+            files
+                .iter()
+                .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(find_license_files(&krate)),
+            vec!["LICENSE-APACHE", "LICENSE-MIT"]
+        );
+
+        // license-file 指定的文件总是被包含
+        krate.license_file = Some(dir.child("serde").child("COPYRIGHT").to_path_buf());
+        assert_eq!(
+            names(find_license_files(&krate)),
+            vec!["COPYRIGHT", "LICENSE-APACHE", "LICENSE-MIT"]
+        );
+
+        assert!(find_license_files(&NoticeCrate::default()).is_empty());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_license_files_symlink() -> Result<()> {
+        let dir = TempDir::new()?;
+        dir.child("LICENSE-MIT").write_str(MIT)?;
+        let krate = package(&dir, "member", Some("MIT"), &[]);
+        std::os::unix::fs::symlink("../LICENSE-MIT", dir.child("member").child("LICENSE-MIT"))?;
+        // 指向不存在文件的链接不是许可证文件
+        std::os::unix::fs::symlink(
+            "../LICENSE-APACHE",
+            dir.child("member").child("LICENSE-APACHE"),
+        )?;
+
+        let files = find_license_files(&krate);
+        assert_eq!(
+            files,
+            vec![dir.child("member").child("LICENSE-MIT").to_path_buf()]
+        );
+
+        let notice = Notice::collect(&[krate])?;
+        assert_eq!(notice.groups[0].texts[0].text, MIT);
+        assert!(notice.groups[0].missing.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_notice() -> Result<()> {
+        let dir = TempDir::new()?;
+        let dual = Some("MIT OR Apache-2.0");
+        let crates = vec![
+            package(
+                &dir,
+                "serde",
+                dual,
+                &[("LICENSE-MIT", MIT), ("LICENSE-APACHE", APACHE)],
+            ),
+            // 只有换行符不同的全文也被视为相同
+            package(
+                &dir,
+                "anyhow",
+                dual,
+                &[
+                    ("LICENSE-MIT", &MIT.replace('\n', "\r\n")),
+                    ("LICENSE-APACHE", APACHE),
+                ],
+            ),
+            package(&dir, "tokio", Some("MIT"), &[("LICENSE", MIT)]),
+            package(&dir, "ring", None, &[]),
+        ];
+
+        let notice = Notice::collect(&crates)?;
+        assert_eq!(
+            notice
+                .groups
+                .iter()
+                .map(|g| g.license.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "MIT",
+                "MIT OR Apache-2.0",
+                t!("output.license_unknown").as_ref()
+            ]
+        );
+
+        let dual = &notice.groups[1];
+        assert_eq!(dual.texts.len(), 2);
+        assert_eq!(dual.texts[0].crates, vec!["anyhow", "serde"]);
+        assert_eq!(dual.texts[0].file_name, "LICENSE-APACHE");
+        assert_eq!(dual.texts[1].text, MIT);
+
+        let unknown = &notice.groups[2];
+        assert!(unknown.texts.is_empty());
+        assert_eq!(unknown.missing, vec!["ring"]);
+
+        let rendered = notice.render();
+        assert_eq!(rendered.matches(APACHE).count(), 1);
+        assert_eq!(rendered.matches(MIT).count(), 2);
+        assert!(rendered.contains("ring"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_notice_non_utf8() -> Result<()> {
+        let dir = TempDir::new()?;
+        let krate = package(&dir, "readline", Some("GPL-3.0-only"), &[]);
+        // Latin-1 编码的 `©`
+        dir.child("readline")
+            .child("COPYING")
+            .write_binary(b"Copyright \xa9 1989 Free Software Foundation")?;
+
+        let notice = Notice::collect(&[krate])?;
+        assert_eq!(
+            notice.groups[0].texts[0].text,
+            "Copyright \u{fffd} 1989 Free Software Foundation"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use cargo_metadata::semver::Version;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::AppError, sources::CrateReleases};
+
+/// 解析得到的版本落后于最新稳定版本的程度
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum VersionLag {
+    UpToDate,
+    Patch,
+    Minor,
+    Major,
+    /// 没有解析得到的版本，或者版本号无法解析
+    #[default]
+    Unknown,
+}
+
+impl VersionLag {
+    /// 比较当前版本与最新版本，当前版本更新（例如预发布版本）时视为最新
+    pub fn compare(current: &str, latest: &str) -> Self {
+        let (Ok(current), Ok(latest)) = (Version::parse(current), Version::parse(latest)) else {
+            return Self::Unknown;
+        };
+
+        if current >= latest {
+            Self::UpToDate
+        } else if current.major != latest.major {
+            Self::Major
+        } else if current.minor != latest.minor {
+            Self::Minor
+        } else {
+            Self::Patch
+        }
+    }
+
+    pub fn is_outdated(self) -> bool {
+        matches!(self, Self::Patch | Self::Minor | Self::Major)
+    }
+}
+
+impl std::str::FromStr for VersionLag {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        [
+            Self::UpToDate,
+            Self::Patch,
+            Self::Minor,
+            Self::Major,
+            Self::Unknown,
+        ]
+        .into_iter()
+        .find(|lag| lag.to_string().eq_ignore_ascii_case(s))
+        .ok_or_else(|| AppError::InvalidVersionLag(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for VersionLag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::UpToDate => t!("outdated.up_to_date"),
+            Self::Patch => t!("outdated.patch"),
+            Self::Minor => t!("outdated.minor"),
+            Self::Major => t!("outdated.major"),
+            Self::Unknown => t!("outdated.unknown"),
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+/// 与 crates.io 上最新稳定版本的比较结果
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct OutdatedInfo {
+    /// 最新的稳定版本
+    pub latest_version: Option<String>,
+    pub lag: VersionLag,
+    /// 解析得到的版本是否已被撤回
+    #[serde(default)]
+    pub yanked: bool,
+    /// 解析得到的版本的发布日期，例如 `2025-03-09`
+    #[serde(default)]
+    pub released_at: Option<String>,
+    /// 最新稳定版本的发布日期
+    #[serde(default)]
+    pub latest_released_at: Option<String>,
+}
+
+/// 只保留发布时间中的日期部分
+fn release_date(created_at: &str) -> String {
+    created_at.get(..10).unwrap_or(created_at).to_string()
+}
+
+impl OutdatedInfo {
+    pub fn from_releases(version: Option<&str>, releases: &CrateReleases) -> Self {
+        let latest = releases.latest_version();
+        let current = version.and_then(|version| releases.find(version));
+
+        Self {
+            latest_version: Some(latest.to_string()),
+            lag: version.map_or(VersionLag::Unknown, |version| {
+                VersionLag::compare(version, latest)
+            }),
+            yanked: current.is_some_and(|release| release.yanked),
+            released_at: current.map(|release| release_date(&release.created_at)),
+            latest_released_at: releases
+                .find(latest)
+                .map(|release| release_date(&release.created_at)),
+        }
+    }
+
+    /// 落后于最新版本或已被撤回
+    pub fn needs_attention(&self) -> bool {
+        self.lag.is_outdated() || self.yanked
+    }
+
+    /// 最新版本与发布日期，例如 `1.0.219 (2025-03-09)`
+    pub fn to_latest_string(&self) -> String {
+        match (&self.latest_version, &self.latest_released_at) {
+            (Some(version), Some(date)) => format!("{} ({})", version, date),
+            (Some(version), None) => version.clone(),
+            (None, _) => String::new(),
+        }
+    }
+
+    pub fn parse_latest(&mut self, s: &str) {
+        let (version, date) = match s.trim().split_once('(') {
+            Some((version, date)) => (version.trim(), date.trim_end_matches(')').trim()),
+            None => (s.trim(), ""),
+        };
+        self.latest_version = Some(version.to_string()).filter(|v| !v.is_empty());
+        self.latest_released_at = Some(date.to_string()).filter(|d| !d.is_empty());
+    }
+
+    /// 落后程度，已撤回时附加撤回标记，例如 `minor, ⚠️ yanked`
+    pub fn to_lag_string(&self) -> String {
+        if self.yanked {
+            format!("{}, {}", self.lag, t!("outdated.yanked"))
+        } else {
+            self.lag.to_string()
+        }
+    }
+
+    pub fn parse_lag(&mut self, s: &str) -> Result<(), AppError> {
+        let (lag, yanked) = match s.trim().split_once(',') {
+            Some((lag, _)) => (lag, true),
+            None => (s, false),
+        };
+        self.lag = lag.parse()?;
+        self.yanked = yanked;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::ReleaseInfo;
+
+    fn releases() -> CrateReleases {
+        let release = |num: &str, yanked: bool, date: &str| ReleaseInfo {
+            num: num.to_string(),
+            yanked,
+            created_at: format!("{}T12:00:00.000000+00:00", date),
+        };
+        CrateReleases {
+            name: "serde".to_string(),
+            max_stable_version: Some("1.0.219".to_string()),
+            max_version: "1.1.0-rc.1".to_string(),
+            versions: vec![
+                release("1.1.0-rc.1", false, "2025-04-01"),
+                release("1.0.219", false, "2025-03-09"),
+                release("1.0.218", true, "2025-02-20"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_version_lag_compare() {
+        assert_eq!(
+            VersionLag::compare("1.0.219", "1.0.219"),
+            VersionLag::UpToDate
+        );
+        assert_eq!(
+            VersionLag::compare("1.1.0-rc.1", "1.0.219"),
+            VersionLag::UpToDate
+        );
+        assert_eq!(VersionLag::compare("1.0.200", "1.0.219"), VersionLag::Patch);
+        assert_eq!(
+            VersionLag::compare("1.0.0-rc.1", "1.0.0"),
+            VersionLag::Patch
+        );
+        assert_eq!(VersionLag::compare("0.11.3", "0.12.0"), VersionLag::Minor);
+        assert_eq!(VersionLag::compare("0.9.9", "1.0.0"), VersionLag::Major);
+        assert_eq!(VersionLag::compare("latest", "1.0.0"), VersionLag::Unknown);
+    }
+
+    #[test]
+    fn test_outdated_info_from_releases() {
+        let releases = releases();
+
+        let info = OutdatedInfo::from_releases(Some("1.0.218"), &releases);
+        assert_eq!(
+            info,
+            OutdatedInfo {
+                latest_version: Some("1.0.219".to_string()),
+                lag: VersionLag::Patch,
+                yanked: true,
+                released_at: Some("2025-02-20".to_string()),
+                latest_released_at: Some("2025-03-09".to_string()),
+            }
+        );
+        assert!(info.needs_attention());
+
+        let info = OutdatedInfo::from_releases(Some("1.0.219"), &releases);
+        assert_eq!(info.lag, VersionLag::UpToDate);
+        assert!(!info.needs_attention());
+
+        let info = OutdatedInfo::from_releases(None, &releases);
+        assert_eq!(info.lag, VersionLag::Unknown);
+        assert_eq!(info.released_at, None);
+    }
+
+    #[test]
+    fn test_outdated_info_strings() -> Result<(), AppError> {
+        let info = OutdatedInfo::from_releases(Some("1.0.218"), &releases());
+
+        let mut parsed = OutdatedInfo {
+            released_at: info.released_at.clone(),
+            ..Default::default()
+        };
+        parsed.parse_latest(&info.to_latest_string());
+        parsed.parse_lag(&info.to_lag_string())?;
+        assert_eq!(parsed, info);
+
+        assert!(parsed.parse_lag("not a lag").is_err());
+        Ok(())
+    }
+}
@@ -1,14 +1,26 @@
 use anyhow::Result;
 use rust_i18n::t;
 use serde::{Deserialize, Serialize};
-use std::{io::Write, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::Write,
+    str::FromStr,
+};
 use tracing::instrument;
 
-use crate::{errors::AppError, sources::Source};
+use crate::{
+    config::{DepthLimit, LinkSource},
+    errors::AppError,
+    funding::{self, FundingLink},
+    maintainers::{self, Maintainer},
+    outdated::OutdatedInfo,
+    sources::{CratesioClient, Source},
+};
 
 /// 定义输出格式
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum OutputFormat {
+    #[default]
     MarkdownTable,
     MarkdownList,
     Csv,
@@ -18,14 +30,14 @@ pub enum OutputFormat {
 }
 
 impl OutputFormat {
-    pub fn to_identifier(&self) -> &str {
+    pub fn to_identifier(self) -> &'static str {
         match self {
             Self::MarkdownTable => "mt",
             Self::MarkdownList => "ml",
             _ => "",
         }
     }
-    pub fn to_extension(&self) -> &str {
+    pub fn to_extension(self) -> &'static str {
         match self {
             Self::MarkdownTable => "md",
             Self::MarkdownList => "md",
@@ -35,16 +47,24 @@ impl OutputFormat {
             Self::Toml => "toml",
         }
     }
+
+    /// 是否为 Markdown 格式，Markdown 可以在同一个文档中包含多个小节
+    pub fn is_markdown(self) -> bool {
+        matches!(self, Self::MarkdownTable | Self::MarkdownList)
+    }
 }
 impl std::fmt::Display for OutputFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
-}
+        let s = match self {
+            Self::MarkdownTable => "markdown-table",
+            Self::MarkdownList => "markdown-list",
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+        };
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        Self::MarkdownTable
+        write!(f, "{}", s)
     }
 }
 
@@ -64,7 +84,7 @@ impl std::str::FromStr for OutputFormat {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum DependencyKind {
     #[default]
     Normal,
@@ -80,7 +100,7 @@ impl std::str::FromStr for DependencyKind {
         let s = s.trim().to_lowercase();
 
         if s.is_empty() {
-            return Err(AppError::InvalidDependencyKind(s.to_string()).into());
+            return Err(AppError::InvalidDependencyKind(s.to_string()));
         }
 
         if s == t!("output.normal").to_lowercase() {
@@ -95,8 +115,7 @@ impl std::str::FromStr for DependencyKind {
             Err(AppError::InvalidDependencyKind(format!(
                 "{}",
                 t!("output.invalid_dependency_kind", kind = s.to_string())
-            ))
-            .into())
+            )))
         }
     }
 }
@@ -126,7 +145,7 @@ impl From<cargo_metadata::DependencyKind> for DependencyKind {
 }
 
 impl DependencyKind {
-    pub fn to_md_table_header(&self) -> impl AsRef<str> {
+    pub fn to_md_table_header(self) -> impl AsRef<str> {
         match self {
             DependencyKind::Normal => format!("| 🔍 | {} | | | | |", t!("output.normal")),
             DependencyKind::Development => format!("| 🔧 | {} | | | | |", t!("output.development")),
@@ -135,7 +154,7 @@ impl DependencyKind {
         }
     }
 
-    pub fn to_md_list_header(&self) -> impl AsRef<str> {
+    pub fn to_md_list_header(self) -> impl AsRef<str> {
         let s = match self {
             DependencyKind::Normal => t!("output.normal"),
             DependencyKind::Development => t!("output.development"),
@@ -166,6 +185,107 @@ impl DependencyKind {
     }
 }
 
+/// 一个依赖项的所有依赖类型，例如同时作为普通依赖与开发依赖
+///
+/// 按 `Normal`、`Development`、`Build`、`Unknown` 的顺序排列，第一个为主要类型。
+/// 只有一种类型时序列化为单个值，与旧版本输出保持兼容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "KindsRepr", into = "KindsRepr")]
+pub struct DependencyKinds(BTreeSet<DependencyKind>);
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KindsRepr {
+    One(DependencyKind),
+    Many(Vec<DependencyKind>),
+}
+
+impl From<KindsRepr> for DependencyKinds {
+    fn from(repr: KindsRepr) -> Self {
+        match repr {
+            KindsRepr::One(kind) => kind.into(),
+            KindsRepr::Many(kinds) => kinds.into_iter().collect(),
+        }
+    }
+}
+
+impl From<DependencyKinds> for KindsRepr {
+    fn from(kinds: DependencyKinds) -> Self {
+        match kinds.0.len() {
+            1 => KindsRepr::One(kinds.primary()),
+            _ => KindsRepr::Many(kinds.0.into_iter().collect()),
+        }
+    }
+}
+
+impl Default for DependencyKinds {
+    fn default() -> Self {
+        DependencyKind::default().into()
+    }
+}
+
+impl From<DependencyKind> for DependencyKinds {
+    fn from(kind: DependencyKind) -> Self {
+        Self(BTreeSet::from([kind]))
+    }
+}
+
+impl FromIterator<DependencyKind> for DependencyKinds {
+    fn from_iter<I: IntoIterator<Item = DependencyKind>>(iter: I) -> Self {
+        let kinds = iter.into_iter().collect::<BTreeSet<_>>();
+        match kinds.is_empty() {
+            true => Self::default(),
+            false => Self(kinds),
+        }
+    }
+}
+
+impl PartialEq<DependencyKind> for DependencyKinds {
+    fn eq(&self, kind: &DependencyKind) -> bool {
+        self.0.len() == 1 && self.0.contains(kind)
+    }
+}
+
+impl DependencyKinds {
+    const SEPARATOR: &'static str = ", ";
+
+    /// 主要依赖类型，决定依赖项在 Markdown 输出中所属的分组
+    pub fn primary(&self) -> DependencyKind {
+        self.0.first().copied().unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// CSV 中使用 `;` 分隔，避免与列分隔符冲突
+    pub fn to_csv_string(&self) -> String {
+        self.to_string().replace(Self::SEPARATOR, "; ")
+    }
+}
+
+impl std::fmt::Display for DependencyKinds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kinds = self
+            .0
+            .iter()
+            .map(|kind| kind.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", kinds.join(Self::SEPARATOR))
+    }
+}
+
+impl std::str::FromStr for DependencyKinds {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split([',', ';'])
+            .map(DependencyKind::from_str)
+            .collect::<Result<BTreeSet<_>, _>>()
+            .map(Self)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct DependencyList {
     dependencies: Vec<DependencyInfo>,
@@ -176,18 +296,254 @@ struct DependencyList {
 pub struct DependencyInfo {
     pub name: String,
     pub description: Option<String>,
-    pub dependency_kind: DependencyKind,
+    pub dependency_kind: DependencyKinds,
     pub crate_url: Option<String>,
     pub source_type: String,
     pub source_url: Option<String>,
     pub stats: DependencyStats,
     pub failed: bool,
     pub error_message: Option<String>,
+    /// 撤回或弃用状态，与获取信息是否失败无关
+    #[serde(default)]
+    pub status: DependencyStatus,
+    /// 在依赖图中的深度，直接依赖为 1，0 表示未知
+    #[serde(default)]
+    pub depth: u32,
+    /// 引入该依赖的父级链路，从工作区成员开始
+    #[serde(default)]
+    pub parents: Vec<String>,
+    /// crate 主页，`--source other` 时优先使用
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// crate 文档地址，`--source other` 时在没有主页的情况下使用
+    #[serde(default)]
+    pub documentation: Option<String>,
+    /// 使用该依赖的工作区成员
+    #[serde(default)]
+    pub used_by: Vec<String>,
+    /// 限定的目标平台，例如 `cfg(windows)`，为空表示在所有平台上使用
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// SPDX 许可证表达式，例如 `MIT OR Apache-2.0`
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Cargo.lock 中解析得到的所有版本，从旧到新排列
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// Cargo.toml 中声明的版本要求，例如 `^1.0`
+    #[serde(default)]
+    pub version_req: Option<String>,
+    /// 与最新稳定版本的比较结果，只在 `outdated` 报告中存在
+    #[serde(default)]
+    pub outdated: Option<OutdatedInfo>,
+    /// 赞助链接，来自仓库的 FUNDING.yml 与 crate 的 `[package.metadata.funding]`
+    #[serde(default)]
+    pub funding: Vec<FundingLink>,
+    /// crate 的所有者与仓库的主要贡献者，使用 `--maintainers` 时获取
+    #[serde(default)]
+    pub maintainers: Vec<Maintainer>,
+    /// 替代注册表的名称，来自 crates.io 或非注册表的依赖为空
+    #[serde(default)]
+    pub registry: Option<String>,
+    /// Cargo.toml 中声明的作者，去掉了邮箱地址
+    #[serde(default)]
+    pub authors: Vec<String>,
 }
 
 #[allow(dead_code)]
 impl DependencyInfo {
-    pub fn to_strings(&self) -> (String, String, String, String, String, String) {
+    const PARENTS_SEPARATOR: &'static str = " → ";
+    /// git 依赖的来源类型，来源链接为仓库地址
+    pub const GIT_SOURCE: &'static str = "Git";
+    /// 本地路径依赖的来源类型
+    pub const PATH_SOURCE: &'static str = "Path";
+    const MEMBERS_SEPARATOR: &'static str = ", ";
+    const TARGETS_SEPARATOR: &'static str = "; ";
+    const AUTHORS_SEPARATOR: &'static str = ", ";
+    const VERSIONS_SEPARATOR: &'static str = ", ";
+
+    /// 是否是 crates.io 上的 crate，替代注册表、git 与本地路径依赖没有 crates.io 页面
+    pub fn is_crates_io_crate(&self) -> bool {
+        self.registry.is_none() && !self.is_unregistered()
+    }
+
+    /// 是否是不来自任何注册表的 git 或本地路径依赖
+    pub fn is_unregistered(&self) -> bool {
+        self.source_type == Self::GIT_SOURCE || self.source_type == Self::PATH_SOURCE
+    }
+
+    /// 深度与引入链路，例如 `2 (cargo-thanku → reqwest)`
+    pub fn to_depth_string(&self) -> String {
+        match (self.depth, self.parents.is_empty()) {
+            (0, _) => String::new(),
+            (depth, true) => depth.to_string(),
+            (depth, false) => format!("{} ({})", depth, self.parents.join(Self::PARENTS_SEPARATOR)),
+        }
+    }
+
+    fn parse_depth(s: &str) -> Result<(u32, Vec<String>)> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok((0, vec![]));
+        }
+
+        let (depth, parents) = match s.split_once('(') {
+            Some((depth, parents)) => (depth.trim(), parents.trim_end_matches(')')),
+            None => (s, ""),
+        };
+
+        let depth = depth
+            .parse::<u32>()
+            .map_err(|_| AppError::InvalidDepth(s.to_string()))?;
+
+        Ok((depth, Self::parse_parents(parents)))
+    }
+
+    /// 解析得到的最新版本，查询 crate 信息时使用
+    pub fn latest_version(&self) -> Option<&str> {
+        self.versions.last().map(String::as_str)
+    }
+
+    /// 解析得到的版本与版本要求，例如 `1.0.219 (^1.0)`，多个版本时为 `0.7.3, 0.8.5 (^0.8)`
+    pub fn to_version_string(&self) -> String {
+        let versions = self.versions.join(Self::VERSIONS_SEPARATOR);
+        match (versions.is_empty(), &self.version_req) {
+            (false, Some(req)) => format!("{} ({})", versions, req),
+            (false, None) => versions,
+            (true, Some(req)) => format!("({})", req),
+            (true, None) => String::new(),
+        }
+    }
+
+    fn parse_version(s: &str) -> Result<(Vec<String>, Option<String>)> {
+        let (versions, req) = match s.trim().split_once('(') {
+            Some((versions, req)) => (versions, req.trim_end_matches(')')),
+            None => (s, ""),
+        };
+        Ok((Self::parse_versions(versions), Self::option_from_str(req)?))
+    }
+
+    fn parse_versions(s: &str) -> Vec<String> {
+        s.split(Self::VERSIONS_SEPARATOR.trim())
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+            .collect()
+    }
+
+    /// 使用该依赖的工作区成员，例如 `cargo-thanku, thanku-core`
+    pub fn to_used_by_string(&self) -> String {
+        self.used_by.join(Self::MEMBERS_SEPARATOR)
+    }
+
+    /// 限定的目标平台，例如 `cfg(unix); cfg(windows)`
+    pub fn to_targets_string(&self) -> String {
+        self.targets.join(Self::TARGETS_SEPARATOR)
+    }
+
+    /// Cargo.toml 中声明的作者，例如 `David Tolnay, Oliver Schneider`
+    pub fn to_authors_string(&self) -> String {
+        self.authors.join(Self::AUTHORS_SEPARATOR)
+    }
+
+    fn parse_authors(s: &str) -> Vec<String> {
+        s.split(Self::AUTHORS_SEPARATOR)
+            .map(|author| author.trim().to_string())
+            .filter(|author| !author.is_empty())
+            .collect()
+    }
+
+    /// 目标平台的 `cfg(...)` 表达式中可能包含逗号，因此只使用 `;` 分隔
+    fn parse_targets(s: &str) -> Vec<String> {
+        s.split(';')
+            .map(|target| target.trim().to_string())
+            .filter(|target| !target.is_empty())
+            .collect()
+    }
+
+    /// 解析工作区成员列表，CSV 中使用 `;` 分隔
+    fn parse_members(s: &str) -> Vec<String> {
+        s.split([',', ';'])
+            .map(|member| member.trim().to_string())
+            .filter(|member| !member.is_empty())
+            .collect()
+    }
+
+    fn parse_parents(s: &str) -> Vec<String> {
+        s.split(Self::PARENTS_SEPARATOR.trim())
+            .map(|parent| parent.trim().to_string())
+            .filter(|parent| !parent.is_empty())
+            .collect()
+    }
+
+    /// 按照链接来源偏好计算 `(crate_url, source_type, source_url)`
+    ///
+    /// - `github`: 保持原样，优先使用仓库链接
+    /// - `crates-io`: 所有链接都指向 crates.io
+    /// - `link-empty`: 不输出任何链接
+    /// - `other`: 来源链接使用主页或文档地址，都没有时保持原样
+    pub fn resolve_links(
+        &self,
+        link_source: &LinkSource,
+    ) -> (Option<String>, String, Option<String>) {
+        match link_source {
+            LinkSource::GitHub => (
+                self.crate_url.clone(),
+                self.source_type.clone(),
+                self.source_url.clone(),
+            ),
+            // 替代注册表、git 与本地路径依赖不在 crates.io 上，保持原样
+            LinkSource::CratesIo if !self.is_crates_io_crate() => (
+                self.crate_url.clone(),
+                self.source_type.clone(),
+                self.source_url.clone(),
+            ),
+            LinkSource::CratesIo => {
+                let crate_url = self
+                    .crate_url
+                    .clone()
+                    .unwrap_or_else(|| CratesioClient::get_crate_url(&self.name));
+                (
+                    Some(crate_url.clone()),
+                    "crates.io".to_string(),
+                    Some(crate_url),
+                )
+            }
+            LinkSource::LinkEmpty => (None, self.source_type.clone(), None),
+            LinkSource::Other => match (&self.homepage, &self.documentation) {
+                (Some(homepage), _) => (
+                    self.crate_url.clone(),
+                    "Homepage".to_string(),
+                    Some(homepage.clone()),
+                ),
+                (None, Some(documentation)) => (
+                    self.crate_url.clone(),
+                    "Documentation".to_string(),
+                    Some(documentation.clone()),
+                ),
+                (None, None) => (
+                    self.crate_url.clone(),
+                    self.source_type.clone(),
+                    self.source_url.clone(),
+                ),
+            },
+        }
+    }
+
+    /// 返回按照链接来源偏好替换链接后的依赖项，用于结构化输出
+    pub fn with_link_source(&self, link_source: &LinkSource) -> Self {
+        let (crate_url, source_type, source_url) = self.resolve_links(link_source);
+        Self {
+            crate_url,
+            source_type,
+            source_url,
+            ..self.clone()
+        }
+    }
+
+    pub fn to_strings(
+        &self,
+        link_source: &LinkSource,
+    ) -> (String, String, String, String, String, String) {
         let name = self.name.clone();
 
         let description = match self.description {
@@ -201,45 +557,105 @@ impl DependencyInfo {
             _ => "❓".to_string(),
         };
 
-        let status = if self.failed {
-            format!("❌ {}", self.error_message.as_deref().unwrap_or("Failed"))
-        } else {
-            "✅".to_string()
+        let status = match self.status {
+            _ if self.failed => {
+                format!("❌ {}", self.error_message.as_deref().unwrap_or("Failed"))
+            }
+            DependencyStatus::Ok => "✅".to_string(),
+            status => format!("⚠️ {}", status),
         };
 
-        let crates_link = if let Some(url) = &self.crate_url {
+        let (crate_url, source_type, source_url) = self.resolve_links(link_source);
+
+        let crates_link = if let Some(url) = &crate_url {
             format!("[{}]({})", self.name, url)
         } else {
             self.name.clone()
         };
 
-        let source_link = if let Some(url) = &self.source_url {
-            format!("[{}]({})", self.source_type, url)
+        let source_link = if let Some(url) = &source_url {
+            format!("[{}]({})", source_type, url)
         } else {
-            self.source_type.clone()
+            source_type
         };
 
         (name, description, crates_link, source_link, stats, status)
     }
 
     pub fn try_from_csv_line(line: &str, header_num: usize) -> Result<Self> {
-        let columns: Vec<&str> = line.split(",").map(|s| s.trim()).collect();
+        let columns = split_csv_line(line);
+        let columns: Vec<&str> = columns.iter().map(|s| s.trim()).collect();
 
         if columns.len() != header_num {
             return Err(AppError::InvalidCsvContent(line.to_string()).into());
         }
 
         let name = columns[0].to_string();
-        let description = if let Some(description) = Self::option_from_str::<String>(columns[1])? {
-            Some(description.replace(";", ","))
-        } else {
-            None
-        };
-        let dependency_kind = DependencyKind::from_str(columns[2])?;
+        let description = Self::option_from_str::<String>(columns[1])?
+            .map(|description| description.replace(";", ","));
+        let dependency_kind = DependencyKinds::from_str(columns[2])?;
         let (_crateio, crate_url) = Self::parse_md_link(columns[3])?;
         let (source_type, source_url) = Self::parse_md_link(columns[4])?;
         let (stars, downloads) = Self::parse_stats(columns[5])?;
-        let (failed, error_message) = Self::parse_status(columns[6])?;
+        let (failed, error_message, status) = Self::parse_status(columns[6])?;
+        // 旧版本的 CSV 没有深度与父级链路列
+        let depth = match columns.get(7) {
+            Some(depth) if !depth.is_empty() => depth
+                .parse::<u32>()
+                .map_err(|_| AppError::InvalidDepth(depth.to_string()))?,
+            _ => 0,
+        };
+        let parents = columns
+            .get(8)
+            .map(|parents| Self::parse_parents(parents))
+            .unwrap_or_default();
+        let used_by = columns
+            .get(9)
+            .map(|members| Self::parse_members(members))
+            .unwrap_or_default();
+        let targets = columns
+            .get(10)
+            .map(|targets| Self::parse_targets(targets))
+            .unwrap_or_default();
+        let license = match columns.get(11) {
+            Some(license) => Self::option_from_str::<String>(license)?,
+            None => None,
+        };
+        let versions = columns
+            .get(12)
+            .map(|versions| Self::parse_versions(versions))
+            .unwrap_or_default();
+        let version_req = match columns.get(13) {
+            Some(req) => Self::option_from_str::<String>(req)?,
+            None => None,
+        };
+        // 最新版本、落后程度、撤回状态与发布日期，全部为空表示没有比较结果
+        let outdated = match columns.get(14..19) {
+            Some([latest_version, lag, yanked, released_at, latest_released_at])
+                if !lag.is_empty() =>
+            {
+                Some(OutdatedInfo {
+                    latest_version: Self::option_from_str(latest_version)?,
+                    lag: lag.parse()?,
+                    yanked: *yanked == "true",
+                    released_at: Self::option_from_str(released_at)?,
+                    latest_released_at: Self::option_from_str(latest_released_at)?,
+                })
+            }
+            _ => None,
+        };
+        let funding = columns
+            .get(19)
+            .map(|sponsor| funding::parse_sponsor_string(sponsor))
+            .unwrap_or_default();
+        let maintainers = columns
+            .get(20)
+            .map(|people| maintainers::parse_maintainers_string(people))
+            .unwrap_or_default();
+        let authors = columns
+            .get(21)
+            .map(|authors| Self::parse_authors(authors))
+            .unwrap_or_default();
 
         let dep = Self {
             name,
@@ -251,31 +667,52 @@ impl DependencyInfo {
             stats: DependencyStats { stars, downloads },
             failed,
             error_message,
+            status,
+            depth,
+            parents,
+            used_by,
+            targets,
+            license,
+            versions,
+            version_req,
+            outdated,
+            funding,
+            maintainers,
+            authors,
+            ..Default::default()
         };
 
         Ok(dep)
     }
 
-    pub fn try_from_md_table_line(line: &str, dependency_kind: &DependencyKind) -> Result<Self> {
-        let columns: Vec<&str> = line
-            .trim_matches(['|', ' ', '\n'])
-            .split("|")
+    /// 解析 Markdown 表格中的一行
+    ///
+    /// `extra_columns` 是表头中基础列之后的附加列，无法识别的列为 `None` 并被忽略
+    pub fn try_from_md_table_line(
+        line: &str,
+        dependency_kind: &DependencyKind,
+        extra_columns: &[Option<TableColumn>],
+    ) -> Result<Self> {
+        // 只去掉首尾的 `|`，保留末尾的空单元格
+        let columns: Vec<&str> = MarkdownTableFormatter::split_table_row(line)
+            .into_iter()
             .map(|s| s.trim())
             .collect();
 
-        if columns.len() != MarkdownTableFormatter::get_column_num() {
+        let column_num = MarkdownTableFormatter::get_column_num();
+        if columns.len() != column_num + extra_columns.len() {
             return Err(AppError::InvalidTableLine(line.to_string()).into());
         }
 
         let name = columns[0].to_string();
         let description = DependencyInfo::option_from_str(columns[1])?;
-        let dependency_kind = dependency_kind.clone();
+        let dependency_kind = DependencyKinds::from(*dependency_kind);
         let (_, crate_url) = Self::parse_md_link(columns[2])?;
         let (source_type, source_url) = Self::parse_md_link(columns[3])?;
         let (stars, downloads) = Self::parse_stats(columns[4])?;
-        let (failed, error_message) = Self::parse_status(columns[5])?;
+        let (failed, error_message, status) = Self::parse_status(columns[5])?;
 
-        let dep = Self {
+        let mut dep = Self {
             name,
             description,
             dependency_kind,
@@ -285,8 +722,16 @@ impl DependencyInfo {
             stats: DependencyStats { stars, downloads },
             failed,
             error_message,
+            status,
+            ..Default::default()
         };
 
+        for (column, cell) in extra_columns.iter().zip(&columns[column_num..]) {
+            if let Some(column) = column {
+                column.parse_cell(&mut dep, cell)?;
+            }
+        }
+
         Ok(dep)
     }
 
@@ -314,18 +759,26 @@ impl DependencyInfo {
 
         let name = parts0[0].trim_start_matches('-').trim().to_string();
         let description = DependencyInfo::option_from_str(parts0[1])?;
-        let dependency_kind = dependency_kind.clone();
-
-        let parts1: Vec<&str> = parts[1].split(")").collect();
+        let dependency_kind = DependencyKinds::from(*dependency_kind);
 
-        if parts1.len() != 4 {
-            return Err(AppError::InvalidListLine(line.to_string()).into());
-        }
-
-        let (_, crate_url) = Self::parse_md_link(format!("{})", parts1[0]).as_str())?;
-        let (source_type, source_url) = Self::parse_md_link(format!("{})", parts1[1]).as_str())?;
-        let (stars, downloads) = Self::parse_stats(format!("{})", parts1[2]).as_str())?;
-        let (failed, error_message) = Self::parse_status(parts1[3])?;
+        // 统计信息总是以 `(🌟`、`(📦` 或 `(❓` 开头，据此切分链接与统计/状态，
+        // 这样链接被省略 (`--source link-empty`) 时也能解析
+        let rest = parts[1].trim();
+        let stats_start = ["(🌟", "(📦", "(❓"]
+            .iter()
+            .filter_map(|prefix| rest.find(prefix))
+            .min()
+            .ok_or_else(|| AppError::InvalidListLine(line.to_string()))?;
+        let (links, rest) = rest.split_at(stats_start);
+        let (stats, status) = rest
+            .split_once(')')
+            .ok_or_else(|| AppError::InvalidListLine(line.to_string()))?;
+        let (crates_link, source_link) = Self::split_md_links(links.trim());
+
+        let (_, crate_url) = Self::parse_md_link(crates_link)?;
+        let (source_type, source_url) = Self::parse_md_link(source_link)?;
+        let (stars, downloads) = Self::parse_stats(stats)?;
+        let (failed, error_message, status) = Self::parse_status(status)?;
 
         let dep = Self {
             name,
@@ -337,11 +790,105 @@ impl DependencyInfo {
             stats: DependencyStats { stars, downloads },
             failed,
             error_message,
+            status,
+            ..Default::default()
         };
 
         Ok(dep)
     }
 
+    /// 将 `[name](url) [source](url)` 切分为 crates 链接与来源链接，两者都可能是纯文本
+    fn split_md_links(s: &str) -> (&str, &str) {
+        let end = if s.starts_with('[') {
+            s.find("](")
+                .and_then(|start| s[start..].find(')').map(|end| start + end + 1))
+        } else {
+            s.find(' ')
+        };
+
+        match end {
+            Some(end) => (s[..end].trim(), s[end..].trim()),
+            None => (s, ""),
+        }
+    }
+
+    /// 解析列表项下的缩进详情行，例如 `  - Depth: 2 (cargo-thanku → reqwest)`
+    ///
+    /// 返回 `false` 表示该行不是当前依赖的详情
+    pub fn try_parse_md_list_detail(&mut self, line: &str) -> Result<bool> {
+        let Some(detail) = line.trim().strip_prefix("- ") else {
+            return Ok(false);
+        };
+
+        if let Some(depth) = detail.strip_prefix(&format!("{}:", t!("output.depth"))) {
+            (self.depth, self.parents) = Self::parse_depth(depth)?;
+            return Ok(true);
+        }
+
+        if let Some(members) = detail.strip_prefix(&format!("{}:", t!("output.used_by"))) {
+            self.used_by = Self::parse_members(members);
+            return Ok(true);
+        }
+
+        if let Some(roles) = detail.strip_prefix(&format!("{}:", t!("output.roles"))) {
+            self.dependency_kind = roles.parse()?;
+            return Ok(true);
+        }
+
+        if let Some(targets) = detail.strip_prefix(&format!("{}:", t!("output.target"))) {
+            self.targets = Self::parse_targets(targets);
+            return Ok(true);
+        }
+
+        if let Some(version) = detail.strip_prefix(&format!("{}:", t!("output.version"))) {
+            (self.versions, self.version_req) = Self::parse_version(version)?;
+            return Ok(true);
+        }
+
+        if let Some(latest) = detail.strip_prefix(&format!("{}:", t!("output.latest"))) {
+            self.outdated
+                .get_or_insert_with(Default::default)
+                .parse_latest(latest);
+            return Ok(true);
+        }
+
+        if let Some(date) = detail.strip_prefix(&format!("{}:", t!("output.released"))) {
+            self.outdated
+                .get_or_insert_with(Default::default)
+                .released_at = Self::option_from_str(date)?;
+            return Ok(true);
+        }
+
+        if let Some(lag) = detail.strip_prefix(&format!("{}:", t!("output.lag"))) {
+            self.outdated
+                .get_or_insert_with(Default::default)
+                .parse_lag(lag)?;
+            return Ok(true);
+        }
+
+        if let Some(license) = detail.strip_prefix(&format!("{}:", t!("output.license"))) {
+            self.license = Self::option_from_str(license.trim())?;
+            return Ok(true);
+        }
+
+        if let Some(sponsor) = detail.strip_prefix(&format!("{}:", t!("output.sponsor"))) {
+            self.funding = funding::parse_sponsor_string(sponsor);
+            return Ok(true);
+        }
+
+        if let Some(people) = detail.strip_prefix(&format!("{}:", t!("output.maintainers"))) {
+            self.maintainers = maintainers::parse_maintainers_string(people);
+            return Ok(true);
+        }
+
+        if let Some(authors) = detail.strip_prefix(&format!("{}:", t!("output.authors"))) {
+            self.authors = Self::parse_authors(authors);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
     const TRIM_PATTERN: [char; 4] = ['[', '(', ' ', ')'];
 
     fn option_from_str<T: FromStr>(s: &str) -> anyhow::Result<Option<T>>
@@ -373,13 +920,13 @@ impl DependencyInfo {
 
         let parts: Vec<&str> = s.split("](").collect();
         let source_type = parts[0]
-            .trim_start_matches(&Self::TRIM_PATTERN)
-            .trim_end_matches(&Self::TRIM_PATTERN);
+            .trim_start_matches(Self::TRIM_PATTERN)
+            .trim_end_matches(Self::TRIM_PATTERN);
         let source_url = if parts.len() > 1 {
             Some(
                 parts[1]
-                    .trim_start_matches(&Self::TRIM_PATTERN)
-                    .trim_end_matches(&Self::TRIM_PATTERN)
+                    .trim_start_matches(Self::TRIM_PATTERN)
+                    .trim_end_matches(Self::TRIM_PATTERN)
                     .to_string(),
             )
         } else {
@@ -399,8 +946,8 @@ impl DependencyInfo {
 
         // we need to parse the string to get the stars and downloads
         let s = s
-            .trim_start_matches(&Self::TRIM_PATTERN)
-            .trim_end_matches(&Self::TRIM_PATTERN);
+            .trim_start_matches(Self::TRIM_PATTERN)
+            .trim_end_matches(Self::TRIM_PATTERN);
 
         match s {
             s if s.contains("🌟") && s.contains("📦") => {
@@ -439,35 +986,148 @@ impl DependencyInfo {
         }
     }
 
-    fn parse_status(s: &str) -> Result<(bool, Option<String>)> {
+    fn parse_status(s: &str) -> Result<(bool, Option<String>, DependencyStatus)> {
         // the output code is like this:
-        // let status = if dep.failed {
-        //     format!("❌ {}", dep.error_message.as_deref().unwrap_or("Failed"))
-        // } else {
-        //     "✅".to_string()
+        // let status = match dep.status {
+        //     _ if dep.failed => format!("❌ {}", dep.error_message...),
+        //     DependencyStatus::Ok => "✅".to_string(),
+        //     status => format!("⚠️ {}", status),
         // };
 
         let s = s
-            .trim_start_matches(&Self::TRIM_PATTERN)
-            .trim_end_matches(&Self::TRIM_PATTERN);
+            .trim_start_matches(Self::TRIM_PATTERN)
+            .trim_end_matches(Self::TRIM_PATTERN);
 
         match s {
-            s if s.contains("✅") => Ok((false, None)),
+            s if s.contains("✅") => Ok((false, None, DependencyStatus::Ok)),
+            s if s.contains("⚠️") => {
+                let parts: Vec<&str> = s.split("⚠️").collect();
+                Ok((false, None, parts[1].parse()?))
+            }
             s if s.contains("❌") => {
                 let parts: Vec<&str> = s.split("❌").collect();
                 let error_message = parts[1].trim();
 
                 if error_message.is_empty() {
-                    return Ok((true, None));
+                    return Ok((true, None, DependencyStatus::Ok));
                 }
 
-                Ok((true, Some(error_message.to_string())))
+                Ok((true, Some(error_message.to_string()), DependencyStatus::Ok))
             }
             _ => Err(AppError::InvalidStatus(s.to_string()).into()),
         }
     }
 }
 
+/// 依赖版本在 crates.io 上的状态
+///
+/// 获取信息失败时由 `failed` 表示，状态保持为 `Ok`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum DependencyStatus {
+    #[default]
+    Ok,
+    /// 代码托管平台上的仓库已归档（只读），弃用或撤回时显示更明确的状态
+    Archived,
+    /// crate 的描述或 README 声明已弃用或不再维护
+    Deprecated,
+    /// 解析得到的版本已被撤回，同时已弃用时也显示为撤回
+    Yanked,
+    /// 已不再是依赖，`--update` 时保留在输出中
+    Removed,
+}
+
+impl DependencyStatus {
+    /// 描述或 README 开头出现这些说法时视为已弃用
+    const DEPRECATION_MARKERS: [&'static str; 11] = [
+        "[deprecated]",
+        "(deprecated)",
+        "deprecated:",
+        "deprecated in favor of",
+        "deprecated in favour of",
+        "crate is deprecated",
+        "crate has been deprecated",
+        "project is deprecated",
+        "project has been deprecated",
+        "no longer maintained",
+        "unmaintained",
+    ];
+
+    /// 只检查 README 的开头，弃用声明通常位于最前面
+    const README_PREFIX_LEN: usize = 2000;
+
+    /// 描述是否声明已弃用，例如 `Deprecated: use foo instead`
+    pub fn is_deprecated_description(description: &str) -> bool {
+        let description = description.trim().to_lowercase();
+        description.starts_with("deprecated") || Self::contains_marker(&description)
+    }
+
+    /// README 的开头是否声明已弃用或不再维护
+    pub fn is_deprecated_readme(readme: &str) -> bool {
+        let prefix = readme
+            .char_indices()
+            .nth(Self::README_PREFIX_LEN)
+            .map_or(readme, |(end, _)| &readme[..end]);
+        Self::contains_marker(&prefix.to_lowercase())
+    }
+
+    /// 仓库已归档且没有更明确的状态时返回 [`Self::Archived`]
+    pub fn or_archived(self, archived: bool) -> Self {
+        match self {
+            Self::Ok if archived => Self::Archived,
+            status => status,
+        }
+    }
+
+    fn contains_marker(text: &str) -> bool {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        Self::DEPRECATION_MARKERS
+            .iter()
+            .any(|marker| text.contains(marker))
+    }
+}
+
+impl std::str::FromStr for DependencyStatus {
+    type Err = AppError;
+
+    /// 同时接受标识符（`yanked`，用于 `--fail-on`）与本地化的名称
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "ok" => return Ok(Self::Ok),
+            "archived" => return Ok(Self::Archived),
+            "deprecated" => return Ok(Self::Deprecated),
+            "yanked" => return Ok(Self::Yanked),
+            "removed" => return Ok(Self::Removed),
+            _ => {}
+        }
+
+        [
+            Self::Ok,
+            Self::Archived,
+            Self::Deprecated,
+            Self::Yanked,
+            Self::Removed,
+        ]
+        .into_iter()
+        .find(|status| status.to_string().eq_ignore_ascii_case(s))
+        .ok_or_else(|| AppError::InvalidStatus(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for DependencyStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ok => t!("output.status_ok"),
+            Self::Archived => t!("output.status_archived"),
+            Self::Deprecated => t!("output.status_deprecated"),
+            Self::Yanked => t!("output.status_yanked"),
+            Self::Removed => t!("output.status_removed"),
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
 /// 依赖项的统计信息
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct DependencyStats {
@@ -483,19 +1143,194 @@ pub trait Formatter {
 
 impl dyn Formatter {
     pub fn new(format: OutputFormat) -> Result<Box<dyn Formatter>> {
-        Ok(match format {
-            OutputFormat::MarkdownTable => Box::new(MarkdownTableFormatter),
-            OutputFormat::MarkdownList => Box::new(MarkdownListFormatter),
-            OutputFormat::Csv => Box::new(CsvFormatter),
-            OutputFormat::Json => Box::new(JsonFormatter),
-            OutputFormat::Yaml => Box::new(YamlFormatter),
-            OutputFormat::Toml => Box::new(TomlFormatter),
-        })
+        Ok(Self::with_options(format, &FormatOptions::default()))
+    }
+
+    pub fn with_options(format: OutputFormat, options: &FormatOptions) -> Box<dyn Formatter> {
+        let link_source = options.link_source;
+        match format {
+            OutputFormat::MarkdownTable => Box::new(MarkdownTableFormatter { link_source }),
+            OutputFormat::MarkdownList => Box::new(MarkdownListFormatter { link_source }),
+            OutputFormat::Csv => Box::new(CsvFormatter { link_source }),
+            OutputFormat::Json => Box::new(JsonFormatter { link_source }),
+            OutputFormat::Yaml => Box::new(YamlFormatter { link_source }),
+            OutputFormat::Toml => Box::new(TomlFormatter { link_source }),
+        }
+    }
+}
+
+/// Markdown 表格中按需显示的附加列，位于基础列之后
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableColumn {
+    /// 解析得到的版本与版本要求，存在版本信息时显示
+    Version,
+    /// 最新的稳定版本与发布日期，存在比较结果时显示
+    Latest,
+    /// 解析得到的版本的发布日期，存在比较结果时显示
+    Released,
+    /// 落后于最新版本的程度与撤回状态，存在比较结果时显示
+    Lag,
+    /// 深度与引入链路，包含传递依赖时显示
+    Depth,
+    /// 使用该依赖的工作区成员，包含多个成员时显示
+    UsedBy,
+    /// 所有的依赖类型，存在多种用法的依赖时显示
+    Roles,
+    /// 限定的目标平台，存在平台相关的依赖时显示
+    Target,
+    /// SPDX 许可证表达式，存在已知许可证的依赖时显示
+    License,
+    /// 赞助链接，存在可以赞助的依赖时显示
+    Sponsor,
+    /// 所有者与主要贡献者，存在维护者信息时显示
+    Maintainers,
+    /// Cargo.toml 中声明的作者，存在作者信息时显示
+    Authors,
+}
+
+impl TableColumn {
+    /// 根据依赖项决定需要显示的附加列
+    fn for_dependencies(deps: &[DependencyInfo]) -> Vec<Self> {
+        let mut columns = vec![];
+        if deps
+            .iter()
+            .any(|dep| !dep.versions.is_empty() || dep.version_req.is_some())
+        {
+            columns.push(Self::Version);
+        }
+        if deps.iter().any(|dep| dep.outdated.is_some()) {
+            columns.extend([Self::Latest, Self::Released, Self::Lag]);
+        }
+        if has_transitive_dependencies(deps) {
+            columns.push(Self::Depth);
+        }
+        if has_multiple_members(deps) {
+            columns.push(Self::UsedBy);
+        }
+        if deps.iter().any(|dep| dep.dependency_kind.len() > 1) {
+            columns.push(Self::Roles);
+        }
+        if deps.iter().any(|dep| !dep.targets.is_empty()) {
+            columns.push(Self::Target);
+        }
+        if has_licenses(deps) {
+            columns.push(Self::License);
+        }
+        if deps.iter().any(|dep| !dep.funding.is_empty()) {
+            columns.push(Self::Sponsor);
+        }
+        if has_maintainers(deps) {
+            columns.push(Self::Maintainers);
+        }
+        if deps.iter().any(|dep| !dep.authors.is_empty()) {
+            columns.push(Self::Authors);
+        }
+        columns
+    }
+
+    fn header(&self) -> String {
+        match self {
+            Self::Version => t!("output.version").to_string(),
+            Self::Latest => t!("output.latest").to_string(),
+            Self::Released => t!("output.released").to_string(),
+            Self::Lag => t!("output.lag").to_string(),
+            Self::Depth => t!("output.depth").to_string(),
+            Self::UsedBy => t!("output.used_by").to_string(),
+            Self::Roles => t!("output.roles").to_string(),
+            Self::Target => t!("output.target").to_string(),
+            Self::License => t!("output.license").to_string(),
+            Self::Sponsor => t!("output.sponsor").to_string(),
+            Self::Maintainers => t!("output.maintainers").to_string(),
+            Self::Authors => t!("output.authors").to_string(),
+        }
+    }
+
+    fn from_header(s: &str) -> Option<Self> {
+        [
+            Self::Version,
+            Self::Latest,
+            Self::Released,
+            Self::Lag,
+            Self::Depth,
+            Self::UsedBy,
+            Self::Roles,
+            Self::Target,
+            Self::License,
+            Self::Sponsor,
+            Self::Maintainers,
+            Self::Authors,
+        ]
+        .into_iter()
+        .find(|column| column.header().eq_ignore_ascii_case(s.trim()))
+    }
+
+    fn format_cell(&self, dep: &DependencyInfo) -> String {
+        match self {
+            Self::Version => dep.to_version_string(),
+            Self::Latest => dep
+                .outdated
+                .as_ref()
+                .map(OutdatedInfo::to_latest_string)
+                .unwrap_or_default(),
+            Self::Released => dep
+                .outdated
+                .as_ref()
+                .and_then(|outdated| outdated.released_at.clone())
+                .unwrap_or_default(),
+            Self::Lag => dep
+                .outdated
+                .as_ref()
+                .map(OutdatedInfo::to_lag_string)
+                .unwrap_or_default(),
+            Self::Depth => dep.to_depth_string(),
+            Self::UsedBy => dep.to_used_by_string(),
+            Self::Roles => dep.dependency_kind.to_string(),
+            Self::Target => dep.to_targets_string(),
+            Self::License => dep.license.clone().unwrap_or_default(),
+            Self::Sponsor => funding::to_sponsor_string(&dep.funding),
+            Self::Maintainers => maintainers::to_maintainers_string(&dep.maintainers),
+            Self::Authors => dep.to_authors_string(),
+        }
+    }
+
+    fn parse_cell(&self, dep: &mut DependencyInfo, cell: &str) -> Result<()> {
+        match self {
+            Self::Version => (dep.versions, dep.version_req) = DependencyInfo::parse_version(cell)?,
+            // 为空表示该依赖没有比较结果
+            Self::Latest | Self::Released | Self::Lag if cell.trim().is_empty() => {}
+            Self::Latest => dep
+                .outdated
+                .get_or_insert_with(Default::default)
+                .parse_latest(cell),
+            Self::Released => {
+                dep.outdated
+                    .get_or_insert_with(Default::default)
+                    .released_at = DependencyInfo::option_from_str(cell)?
+            }
+            Self::Lag => dep
+                .outdated
+                .get_or_insert_with(Default::default)
+                .parse_lag(cell)?,
+            Self::Depth => (dep.depth, dep.parents) = DependencyInfo::parse_depth(cell)?,
+            Self::UsedBy => dep.used_by = DependencyInfo::parse_members(cell),
+            // 为空时保留所在分组的依赖类型
+            Self::Roles if cell.trim().is_empty() => {}
+            Self::Roles => dep.dependency_kind = cell.parse()?,
+            Self::Target => dep.targets = DependencyInfo::parse_targets(cell),
+            Self::License => dep.license = DependencyInfo::option_from_str(cell.trim())?,
+            Self::Sponsor => dep.funding = funding::parse_sponsor_string(cell),
+            Self::Maintainers => dep.maintainers = maintainers::parse_maintainers_string(cell),
+            Self::Authors => dep.authors = DependencyInfo::parse_authors(cell),
+        }
+        Ok(())
     }
 }
 
 /// Markdown 表格格式化器
-pub struct MarkdownTableFormatter;
+#[derive(Default)]
+pub struct MarkdownTableFormatter {
+    link_source: LinkSource,
+}
 
 impl MarkdownTableFormatter {
     fn get_header() -> impl AsRef<str> {
@@ -633,17 +1468,8 @@ impl MarkdownTableFormatter {
         let mut cells = Vec::new();
 
         // 处理以'|'开头和结尾的情况
-        let processed_line = if line.starts_with('|') {
-            if line.ends_with('|') {
-                &line[1..line.len() - 1]
-            } else {
-                &line[1..]
-            }
-        } else if line.ends_with('|') {
-            &line[..line.len() - 1]
-        } else {
-            line
-        };
+        let processed_line = line.strip_prefix('|').unwrap_or(line);
+        let processed_line = processed_line.strip_suffix('|').unwrap_or(processed_line);
 
         // 分割单元格
         for cell in processed_line.split('|') {
@@ -657,15 +1483,21 @@ impl MarkdownTableFormatter {
 impl Formatter for MarkdownTableFormatter {
     fn format(&self, deps: &[DependencyInfo]) -> Result<String> {
         let mut output = String::new();
+        // 深度列与使用者列只在需要时显示
+        let extra_columns = TableColumn::for_dependencies(deps);
 
         // 表头
         output.push_str(&format!(
-            "\n{}\n",
+            "\n{}",
             MarkdownTableFormatter::get_header().as_ref()
         ));
+        for column in &extra_columns {
+            output.push_str(&format!(" {} |", column.header()));
+        }
         output.push_str(&format!(
-            "{}\n",
-            MarkdownTableFormatter::get_separator().as_ref()
+            "\n{}{}\n",
+            MarkdownTableFormatter::get_separator().as_ref(),
+            "---|".repeat(extra_columns.len())
         ));
 
         let dep_kind_order = vec![
@@ -683,18 +1515,30 @@ impl Formatter for MarkdownTableFormatter {
 
             for dep in deps {
                 if show_header {
-                    output.push_str(&format!("{}\n", header.as_ref()));
+                    output.push_str(&format!(
+                        "{}{}\n",
+                        header.as_ref(),
+                        " |".repeat(extra_columns.len())
+                    ));
                     show_header = false;
                 }
-                let (name, description, crates_link, source_link, stats, status) = dep.to_strings();
+                let (name, description, crates_link, source_link, stats, status) =
+                    dep.to_strings(&self.link_source);
 
                 output.push_str(&format!(
-                    "| {} | {} | {} | {} | {} | {} |\n",
+                    "| {} | {} | {} | {} | {} | {} |",
                     name, description, crates_link, source_link, stats, status
                 ));
+                for column in &extra_columns {
+                    output.push_str(&format!(" {} |", column.format_cell(dep)));
+                }
+                output.push('\n');
             }
         }
 
+        output.push_str(&format_license_summary(deps));
+        output.push_str(&format_people_to_thank(deps));
+
         Ok(output)
     }
 
@@ -709,6 +1553,17 @@ impl Formatter for MarkdownTableFormatter {
 
         let md_table = first_md_table.unwrap();
 
+        // 表头中基础列之后的附加列
+        let extra_columns = md_table
+            .lines()
+            .next()
+            .map(MarkdownTableFormatter::split_table_row)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(MarkdownTableFormatter::get_column_num())
+            .map(TableColumn::from_header)
+            .collect::<Vec<_>>();
+
         let mut deps = vec![];
         let mut dependency_kind = DependencyKind::Unknown;
         // skip the first two lines (header and separator)
@@ -736,28 +1591,211 @@ impl Formatter for MarkdownTableFormatter {
                 _ => {}
             };
 
-            let dep = DependencyInfo::try_from_md_table_line(line, &dependency_kind)?;
-            deps.push(dep);
-        }
+            let dep =
+                DependencyInfo::try_from_md_table_line(line, &dependency_kind, &extra_columns)?;
+            deps.push(dep);
+        }
+
+        Ok(deps)
+    }
+}
+
+/// 按照链接来源偏好替换所有依赖项的链接
+fn with_link_source(deps: &[DependencyInfo], link_source: &LinkSource) -> Vec<DependencyInfo> {
+    deps.iter()
+        .map(|dep| dep.with_link_source(link_source))
+        .collect()
+}
+
+/// 是否包含直接依赖之外的传递依赖
+fn has_transitive_dependencies(deps: &[DependencyInfo]) -> bool {
+    deps.iter().any(|dep| dep.depth > 1)
+}
+
+/// 是否有依赖项带有许可证信息
+fn has_licenses(deps: &[DependencyInfo]) -> bool {
+    deps.iter().any(|dep| dep.license.is_some())
+}
+
+/// 按许可证统计 crate 数量，按数量降序、名称升序排列
+///
+/// 没有许可证信息的依赖归入“未知”
+pub fn license_summary(deps: &[DependencyInfo]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for dep in deps {
+        let license = match &dep.license {
+            Some(license) => license.clone(),
+            None => t!("output.license_unknown").to_string(),
+        };
+        *counts.entry(license).or_default() += 1;
+    }
+
+    let mut summary = counts.into_iter().collect::<Vec<_>>();
+    summary.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    summary
+}
+
+/// Markdown 输出末尾的许可证汇总，没有任何许可证信息时为空
+///
+/// 使用 `###` 标题，解析时不会被当作依赖列表的一部分
+fn format_license_summary(deps: &[DependencyInfo]) -> String {
+    if !has_licenses(deps) {
+        return String::new();
+    }
+
+    let mut output = format!("\n### {}\n\n", t!("output.license_summary"));
+    for (license, count) in license_summary(deps) {
+        output.push_str(&format!("- {}: {}\n", license, count));
+    }
+    output
+}
+
+fn has_maintainers(deps: &[DependencyInfo]) -> bool {
+    deps.iter().any(|dep| !dep.maintainers.is_empty())
+}
+
+/// 按维护者汇总其维护的 crates，按 crate 数量降序、登录名升序排列
+///
+/// 登录名不区分大小写，同一个人在不同 crate 中的信息以第一次出现的为准
+pub fn people_to_thank(deps: &[DependencyInfo]) -> Vec<(Maintainer, Vec<String>)> {
+    let mut people: Vec<(Maintainer, Vec<String>)> = vec![];
+    for dep in deps {
+        for maintainer in &dep.maintainers {
+            let index = match people
+                .iter()
+                .position(|(person, _)| person.login.eq_ignore_ascii_case(&maintainer.login))
+            {
+                Some(index) => index,
+                None => {
+                    people.push((maintainer.clone(), vec![]));
+                    people.len() - 1
+                }
+            };
+            let crates = &mut people[index].1;
+            if !crates.contains(&dep.name) {
+                crates.push(dep.name.clone());
+            }
+        }
+    }
+
+    for (_, crates) in &mut people {
+        crates.sort();
+    }
+    people.sort_by(|(a, a_crates), (b, b_crates)| {
+        b_crates
+            .len()
+            .cmp(&a_crates.len())
+            .then_with(|| a.login.to_lowercase().cmp(&b.login.to_lowercase()))
+    });
+    people
+}
+
+/// Markdown 输出末尾的致谢名单，没有任何维护者信息时为空
+///
+/// 与许可证汇总一样使用 `###` 标题，解析时不会被当作依赖列表的一部分
+fn format_people_to_thank(deps: &[DependencyInfo]) -> String {
+    if !has_maintainers(deps) {
+        return String::new();
+    }
+
+    let mut output = format!("\n### {}\n\n", t!("output.people_to_thank"));
+    for (maintainer, crates) in people_to_thank(deps) {
+        output.push_str(&format!(
+            "- {}: {}\n",
+            maintainers::to_maintainer_string(&maintainer),
+            crates.join(", ")
+        ));
+    }
+    output
+}
+
+/// 依赖项是否来自多个工作区成员
+fn has_multiple_members(deps: &[DependencyInfo]) -> bool {
+    let mut members = deps.iter().flat_map(|dep| &dep.used_by);
+    members
+        .next()
+        .is_some_and(|first| members.any(|member| member != first))
+}
 
-        Ok(deps)
+/// 按工作区成员拆分依赖项，结果按成员名称排序
+///
+/// 每个成员的视图中不再包含使用者信息
+pub fn split_by_member(deps: &[DependencyInfo]) -> Vec<(String, Vec<DependencyInfo>)> {
+    let mut members = std::collections::BTreeMap::<String, Vec<DependencyInfo>>::new();
+    for dep in deps {
+        for member in &dep.used_by {
+            members
+                .entry(member.clone())
+                .or_default()
+                .push(DependencyInfo {
+                    used_by: vec![],
+                    ..dep.clone()
+                });
+        }
     }
+    members.into_iter().collect()
+}
+
+/// `--update` 时合并已有输出中解析得到的依赖与新的结果
+///
+/// - 以新的结果为准，但已有输出中的描述视为手动修改，优先保留
+/// - 新增的依赖直接加入
+/// - 已不再是依赖的 crate 保留原有的信息，状态标记为 [`DependencyStatus::Removed`]
+/// - 上一次已经标记为移除的 crate 不再保留，已移除的依赖只在一次更新中可见
+pub fn merge_update(
+    previous: Vec<DependencyInfo>,
+    current: &[DependencyInfo],
+) -> Vec<DependencyInfo> {
+    let mut previous = previous
+        .into_iter()
+        .map(|dep| (dep.name.clone(), dep))
+        .collect::<std::collections::BTreeMap<_, _>>();
+
+    let mut merged = current
+        .iter()
+        .map(|dep| {
+            // 没有描述时输出为 `unknown`，不是手动修改的内容
+            let description = previous
+                .remove(&dep.name)
+                .and_then(|previous| previous.description)
+                .filter(|description| description != "unknown");
+            DependencyInfo {
+                description: description.or_else(|| dep.description.clone()),
+                ..dep.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    merged.extend(
+        previous
+            .into_values()
+            .filter(|dep| dep.status != DependencyStatus::Removed)
+            .map(|dep| DependencyInfo {
+                status: DependencyStatus::Removed,
+                ..dep
+            }),
+    );
+    merged
 }
 
 fn take_sort_dependencies<'a>(
     deps: &'a [DependencyInfo],
     kind: &DependencyKind,
 ) -> Vec<&'a DependencyInfo> {
+    // 每个依赖只出现一次，位于其主要依赖类型的分组中
     let mut filter_sorted_deps = deps
         .iter()
-        .filter(|dep| dep.dependency_kind == *kind)
+        .filter(|dep| dep.dependency_kind.primary() == *kind)
         .collect::<Vec<_>>();
     filter_sorted_deps.sort_by(|a, b| a.name.cmp(&b.name));
     filter_sorted_deps
 }
 
 /// Markdown 列表格式化器
-pub struct MarkdownListFormatter;
+#[derive(Default)]
+pub struct MarkdownListFormatter {
+    link_source: LinkSource,
+}
 
 impl MarkdownListFormatter {
     fn get_header() -> impl AsRef<str> {
@@ -788,19 +1826,20 @@ impl MarkdownListFormatter {
 
         // 找到列表的结束位置
         // 可能是下一个同级标题或文档结束
-        let end_pos = headers
+        let next_header_pos = headers
             .iter()
             .skip(start_header_idx + 1)
             .find(|m| content[m.start()..].starts_with("# "))
-            .map(|m| m.start())
-            .unwrap_or_else(|| {
-                // 如果没有下一个主标题，查找可能的其他结束标记
-                // 例如 "### " 开头的标题或文档结束
-                content[start_pos..]
-                    .find("\n### ")
-                    .map(|pos| start_pos + pos)
-                    .unwrap_or(content.len())
-            });
+            .map(|m| m.start());
+        // "### " 开头的标题（例如许可证汇总）也是列表的结束标记
+        let sub_header_pos = content[start_pos..]
+            .find("\n### ")
+            .map(|pos| start_pos + pos);
+        let end_pos = [next_header_pos, sub_header_pos]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(content.len());
 
         // 提取列表内容
         let list_content = &content[start_pos..end_pos];
@@ -831,6 +1870,9 @@ impl MarkdownListFormatter {
 impl Formatter for MarkdownListFormatter {
     fn format(&self, deps: &[DependencyInfo]) -> Result<String> {
         let mut output = String::new();
+        // 只有包含传递依赖时才显示深度详情，只有包含多个成员时才显示使用者
+        let show_depth = has_transitive_dependencies(deps);
+        let show_used_by = has_multiple_members(deps);
         output.push_str(&format!(
             "\n{}\n",
             MarkdownListFormatter::get_header().as_ref()
@@ -854,15 +1896,93 @@ impl Formatter for MarkdownListFormatter {
                     output.push_str(&format!("\n{}\n", header.as_ref()));
                     show_header = false;
                 }
-                let (name, description, crates_link, source_link, stats, status) = dep.to_strings();
+                let (name, description, crates_link, source_link, stats, status) =
+                    dep.to_strings(&self.link_source);
 
                 output.push_str(&format!(
                     "- {} : {} - {} {} ({}) {}\n",
                     name, description, crates_link, source_link, stats, status
                 ));
+                if !dep.versions.is_empty() || dep.version_req.is_some() {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.version"),
+                        dep.to_version_string()
+                    ));
+                }
+                if let Some(outdated) = &dep.outdated {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.latest"),
+                        outdated.to_latest_string()
+                    ));
+                    if let Some(date) = &outdated.released_at {
+                        output.push_str(&format!("  - {}: {}\n", t!("output.released"), date));
+                    }
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.lag"),
+                        outdated.to_lag_string()
+                    ));
+                }
+                if show_depth {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.depth"),
+                        dep.to_depth_string()
+                    ));
+                }
+                if show_used_by {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.used_by"),
+                        dep.to_used_by_string()
+                    ));
+                }
+                if dep.dependency_kind.len() > 1 {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.roles"),
+                        dep.dependency_kind
+                    ));
+                }
+                if !dep.targets.is_empty() {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.target"),
+                        dep.to_targets_string()
+                    ));
+                }
+                if let Some(license) = &dep.license {
+                    output.push_str(&format!("  - {}: {}\n", t!("output.license"), license));
+                }
+                if !dep.funding.is_empty() {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.sponsor"),
+                        funding::to_sponsor_string(&dep.funding)
+                    ));
+                }
+                if !dep.maintainers.is_empty() {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.maintainers"),
+                        maintainers::to_maintainers_string(&dep.maintainers)
+                    ));
+                }
+                if !dep.authors.is_empty() {
+                    output.push_str(&format!(
+                        "  - {}: {}\n",
+                        t!("output.authors"),
+                        dep.to_authors_string()
+                    ));
+                }
             }
         }
 
+        output.push_str(&format_license_summary(deps));
+        output.push_str(&format_people_to_thank(deps));
+
         Ok(output)
     }
 
@@ -878,10 +1998,18 @@ impl Formatter for MarkdownListFormatter {
 
         let md_list = first_md_list.unwrap();
 
-        let mut deps = vec![];
+        let mut deps: Vec<DependencyInfo> = vec![];
         let mut dependency_kind = DependencyKind::Unknown;
         // skip the first two lines (header and separator)
         for line in md_list.lines() {
+            // 缩进的详情行属于上一个依赖
+            if line.starts_with([' ', '\t'])
+                && let Some(dep) = deps.last_mut()
+                && dep.try_parse_md_list_detail(line)?
+            {
+                continue;
+            }
+
             let line = line.trim();
             match line {
                 line if line.contains(DependencyKind::Normal.to_md_list_header().as_ref()) => {
@@ -917,11 +2045,15 @@ impl Formatter for MarkdownListFormatter {
 }
 
 /// JSON 格式化器
-pub struct JsonFormatter;
+#[derive(Default)]
+pub struct JsonFormatter {
+    link_source: LinkSource,
+}
 
 impl Formatter for JsonFormatter {
     fn format(&self, deps: &[DependencyInfo]) -> Result<String> {
-        Ok(serde_json::to_string_pretty(deps)?)
+        let deps = with_link_source(deps, &self.link_source);
+        Ok(serde_json::to_string_pretty(&deps)?)
     }
 
     fn parse(&self, content: &str) -> Result<Vec<DependencyInfo>> {
@@ -930,7 +2062,10 @@ impl Formatter for JsonFormatter {
 }
 
 // TOML 格式化器
-pub struct TomlFormatter;
+#[derive(Default)]
+pub struct TomlFormatter {
+    link_source: LinkSource,
+}
 
 impl Formatter for TomlFormatter {
     fn format(&self, deps: &[DependencyInfo]) -> Result<String> {
@@ -942,7 +2077,7 @@ impl Formatter for TomlFormatter {
 
         // Ok(toml::to_string_pretty(&deps_map)?)
         let deps_list = DependencyList {
-            dependencies: deps.to_vec(),
+            dependencies: with_link_source(deps, &self.link_source),
         };
 
         Ok(toml::to_string_pretty(&deps_list)?)
@@ -955,11 +2090,15 @@ impl Formatter for TomlFormatter {
 }
 
 /// YAML 格式化器
-pub struct YamlFormatter;
+#[derive(Default)]
+pub struct YamlFormatter {
+    link_source: LinkSource,
+}
 
 impl Formatter for YamlFormatter {
     fn format(&self, deps: &[DependencyInfo]) -> Result<String> {
-        Ok(serde_yaml::to_string(deps)?)
+        let deps = with_link_source(deps, &self.link_source);
+        Ok(serde_yaml::to_string(&deps)?)
     }
 
     fn parse(&self, content: &str) -> Result<Vec<DependencyInfo>> {
@@ -967,14 +2106,52 @@ impl Formatter for YamlFormatter {
     }
 }
 
+/// 包含逗号或引号的 CSV 字段使用双引号包裹
+fn quote_csv_field(s: &str) -> String {
+    if s.contains([',', '"']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// 按逗号切分 CSV 行，双引号包裹的字段中可以包含逗号，`""` 表示一个引号
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut columns = vec![];
+    let mut column = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                column.push('"');
+                chars.next();
+            }
+            '"' if quoted || column.trim().is_empty() => quoted = !quoted,
+            ',' if !quoted => columns.push(std::mem::take(&mut column)),
+            c => column.push(c),
+        }
+    }
+    columns.push(column);
+
+    columns
+}
+
 /// CSV 格式化器
-pub struct CsvFormatter;
+#[derive(Default)]
+pub struct CsvFormatter {
+    link_source: LinkSource,
+}
 
 impl CsvFormatter {
     fn get_header() -> impl AsRef<str> {
         t!("output.csv_header").replace("，", ",")
     }
 
+    /// 不包含深度与父级链路列的旧版本 CSV 列数
+    const LEGACY_COLUMN_NUM: usize = 7;
+
     fn column_num() -> usize {
         CsvFormatter::get_header().as_ref().split(",").count()
     }
@@ -987,13 +2164,56 @@ impl Formatter for CsvFormatter {
         output.push_str(&format!("\n{}\n", header.as_ref()));
 
         for dep in deps {
-            let (name, description, crates_link, source_link, stats, status) = dep.to_strings();
+            let (name, description, crates_link, source_link, stats, status) =
+                dep.to_strings(&self.link_source);
             let description = description.replace(",", ";");
-            let dependency_kind = dep.dependency_kind.to_string();
+            let dependency_kind = dep.dependency_kind.to_csv_string();
+            let depth = match dep.depth {
+                0 => String::new(),
+                depth => depth.to_string(),
+            };
+            let parents = dep.parents.join(DependencyInfo::PARENTS_SEPARATOR);
+            let used_by = dep.used_by.join("; ");
+            let targets = quote_csv_field(&dep.to_targets_string());
+            let license = quote_csv_field(dep.license.as_deref().unwrap_or_default());
+            let versions = quote_csv_field(&dep.versions.join(DependencyInfo::VERSIONS_SEPARATOR));
+            let version_req = quote_csv_field(dep.version_req.as_deref().unwrap_or_default());
+            let outdated = match &dep.outdated {
+                Some(outdated) => [
+                    outdated.latest_version.clone().unwrap_or_default(),
+                    outdated.lag.to_string(),
+                    if outdated.yanked { "true" } else { "" }.to_string(),
+                    outdated.released_at.clone().unwrap_or_default(),
+                    outdated.latest_released_at.clone().unwrap_or_default(),
+                ]
+                .join(","),
+                None => ",,,,".to_string(),
+            };
+            let sponsor = quote_csv_field(&funding::to_sponsor_string(&dep.funding));
+            let maintainers =
+                quote_csv_field(&maintainers::to_maintainers_string(&dep.maintainers));
+            let authors = quote_csv_field(&dep.to_authors_string());
 
             output.push_str(&format!(
-                "{},{},{},{},{},{},{}\n",
-                name, description, dependency_kind, crates_link, source_link, stats, status,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                name,
+                description,
+                dependency_kind,
+                crates_link,
+                source_link,
+                stats,
+                status,
+                depth,
+                parents,
+                used_by,
+                targets,
+                license,
+                versions,
+                version_req,
+                outdated,
+                sponsor,
+                maintainers,
+                authors,
             ));
         }
 
@@ -1012,7 +2232,7 @@ impl Formatter for CsvFormatter {
         let columns = header.unwrap().split(",").collect::<Vec<_>>();
         let column_num = columns.len();
 
-        if column_num != CsvFormatter::column_num() {
+        if !(CsvFormatter::LEGACY_COLUMN_NUM..=CsvFormatter::column_num()).contains(&column_num) {
             return Err(AppError::InvalidCsvContent(content.to_string()).into());
         }
 
@@ -1026,28 +2246,71 @@ impl Formatter for CsvFormatter {
     }
 }
 
+/// 格式化选项
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// 只输出深度在限制范围内的依赖，`None` 表示不过滤
+    pub depth: Option<DepthLimit>,
+    /// 链接来源偏好
+    pub link_source: LinkSource,
+    /// 只输出存在赞助链接的依赖
+    pub sponsorable_only: bool,
+}
+
+impl FormatOptions {
+    pub fn filter(&self, deps: &[DependencyInfo]) -> Vec<DependencyInfo> {
+        deps.iter()
+            .filter(|dep| self.depth.is_none_or(|depth| depth.allows(dep.depth)))
+            .filter(|dep| !self.sponsorable_only || !dep.funding.is_empty())
+            .cloned()
+            .collect()
+    }
+}
+
 // 输出管理器
 pub struct OutputManager<W: Write> {
     formatter: Box<dyn Formatter>,
     writer: W,
+    options: FormatOptions,
 }
 
 impl<W: Write> OutputManager<W> {
+    #[allow(unused)]
     pub fn new(format: OutputFormat, writer: W) -> Self {
-        let formatter: Box<dyn Formatter> = match format {
-            OutputFormat::MarkdownTable => Box::new(MarkdownTableFormatter),
-            OutputFormat::MarkdownList => Box::new(MarkdownListFormatter),
-            OutputFormat::Json => Box::new(JsonFormatter),
-            OutputFormat::Toml => Box::new(TomlFormatter),
-            OutputFormat::Yaml => Box::new(YamlFormatter),
-            OutputFormat::Csv => Box::new(CsvFormatter),
-        };
+        Self::new_with_options(format, writer, FormatOptions::default())
+    }
 
-        Self { formatter, writer }
+    pub fn new_with_options(format: OutputFormat, writer: W, options: FormatOptions) -> Self {
+        let formatter = <dyn Formatter>::with_options(format, &options);
+
+        Self {
+            formatter,
+            writer,
+            options,
+        }
     }
 
     pub fn write(&mut self, deps: &[DependencyInfo]) -> Result<()> {
-        let content = self.formatter.format(deps)?;
+        let deps = self.options.filter(deps);
+        let content = self.formatter.format(&deps)?;
+        self.writer.write_all(content.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// 先输出合并视图，再为每个工作区成员输出一节
+    ///
+    /// 只适用于 Markdown 格式，解析时只会读取第一节的合并视图
+    pub fn write_per_package(&mut self, deps: &[DependencyInfo]) -> Result<()> {
+        let deps = self.options.filter(deps);
+        let mut content = self.formatter.format(&deps)?;
+        for (member, member_deps) in split_by_member(&deps) {
+            content.push_str(&format!(
+                "\n# {}\n",
+                t!("output.package_section", name = member)
+            ));
+            content.push_str(&self.formatter.format(&member_deps)?);
+        }
         self.writer.write_all(content.as_bytes())?;
         self.writer.flush()?;
         Ok(())
@@ -1069,7 +2332,8 @@ impl From<(&str, &Source)> for DependencyInfo {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
             Source::CratesIo { downloads, .. } => Self {
                 name: name.to_string(),
@@ -1083,7 +2347,8 @@ impl From<(&str, &Source)> for DependencyInfo {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
             Source::Link { url } => Self {
                 name: name.to_string(),
@@ -1097,7 +2362,8 @@ impl From<(&str, &Source)> for DependencyInfo {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
             Source::Other { description } => Self {
                 name: name.to_string(),
@@ -1111,7 +2377,8 @@ impl From<(&str, &Source)> for DependencyInfo {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
         }
     }
@@ -1122,6 +2389,7 @@ mod tests {
     use crate::config::Config;
 
     use super::*;
+    use crate::outdated::VersionLag;
 
     #[test]
     fn test_toml_formatter_with_hashmap() {
@@ -1141,7 +2409,8 @@ mod tests {
                 },
                 failed: false,
                 error_message: Some("".to_string()),
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
             DependencyInfo {
                 name: "test".to_string(),
@@ -1158,7 +2427,8 @@ mod tests {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Development,
+                dependency_kind: DependencyKind::Development.into(),
+                ..Default::default()
             },
         ];
 
@@ -1168,7 +2438,7 @@ mod tests {
             .map(|dep| (dep.name.clone(), dep))
             .collect();
 
-        // let formatter = TomlFormatter;
+        // let formatter = TomlFormatter::default();
         let result = toml::to_string(&deps_map).unwrap();
         println!("{}", result);
         assert!(result.contains("name = \"serde\""));
@@ -1192,7 +2462,8 @@ mod tests {
                 },
                 failed: false,
                 error_message: Some("".to_string()),
-                dependency_kind: DependencyKind::Normal,
+                dependency_kind: DependencyKind::Normal.into(),
+                ..Default::default()
             },
             DependencyInfo {
                 name: "test".to_string(),
@@ -1209,11 +2480,12 @@ mod tests {
                 },
                 failed: false,
                 error_message: None,
-                dependency_kind: DependencyKind::Development,
+                dependency_kind: DependencyKind::Development.into(),
+                ..Default::default()
             },
         ];
 
-        let formatter = TomlFormatter;
+        let formatter = TomlFormatter::default();
         let result = formatter.format(&deps).unwrap();
         println!("{}", result);
         assert!(result.contains("name = \"serde\""));
@@ -1242,10 +2514,11 @@ mod tests {
             },
             failed: false,
             error_message: None,
-            dependency_kind: DependencyKind::Normal,
+            dependency_kind: DependencyKind::Normal.into(),
+            ..Default::default()
         }];
 
-        let formatter = MarkdownTableFormatter;
+        let formatter = MarkdownTableFormatter::default();
         let result = formatter.format(&deps).unwrap();
         println!("{}", result);
         assert!(result.contains("| [serde](https://crates.io/crates/serde) |"));
@@ -1269,7 +2542,8 @@ mod tests {
             },
             failed: false,
             error_message: None,
-            dependency_kind: DependencyKind::Unknown,
+            dependency_kind: DependencyKind::Unknown.into(),
+            ..Default::default()
         }];
 
         let mut buffer = Vec::new();
@@ -1299,7 +2573,8 @@ mod tests {
             },
             failed: false,
             error_message: None,
-            dependency_kind: DependencyKind::Development,
+            dependency_kind: DependencyKind::Development.into(),
+            ..Default::default()
         }];
 
         let temp_dir = assert_fs::TempDir::new()?;
@@ -1334,7 +2609,8 @@ mod tests {
             },
             failed: false,
             error_message: None,
-            dependency_kind: DependencyKind::Normal,
+            dependency_kind: DependencyKind::Normal.into(),
+            ..Default::default()
         }];
 
         let config = Config::default();
@@ -1363,7 +2639,8 @@ mod tests {
             },
             failed: true,
             error_message: Some("Failed to fetch repository info".to_string()),
-            dependency_kind: DependencyKind::Normal,
+            dependency_kind: DependencyKind::Normal.into(),
+            ..Default::default()
         }];
 
         let config = Config::default();
@@ -1392,14 +2669,17 @@ mod tests {
             },
             failed: false,
             error_message: None,
-            dependency_kind: DependencyKind::Normal,
+            dependency_kind: DependencyKind::Normal.into(),
+            ..Default::default()
         }];
 
         let temp_dir = assert_fs::TempDir::new()?;
         let file_path = temp_dir.path().join("test-output.md");
 
-        let mut config = Config::default();
-        config.output = Some(file_path.clone());
+        let config = Config {
+            output: Some(file_path.clone()),
+            ..Default::default()
+        };
 
         let mut output = config.get_output_writer()?;
         let mut manager = OutputManager::new(OutputFormat::MarkdownTable, &mut output);
@@ -1413,6 +2693,687 @@ mod tests {
         Ok(())
     }
 
+    fn transitive_deps() -> Vec<DependencyInfo> {
+        vec![
+            DependencyInfo {
+                name: "reqwest".to_string(),
+                description: Some("higher level HTTP client library".to_string()),
+                crate_url: Some("https://crates.io/crates/reqwest".to_string()),
+                source_type: "GitHub".to_string(),
+                source_url: Some("https://github.com/seanmonstar/reqwest".to_string()),
+                dependency_kind: DependencyKind::Normal.into(),
+                depth: 1,
+                parents: vec!["cargo-thanku".to_string()],
+                ..Default::default()
+            },
+            DependencyInfo {
+                name: "hyper".to_string(),
+                description: Some("A protective and efficient HTTP library".to_string()),
+                crate_url: Some("https://crates.io/crates/hyper".to_string()),
+                source_type: "GitHub".to_string(),
+                source_url: Some("https://github.com/hyperium/hyper".to_string()),
+                dependency_kind: DependencyKind::Normal.into(),
+                depth: 2,
+                parents: vec!["cargo-thanku".to_string(), "reqwest".to_string()],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_markdown_table_with_depth() -> Result<()> {
+        let deps = transitive_deps();
+        let formatter = MarkdownTableFormatter::default();
+        let result = formatter.format(&deps)?;
+        assert!(result.contains(" 2 (cargo-thanku → reqwest) |"));
+
+        let parsed = formatter.parse(&result)?;
+        let hyper = parsed.iter().find(|dep| dep.name == "hyper").unwrap();
+        assert_eq!(hyper.depth, 2);
+        assert_eq!(hyper.parents, vec!["cargo-thanku", "reqwest"]);
+
+        // 只有直接依赖时不显示深度列
+        let result = formatter.format(&deps[..1])?;
+        assert!(!result.contains("(cargo-thanku)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_list_with_depth() -> Result<()> {
+        let deps = transitive_deps();
+        let formatter = MarkdownListFormatter::default();
+        let result = formatter.format(&deps)?;
+
+        let parsed = formatter.parse(&result)?;
+        assert_eq!(parsed.len(), 2);
+        let hyper = parsed.iter().find(|dep| dep.name == "hyper").unwrap();
+        assert_eq!(hyper.depth, 2);
+        assert_eq!(hyper.parents, vec!["cargo-thanku", "reqwest"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_manager_filter_depth() -> Result<()> {
+        let deps = transitive_deps();
+        let mut buffer = Vec::new();
+        let options = FormatOptions {
+            depth: Some(DepthLimit::Level(1)),
+            ..Default::default()
+        };
+        let mut manager = OutputManager::new_with_options(OutputFormat::Json, &mut buffer, options);
+        manager.write(&deps)?;
+
+        let parsed = JsonFormatter::default().parse(&String::from_utf8(buffer)?)?;
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, "reqwest");
+        Ok(())
+    }
+
+    /// 以所有格式输出并解析 `deps`，对每个依赖与解析得到的同名依赖调用 `check`
+    ///
+    /// 返回每种格式的输出，用于检查只出现在部分格式中的内容
+    fn assert_round_trip(
+        deps: &[DependencyInfo],
+        check: impl Fn(&DependencyInfo, &DependencyInfo, OutputFormat),
+    ) -> Result<Vec<(OutputFormat, String)>> {
+        let mut results = vec![];
+        for format in [
+            OutputFormat::MarkdownTable,
+            OutputFormat::MarkdownList,
+            OutputFormat::Csv,
+            OutputFormat::Json,
+            OutputFormat::Yaml,
+            OutputFormat::Toml,
+        ] {
+            let formatter = <dyn Formatter>::new(format)?;
+            let result = formatter.format(deps)?;
+            let parsed = formatter.parse(&result)?;
+            assert_eq!(parsed.len(), deps.len(), "{}", format);
+
+            for dep in deps {
+                let parsed = parsed.iter().find(|p| p.name == dep.name).unwrap();
+                check(parsed, dep, format);
+            }
+            results.push((format, result));
+        }
+        Ok(results)
+    }
+
+    /// 两个工作区成员共同使用的依赖
+    fn workspace_deps() -> Vec<DependencyInfo> {
+        let dep = |name: &str, used_by: &[&str]| DependencyInfo {
+            name: name.to_string(),
+            description: Some(format!("{} description", name)),
+            crate_url: Some(format!("https://crates.io/crates/{}", name)),
+            source_type: "crates.io".to_string(),
+            depth: 1,
+            used_by: used_by.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        };
+        vec![
+            dep("serde", &["app", "tool"]),
+            dep("clap", &["tool"]),
+            dep("tokio", &["app"]),
+        ]
+    }
+
+    #[test]
+    fn test_formatters_used_by() -> Result<()> {
+        let deps = workspace_deps();
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.used_by, dep.used_by, "{}", format);
+        })?;
+
+        // 只有一个工作区成员时不显示使用者列
+        let single = vec![DependencyInfo {
+            used_by: vec!["app".to_string()],
+            ..workspace_deps()[0].clone()
+        }];
+        let result = MarkdownTableFormatter::default().format(&single)?;
+        assert!(!result.contains(&t!("output.used_by").to_string()));
+        Ok(())
+    }
+
+    /// 同时作为普通依赖与开发依赖、且限定目标平台的依赖
+    fn multi_role_deps() -> Vec<DependencyInfo> {
+        vec![
+            DependencyInfo {
+                name: "tokio".to_string(),
+                description: Some("An event-driven runtime".to_string()),
+                crate_url: Some("https://crates.io/crates/tokio".to_string()),
+                source_type: "crates.io".to_string(),
+                dependency_kind: [DependencyKind::Development, DependencyKind::Normal]
+                    .into_iter()
+                    .collect(),
+                targets: vec![
+                    "cfg(all(unix, not(target_os = \"macos\")))".to_string(),
+                    "cfg(windows)".to_string(),
+                ],
+                ..Default::default()
+            },
+            DependencyInfo {
+                name: "assert_fs".to_string(),
+                description: Some("Filesystem fixtures".to_string()),
+                crate_url: Some("https://crates.io/crates/assert_fs".to_string()),
+                source_type: "crates.io".to_string(),
+                dependency_kind: DependencyKind::Development.into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_dependency_kinds_serde() -> Result<()> {
+        // 只有一种类型时与旧版本的输出相同
+        let kinds = DependencyKinds::from(DependencyKind::Build);
+        assert_eq!(serde_json::to_string(&kinds)?, "\"Build\"");
+        assert_eq!(serde_json::from_str::<DependencyKinds>("\"Build\"")?, kinds);
+
+        let kinds = "Development; Normal".parse::<DependencyKinds>()?;
+        assert_eq!(kinds.primary(), DependencyKind::Normal);
+        assert_eq!(
+            serde_json::to_string(&kinds)?,
+            r#"["Normal","Development"]"#
+        );
+        assert_eq!(kinds.to_string(), "Normal, Development");
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_roles_and_targets() -> Result<()> {
+        let deps = multi_role_deps();
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.dependency_kind, dep.dependency_kind, "{}", format);
+            assert_eq!(parsed.targets, dep.targets, "{}", format);
+        })?;
+
+        // Markdown 中每个依赖只出现一次，位于主要类型的分组中
+        let result = MarkdownTableFormatter::default().format(&deps)?;
+        assert_eq!(result.matches("| tokio |").count(), 1);
+        let normal = result.find(DependencyKind::Normal.to_md_table_header().as_ref());
+        let tokio = result.find("| tokio |");
+        assert!(normal < tokio);
+        Ok(())
+    }
+
+    fn licensed_deps() -> Vec<DependencyInfo> {
+        let dep = |name: &str, license: Option<&str>| DependencyInfo {
+            name: name.to_string(),
+            description: Some(format!("The {} crate", name)),
+            crate_url: Some(format!("https://crates.io/crates/{}", name)),
+            source_type: "crates.io".to_string(),
+            license: license.map(str::to_string),
+            ..Default::default()
+        };
+        vec![
+            dep("serde", Some("MIT OR Apache-2.0")),
+            dep("tokio", Some("MIT")),
+            dep("anyhow", Some("MIT OR Apache-2.0")),
+            dep("ring", None),
+        ]
+    }
+
+    #[test]
+    fn test_license_summary() {
+        assert_eq!(
+            license_summary(&licensed_deps()),
+            vec![
+                ("MIT OR Apache-2.0".to_string(), 2),
+                ("MIT".to_string(), 1),
+                (t!("output.license_unknown").to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_formatters_license() -> Result<()> {
+        let deps = licensed_deps();
+        let results = assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.license, dep.license, "{}", format);
+        })?;
+        for (format, result) in results {
+            // 许可证汇总只出现在 Markdown 输出的末尾
+            let summary = format!("### {}", t!("output.license_summary"));
+            assert_eq!(
+                result.contains(&summary),
+                format.is_markdown(),
+                "{}",
+                format
+            );
+        }
+
+        // 没有任何许可证信息时不显示许可证列与汇总
+        let deps = multi_role_deps();
+        let result = MarkdownTableFormatter::default().format(&deps)?;
+        assert!(!result.contains(t!("output.license").as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_version() -> Result<()> {
+        let mut deps = licensed_deps();
+        deps[0].versions = vec!["1.0.219".to_string()];
+        deps[0].version_req = Some(">=1.0, <2".to_string());
+        deps[1].versions = vec!["0.11.27".to_string(), "0.12.4".to_string()];
+        deps[2].version_req = Some("^0.3".to_string());
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.versions, dep.versions, "{}", format);
+            assert_eq!(parsed.version_req, dep.version_req, "{}", format);
+        })?;
+
+        assert_eq!(deps[0].to_version_string(), "1.0.219 (>=1.0, <2)");
+        assert_eq!(deps[1].to_version_string(), "0.11.27, 0.12.4");
+        assert_eq!(deps[1].latest_version(), Some("0.12.4"));
+        assert_eq!(deps[2].to_version_string(), "(^0.3)");
+        assert_eq!(deps[3].to_version_string(), "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_outdated() -> Result<()> {
+        let mut deps = licensed_deps();
+        deps[0].versions = vec!["1.0.218".to_string()];
+        deps[0].outdated = Some(OutdatedInfo {
+            latest_version: Some("1.0.219".to_string()),
+            lag: VersionLag::Patch,
+            yanked: true,
+            released_at: Some("2025-02-20".to_string()),
+            latest_released_at: Some("2025-03-09".to_string()),
+        });
+        deps[1].outdated = Some(OutdatedInfo {
+            latest_version: Some("0.12.15".to_string()),
+            lag: VersionLag::Unknown,
+            ..Default::default()
+        });
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.outdated, dep.outdated, "{}", format);
+        })?;
+
+        // 没有比较结果时不显示相关的列
+        let result = MarkdownTableFormatter::default().format(&licensed_deps())?;
+        assert!(!result.contains(t!("output.lag").as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_sponsor() -> Result<()> {
+        let mut deps = licensed_deps();
+        deps[0].funding = vec![
+            FundingLink {
+                platform: "GITHUB".to_string(),
+                url: "https://github.com/sponsors/dtolnay".to_string(),
+            },
+            FundingLink::custom("https://example.com/donate"),
+        ];
+
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.funding, dep.funding, "{}", format);
+        })?;
+
+        // 只输出可以赞助的依赖
+        let mut buffer = Vec::new();
+        let options = FormatOptions {
+            sponsorable_only: true,
+            ..Default::default()
+        };
+        OutputManager::new_with_options(OutputFormat::Json, &mut buffer, options).write(&deps)?;
+        let parsed = JsonFormatter::default().parse(&String::from_utf8(buffer)?)?;
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, deps[0].name);
+
+        // 没有赞助链接时不显示赞助列
+        let result = MarkdownTableFormatter::default().format(&licensed_deps())?;
+        assert!(!result.contains(t!("output.sponsor").as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_maintainers() -> Result<()> {
+        let person = |login: &str| Maintainer {
+            login: login.to_string(),
+            name: None,
+            url: Some(format!("https://github.com/{}", login)),
+        };
+        let mut deps = licensed_deps();
+        deps[0].maintainers = vec![person("dtolnay"), person("oli-obk")];
+        deps[2].maintainers = vec![person("dtolnay")];
+        deps[3].maintainers = vec![Maintainer {
+            login: "briansmith".to_string(),
+            name: None,
+            url: None,
+        }];
+
+        let results = assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.maintainers, dep.maintainers, "{}", format);
+        })?;
+        for (format, result) in results {
+            // 致谢名单只出现在 Markdown 输出的末尾
+            let section = format!("### {}", t!("output.people_to_thank"));
+            assert_eq!(
+                result.contains(&section),
+                format.is_markdown(),
+                "{}",
+                format
+            );
+        }
+
+        // 按维护的 crate 数量排序
+        let people = people_to_thank(&deps)
+            .into_iter()
+            .map(|(maintainer, crates)| (maintainer.login, crates))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            people,
+            vec![
+                (
+                    "dtolnay".to_string(),
+                    vec!["anyhow".to_string(), "serde".to_string()]
+                ),
+                ("briansmith".to_string(), vec!["ring".to_string()]),
+                ("oli-obk".to_string(), vec!["serde".to_string()]),
+            ]
+        );
+        let result = MarkdownListFormatter::default().format(&deps)?;
+        assert!(result.contains("- [dtolnay](https://github.com/dtolnay): anyhow, serde\n"));
+
+        // 没有维护者信息时不显示维护者列与致谢名单
+        let result = MarkdownTableFormatter::default().format(&licensed_deps())?;
+        assert!(!result.contains(t!("output.maintainers").as_ref()));
+        assert!(!result.contains(t!("output.people_to_thank").as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatters_authors() -> Result<()> {
+        let mut deps = licensed_deps();
+        deps[0].authors = vec!["Erick Tryzelaar".to_string(), "David Tolnay".to_string()];
+        deps[3].authors = vec!["Brian Smith".to_string()];
+
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.authors, dep.authors, "{}", format);
+        })?;
+
+        // 没有作者信息时不显示作者列
+        let result = MarkdownTableFormatter::default().format(&licensed_deps())?;
+        assert!(!result.contains(t!("output.authors").as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_update() -> Result<()> {
+        let current = licensed_deps();
+        for format in [
+            OutputFormat::MarkdownTable,
+            OutputFormat::MarkdownList,
+            OutputFormat::Csv,
+            OutputFormat::Json,
+            OutputFormat::Yaml,
+            OutputFormat::Toml,
+        ] {
+            let formatter = <dyn Formatter>::new(format)?;
+
+            // 已有输出：手动修改了 serde 的描述，ring 尚未加入，legacy 已不再是依赖
+            let mut previous = licensed_deps();
+            previous[0].description = Some("Thanks for serializing everything".to_string());
+            previous[3].name = "legacy".to_string();
+            let previous = formatter.parse(&formatter.format(&previous)?)?;
+
+            let merged = merge_update(previous, &current);
+            let content = formatter.format(&merged)?;
+            let parsed = formatter.parse(&content)?;
+            assert_eq!(parsed.len(), 5, "{}", format);
+
+            let find = |name: &str| parsed.iter().find(|dep| dep.name == name).unwrap();
+            assert_eq!(
+                find("serde").description.as_deref(),
+                Some("Thanks for serializing everything"),
+                "{}",
+                format
+            );
+            assert_eq!(
+                find("tokio").description.as_deref(),
+                Some("The tokio crate")
+            );
+            assert_eq!(find("ring").status, DependencyStatus::Ok, "{}", format);
+            assert_eq!(
+                find("legacy").status,
+                DependencyStatus::Removed,
+                "{}",
+                format
+            );
+
+            // 再次合并时，上一次已标记为移除的依赖被删除
+            let merged = merge_update(parsed.clone(), &current);
+            assert_eq!(merged.len(), 4, "{}", format);
+            assert!(merged.iter().all(|dep| dep.name != "legacy"));
+            let merged = merge_update(formatter.parse(&formatter.format(&merged)?)?, &current);
+            assert_eq!(merged.len(), 4, "{}", format);
+
+            // 标记为移除之后重新加入的依赖恢复正常状态
+            let mut current = current.clone();
+            current.push(DependencyInfo {
+                name: "legacy".to_string(),
+                ..current[3].clone()
+            });
+            let merged = merge_update(parsed, &current);
+            assert_eq!(merged.len(), 5);
+            assert!(merged.iter().all(|dep| dep.status == DependencyStatus::Ok));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dependency_status() -> Result<()> {
+        assert!(DependencyStatus::is_deprecated_description(
+            "Deprecated: use tempfile instead"
+        ));
+        assert!(DependencyStatus::is_deprecated_description(
+            "[DEPRECATED] Error management"
+        ));
+        assert!(!DependencyStatus::is_deprecated_description(
+            "Helpers for emitting deprecation warnings"
+        ));
+        assert!(DependencyStatus::is_deprecated_readme(
+            "<h1>foo</h1>\n<p>This crate is\n  <b>no longer maintained</b>.</p>"
+        ));
+        // 只检查 README 的开头
+        let readme = format!("<h1>foo</h1>{}unmaintained", " ".repeat(3000));
+        assert!(!DependencyStatus::is_deprecated_readme(&readme));
+
+        let mut deps = licensed_deps();
+        deps[0].status = DependencyStatus::Yanked;
+        deps[1].status = DependencyStatus::Deprecated;
+        assert_round_trip(&deps, |parsed, dep, format| {
+            assert_eq!(parsed.status, dep.status, "{}", format);
+        })?;
+
+        assert_eq!(
+            "yanked".parse::<DependencyStatus>()?,
+            DependencyStatus::Yanked
+        );
+        assert!("broken".parse::<DependencyStatus>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(split_csv_line("a,b,,c"), vec!["a", "b", "", "c"]);
+        assert_eq!(
+            split_csv_line(r#"a,"cfg(all(unix, windows))","say ""hi""""#),
+            vec!["a", "cfg(all(unix, windows))", r#"say "hi""#]
+        );
+        assert_eq!(
+            quote_csv_field(r#"cfg(target_os = "linux")"#),
+            r#""cfg(target_os = ""linux"")""#
+        );
+    }
+
+    #[test]
+    fn test_split_by_member() {
+        let members = split_by_member(&workspace_deps());
+        let names = members
+            .iter()
+            .map(|(member, deps)| {
+                let deps = deps.iter().map(|dep| dep.name.as_str()).collect::<Vec<_>>();
+                (member.as_str(), deps)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("app", vec!["serde", "tokio"]),
+                ("tool", vec!["serde", "clap"])
+            ]
+        );
+        assert!(members[0].1.iter().all(|dep| dep.used_by.is_empty()));
+    }
+
+    #[test]
+    fn test_output_manager_per_package() -> Result<()> {
+        let mut buffer = Vec::new();
+        let mut manager = OutputManager::new(OutputFormat::MarkdownTable, &mut buffer);
+        manager.write_per_package(&workspace_deps())?;
+
+        let result = String::from_utf8(buffer)?;
+        assert!(result.contains(&format!("# {}", t!("output.package_section", name = "app"))));
+        assert!(result.contains(&format!(
+            "# {}",
+            t!("output.package_section", name = "tool")
+        )));
+
+        // 解析时读取合并视图
+        let parsed = MarkdownTableFormatter::default().parse(&result)?;
+        assert_eq!(parsed.len(), 3);
+        Ok(())
+    }
+
+    fn link_source_dep() -> DependencyInfo {
+        DependencyInfo {
+            name: "serde".to_string(),
+            description: Some("A serialization framework".to_string()),
+            crate_url: Some("https://crates.io/crates/serde".to_string()),
+            source_type: "GitHub".to_string(),
+            source_url: Some("https://github.com/serde-rs/serde".to_string()),
+            homepage: Some("https://serde.rs".to_string()),
+            documentation: Some("https://docs.rs/serde".to_string()),
+            dependency_kind: DependencyKind::Normal.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_strings_link_source_github() {
+        let (_, _, crates_link, source_link, _, _) =
+            link_source_dep().to_strings(&LinkSource::GitHub);
+        assert_eq!(crates_link, "[serde](https://crates.io/crates/serde)");
+        assert_eq!(source_link, "[GitHub](https://github.com/serde-rs/serde)");
+    }
+
+    #[test]
+    fn test_to_strings_link_source_crates_io() {
+        let (_, _, crates_link, source_link, _, _) =
+            link_source_dep().to_strings(&LinkSource::CratesIo);
+        assert_eq!(crates_link, "[serde](https://crates.io/crates/serde)");
+        assert_eq!(source_link, "[crates.io](https://crates.io/crates/serde)");
+
+        // 没有 crates.io 链接时根据名称生成
+        let dep = DependencyInfo {
+            crate_url: None,
+            ..link_source_dep()
+        };
+        let (_, _, crates_link, _, _, _) = dep.to_strings(&LinkSource::CratesIo);
+        assert_eq!(crates_link, "[serde](https://crates.io/crates/serde)");
+
+        // git 依赖不在 crates.io 上，不生成 crates.io 链接
+        let dep = DependencyInfo {
+            crate_url: None,
+            source_type: DependencyInfo::GIT_SOURCE.to_string(),
+            ..link_source_dep()
+        };
+        let (_, _, crates_link, source_link, _, _) = dep.to_strings(&LinkSource::CratesIo);
+        assert_eq!(crates_link, "serde");
+        assert_eq!(source_link, "[Git](https://github.com/serde-rs/serde)");
+    }
+
+    #[test]
+    fn test_to_strings_link_source_link_empty() {
+        let (_, _, crates_link, source_link, _, _) =
+            link_source_dep().to_strings(&LinkSource::LinkEmpty);
+        assert_eq!(crates_link, "serde");
+        assert_eq!(source_link, "GitHub");
+    }
+
+    #[test]
+    fn test_to_strings_link_source_other() {
+        let (_, _, _, source_link, _, _) = link_source_dep().to_strings(&LinkSource::Other);
+        assert_eq!(source_link, "[Homepage](https://serde.rs)");
+
+        let dep = DependencyInfo {
+            homepage: None,
+            ..link_source_dep()
+        };
+        let (_, _, _, source_link, _, _) = dep.to_strings(&LinkSource::Other);
+        assert_eq!(source_link, "[Documentation](https://docs.rs/serde)");
+
+        // 既没有主页也没有文档时保持原来的来源链接
+        let dep = DependencyInfo {
+            homepage: None,
+            documentation: None,
+            ..link_source_dep()
+        };
+        let (_, _, _, source_link, _, _) = dep.to_strings(&LinkSource::Other);
+        assert_eq!(source_link, "[GitHub](https://github.com/serde-rs/serde)");
+    }
+
+    #[test]
+    fn test_formatters_link_source() -> Result<()> {
+        let deps = vec![link_source_dep()];
+        let formats = [
+            OutputFormat::MarkdownTable,
+            OutputFormat::MarkdownList,
+            OutputFormat::Csv,
+            OutputFormat::Json,
+            OutputFormat::Yaml,
+            OutputFormat::Toml,
+        ];
+        let link_sources = [
+            LinkSource::GitHub,
+            LinkSource::CratesIo,
+            LinkSource::LinkEmpty,
+            LinkSource::Other,
+        ];
+
+        for format in formats {
+            for link_source in link_sources {
+                let options = FormatOptions {
+                    link_source,
+                    ..Default::default()
+                };
+                let formatter = <dyn Formatter>::with_options(format, &options);
+                let parsed = formatter.parse(&formatter.format(&deps)?)?;
+                assert_eq!(parsed.len(), 1, "{} / {:?}", format, link_source);
+
+                let (crate_url, source_type, source_url) = deps[0].resolve_links(&link_source);
+                assert_eq!(
+                    parsed[0].crate_url, crate_url,
+                    "{} / {:?}",
+                    format, link_source
+                );
+                assert_eq!(
+                    parsed[0].source_type, source_type,
+                    "{} / {:?}",
+                    format, link_source
+                );
+                assert_eq!(
+                    parsed[0].source_url, source_url,
+                    "{} / {:?}",
+                    format, link_source
+                );
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_md_table_func() -> Result<()> {
         let header = MarkdownTableFormatter::get_header();
@@ -1437,11 +3398,12 @@ mod tests {
 
     #[test]
     fn test_parse_status() -> Result<()> {
-        let (failed, error_message) = DependencyInfo::parse_status("✅")?;
+        let (failed, error_message, status) = DependencyInfo::parse_status("✅")?;
         assert!(!failed);
         assert_eq!(error_message, None);
+        assert_eq!(status, DependencyStatus::Ok);
 
-        let (failed, error_message) =
+        let (failed, error_message, _) =
             DependencyInfo::parse_status("❌ Unknown error: failed to fetch repository info")?;
         assert!(failed);
         assert_eq!(
@@ -1449,10 +3411,19 @@ mod tests {
             Some("Unknown error: failed to fetch repository info".to_string())
         );
 
-        let (failed, error_message) = DependencyInfo::parse_status("❌ ")?;
+        let (failed, error_message, _) = DependencyInfo::parse_status("❌ ")?;
         assert!(failed);
         assert_eq!(error_message, None);
 
+        let yanked = format!("⚠️ {}", DependencyStatus::Yanked);
+        let (failed, _, status) = DependencyInfo::parse_status(&yanked)?;
+        assert!(!failed);
+        assert_eq!(status, DependencyStatus::Yanked);
+
+        let archived = format!("⚠️ {}", DependencyStatus::Archived);
+        let (_, _, status) = DependencyInfo::parse_status(&archived)?;
+        assert_eq!(status, DependencyStatus::Archived);
+
         let parse_error = DependencyInfo::parse_status("🍃 test content");
         assert!(parse_error.is_err());
 
@@ -1601,7 +3572,7 @@ mod tests {
     fn test_try_from_md_table_line() -> Result<()> {
         const LINE: &str = "| anyhow | Flexible concrete Error type built on std::error::Error | [anyhow](https://crates.io/crates/anyhow) | [GitHub](https://github.com/dtolnay/anyhow) | ❓ | ✅ |";
 
-        let dep = DependencyInfo::try_from_md_table_line(LINE, &DependencyKind::Normal)?;
+        let dep = DependencyInfo::try_from_md_table_line(LINE, &DependencyKind::Normal, &[])?;
         assert_eq!(dep.name, "anyhow");
         assert_eq!(
             dep.description,
@@ -1679,7 +3650,7 @@ mod tests {
     fn test_parse_md_table_en() -> Result<()> {
         rust_i18n::set_locale("en");
         let content = std::fs::read_to_string("./assets/output/THANKU_table_en.md")?;
-        let deps = MarkdownTableFormatter.parse(&content)?;
+        let deps = MarkdownTableFormatter::default().parse(&content)?;
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::MarkdownTable, &mut output); // Pass &mut output
         manager.write(&deps)?;
@@ -1697,7 +3668,7 @@ mod tests {
     fn test_parse_md_list_en() -> Result<()> {
         rust_i18n::set_locale("en");
         let content = std::fs::read_to_string("./assets/output/THANKU_list_en.md")?;
-        let deps = MarkdownListFormatter.parse(&content)?;
+        let deps = MarkdownListFormatter::default().parse(&content)?;
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::MarkdownList, &mut output); // Pass &mut output
         manager.write(&deps)?;
@@ -1716,7 +3687,7 @@ mod tests {
     fn test_parse_csv_en() -> Result<()> {
         rust_i18n::set_locale("en");
         let content = std::fs::read_to_string("./assets/output/THANKU_en.csv")?;
-        let deps = CsvFormatter.parse(&content)?;
+        let deps = CsvFormatter::default().parse(&content)?;
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::Csv, &mut output); // Pass &mut output
         manager.write(&deps)?;
@@ -1737,7 +3708,7 @@ mod tests {
     fn test_parse_csv_failed_zh() {
         rust_i18n::set_locale("zh");
         let content = std::fs::read_to_string("./assets/output/THANKU_en.csv").unwrap();
-        let deps = CsvFormatter.parse(&content).unwrap();
+        let deps = CsvFormatter::default().parse(&content).unwrap();
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::Csv, &mut output); // Pass &mut output
         manager.write(&deps).unwrap();
@@ -1754,7 +3725,7 @@ mod tests {
     fn test_parse_json_en() -> Result<()> {
         rust_i18n::set_locale("en");
         let content = std::fs::read_to_string("./assets/output/THANKU_json_en.json")?;
-        let deps = JsonFormatter.parse(&content)?;
+        let deps = JsonFormatter::default().parse(&content)?;
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::Json, &mut output); // Pass &mut output
         manager.write(&deps)?;
@@ -1772,7 +3743,7 @@ mod tests {
     fn test_parse_yaml_en() -> Result<()> {
         rust_i18n::set_locale("en");
         let content = std::fs::read_to_string("./assets/output/THANKU_yaml_en.yaml")?;
-        let deps = YamlFormatter.parse(&content)?;
+        let deps = YamlFormatter::default().parse(&content)?;
         let mut output = Vec::new(); // Change String to Vec<u8>
         let mut manager = OutputManager::new(OutputFormat::Yaml, &mut output); // Pass &mut output
         manager.write(&deps)?;
@@ -0,0 +1,231 @@
+use rust_i18n::t;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// crates.io 的索引地址，分别为 git 协议与稀疏协议
+const CRATES_IO_INDEXES: [&str; 2] = [
+    "https://github.com/rust-lang/crates.io-index",
+    "https://index.crates.io",
+];
+
+/// Cargo 配置中 `[registries]` 下的一个注册表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registry {
+    pub name: String,
+    /// 索引地址，稀疏索引以 `sparse+` 开头
+    pub index: String,
+    /// 来自环境变量、`credentials.toml` 或配置文件的令牌
+    pub token: Option<String>,
+}
+
+impl Registry {
+    /// 稀疏索引可以直接通过 HTTP 读取，git 索引只能使用注册表的 API
+    pub fn is_sparse(&self) -> bool {
+        self.index.starts_with("sparse+")
+    }
+
+    /// 去掉协议前缀、以 `/` 结尾的索引地址
+    pub fn index_url(&self) -> String {
+        format!("{}/", normalize_index(&self.index))
+    }
+}
+
+/// 去掉 `registry+`/`sparse+` 前缀与末尾的 `/`，用于比较索引地址
+fn normalize_index(index: &str) -> &str {
+    index
+        .trim_start_matches("registry+")
+        .trim_start_matches("sparse+")
+        .trim_end_matches('/')
+}
+
+/// 索引地址是否属于 crates.io
+pub fn is_crates_io(index: &str) -> bool {
+    CRATES_IO_INDEXES.contains(&normalize_index(index))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CargoConfig {
+    #[serde(default)]
+    registries: BTreeMap<String, RegistryConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RegistryConfig {
+    index: Option<String>,
+    token: Option<String>,
+}
+
+/// 从 Cargo 配置中读取的所有注册表
+#[derive(Debug, Clone, Default)]
+pub struct Registries(Vec<Registry>);
+
+impl Registries {
+    /// 按照 Cargo 的规则读取注册表配置
+    ///
+    /// - 从 `dir`（通常为工作区目录）开始逐级向上读取 `.cargo/config.toml`（以及旧的 `.cargo/config`），
+    ///   最后读取 `$CARGO_HOME/config.toml`，离 `dir` 越近的配置优先级越高
+    /// - 令牌依次来自 `CARGO_REGISTRIES_<NAME>_TOKEN`、`$CARGO_HOME/credentials.toml` 与配置文件，
+    ///   不支持凭据提供程序（`credential-provider`）
+    pub fn load(dir: &Path) -> Self {
+        Self::load_from(dir, cargo_home().as_deref(), |name| {
+            std::env::var(name).ok()
+        })
+    }
+
+    fn load_from(
+        dir: &Path,
+        cargo_home: Option<&Path>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+        let config_dirs = dir
+            .ancestors()
+            .map(|dir| dir.join(".cargo"))
+            .chain(cargo_home.map(Path::to_path_buf));
+
+        let mut registries: BTreeMap<String, RegistryConfig> = BTreeMap::new();
+        for dir in config_dirs {
+            for file in ["config.toml", "config"] {
+                for (name, config) in read_config(&dir.join(file)).registries {
+                    let entry = registries.entry(name).or_default();
+                    entry.index = entry.index.take().or(config.index);
+                    entry.token = entry.token.take().or(config.token);
+                }
+            }
+        }
+
+        let credentials = cargo_home
+            .map(|home| {
+                let mut credentials = read_config(&home.join("credentials.toml")).registries;
+                for (name, config) in read_config(&home.join("credentials")).registries {
+                    credentials.entry(name).or_insert(config);
+                }
+                credentials
+            })
+            .unwrap_or_default();
+
+        let registries = registries
+            .into_iter()
+            .filter_map(|(name, config)| {
+                let env_name = name.to_uppercase().replace('-', "_");
+                let index = env(&format!("CARGO_REGISTRIES_{}_INDEX", env_name)).or(config.index)?;
+                let token = env(&format!("CARGO_REGISTRIES_{}_TOKEN", env_name))
+                    .or_else(|| credentials.get(&name).and_then(|c| c.token.clone()))
+                    .or(config.token);
+                Some(Registry { name, index, token })
+            })
+            .collect();
+        Self(registries)
+    }
+
+    /// 按索引地址查找注册表，忽略协议前缀与末尾的 `/`
+    pub fn find_by_index(&self, index: &str) -> Option<&Registry> {
+        self.0
+            .iter()
+            .find(|registry| normalize_index(&registry.index) == normalize_index(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Registry> {
+        self.0.iter()
+    }
+}
+
+/// 读取一个 Cargo 配置文件，不存在或无法解析时视为空配置
+fn read_config(path: &Path) -> CargoConfig {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return CargoConfig::default();
+    };
+    toml::from_str(&content).unwrap_or_else(|e| {
+        debug!(
+            "{}",
+            t!(
+                "registries.invalid_config",
+                path = path.display(),
+                error = e.to_string()
+            )
+        );
+        CargoConfig::default()
+    })
+}
+
+/// `$CARGO_HOME`，未设置时为用户目录下的 `.cargo`
+fn cargo_home() -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+
+    env_dir("CARGO_HOME").or_else(|| {
+        let home = if cfg!(windows) {
+            env_dir("USERPROFILE")
+        } else {
+            env_dir("HOME")
+        };
+        home.map(|home| home.join(".cargo"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_load_registries() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        temp.child("workspace/.cargo/config.toml").write_str(
+            "[registries.internal]\nindex = \"sparse+https://registry.example.com/index/\"\n",
+        )?;
+        // 上级目录中的配置优先级更低
+        temp.child(".cargo/config.toml").write_str(
+            "[registries.internal]\nindex = \"sparse+https://ignored.example.com/\"\n\n\
+             [registries.legacy]\nindex = \"https://git.example.com/index.git\"\ntoken = \"config-token\"\n",
+        )?;
+        temp.child("home/credentials.toml")
+            .write_str("[registries.internal]\ntoken = \"credentials-token\"\n")?;
+        temp.child("workspace/member").create_dir_all()?;
+
+        let env =
+            |name: &str| (name == "CARGO_REGISTRIES_LEGACY_TOKEN").then(|| "env-token".to_string());
+        let registries = Registries::load_from(
+            temp.child("workspace/member").path(),
+            Some(temp.child("home").path()),
+            env,
+        );
+
+        let internal = registries
+            .find_by_index("sparse+https://registry.example.com/index/")
+            .unwrap();
+        assert_eq!(internal.name, "internal");
+        assert!(internal.is_sparse());
+        assert_eq!(internal.index_url(), "https://registry.example.com/index/");
+        assert_eq!(internal.token.as_deref(), Some("credentials-token"));
+
+        // cargo metadata 中的 git 索引带有 `registry+` 前缀
+        let legacy = registries
+            .find_by_index("registry+https://git.example.com/index.git")
+            .unwrap();
+        assert!(!legacy.is_sparse());
+        assert_eq!(legacy.token.as_deref(), Some("env-token"));
+
+        assert!(
+            registries
+                .find_by_index("https://other.example.com")
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_crates_io() {
+        assert!(is_crates_io(
+            "registry+https://github.com/rust-lang/crates.io-index"
+        ));
+        assert!(is_crates_io("sparse+https://index.crates.io/"));
+        assert!(!is_crates_io("sparse+https://registry.example.com/index/"));
+    }
+}
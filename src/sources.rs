@@ -1,10 +1,25 @@
 use anyhow::Result;
-use reqwest::Client;
-use serde::Deserialize;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
 use std::time::Duration;
-use tracing::instrument;
+use tracing::{debug, instrument};
 use url::Url;
 
+pub mod cache;
+pub mod graphql;
+pub mod hosts;
+pub mod rate_limit;
+pub mod registry;
+
+use crate::{errors::AppError, funding::FundingLink, maintainers::Maintainer};
+use cache::{Cache, CacheKey};
+use hosts::{BitbucketClient, GitLabClient, GiteaClient, HostKind, RepositoryHost, RepositoryRef};
+use rate_limit::RateLimiter;
+use registry::RegistryClient;
+
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub enum Source {
@@ -30,8 +45,9 @@ impl Source {
     pub fn from_url(url: &Option<Url>) -> Option<Self> {
         url.as_ref().and_then(|u| match u.host_str()? {
             "github.com" => {
+                // 只取前两段路径，例如 `/owner/repo/tree/master/crate` 中的 `owner/repo`
                 let path = u.path().trim_matches('/');
-                let mut parts = path.splitn(2, '/');
+                let mut parts = path.split('/');
                 Some(Self::GitHub {
                     owner: parts.next()?.to_string(),
                     repo: parts.next()?.trim_end_matches(".git").to_string(),
@@ -51,97 +67,843 @@ impl Source {
 }
 
 #[allow(unused)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrateInfo {
     pub name: String,
     pub description: Option<String>,
     // pub repository: Option<Url>,
     pub repository: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub documentation: Option<String>,
     pub downloads: u32,
+    /// 指定版本时为该版本号，此时其它信息也来自该版本
+    #[serde(default)]
+    pub version: Option<String>,
+    /// 指定的版本是否已被撤回
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+/// `/crates/{name}/{version}` 返回的单个版本信息
+#[derive(Debug, Deserialize)]
+struct VersionInfo {
+    #[serde(rename = "crate")]
+    name: String,
+    num: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    documentation: Option<String>,
+    downloads: u32,
+    #[serde(default)]
+    yanked: bool,
+}
+
+impl From<VersionInfo> for CrateInfo {
+    fn from(version: VersionInfo) -> Self {
+        Self {
+            name: version.name,
+            description: version.description,
+            repository: version.repository,
+            homepage: version.homepage,
+            documentation: version.documentation,
+            downloads: version.downloads,
+            version: Some(version.num),
+            yanked: version.yanked,
+        }
+    }
+}
+
+/// crate 的发布信息，来自 `/crates/{name}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrateReleases {
+    pub name: String,
+    /// 最新的稳定版本，只有预发布版本时为空
+    pub max_stable_version: Option<String>,
+    pub max_version: String,
+    pub versions: Vec<ReleaseInfo>,
+}
+
+impl CrateReleases {
+    /// 最新的稳定版本，没有稳定版本时使用最新版本
+    pub fn latest_version(&self) -> &str {
+        self.max_stable_version
+            .as_deref()
+            .unwrap_or(&self.max_version)
+    }
+
+    pub fn find(&self, version: &str) -> Option<&ReleaseInfo> {
+        self.versions.iter().find(|release| release.num == version)
+    }
+}
+
+/// 单个版本的发布信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub num: String,
+    #[serde(default)]
+    pub yanked: bool,
+    /// 发布时间，RFC 3339 格式，例如 `2025-03-09T17:09:56.371603+00:00`
+    pub created_at: String,
+}
+
+/// 依赖元数据来源
+///
+/// 默认实现 [`ApiSource`] 请求 crates.io 与 GitHub，
+/// 测试中可以替换为本地数据，使整个流程不依赖网络
+pub trait MetadataSource: Send + Sync + 'static {
+    /// 获取 crate 的基本信息（描述、仓库地址等），指定版本时获取该版本的信息
+    fn get_crate_info(
+        &self,
+        name: &str,
+        version: Option<&str>,
+    ) -> impl Future<Output = Result<CrateInfo>> + Send;
+
+    /// 获取指定版本的 README，没有 README 时返回 `Ok(None)`
+    fn get_readme(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> impl Future<Output = Result<Option<String>>> + Send;
+
+    /// 获取 crate 的所有版本与最新的稳定版本
+    fn get_crate_releases(&self, name: &str) -> impl Future<Output = Result<CrateReleases>> + Send;
+
+    /// 获取仓库的统计信息，没有可用的仓库信息来源时返回 `Ok(None)`
+    fn get_repository_info(
+        &self,
+        repo: &RepositoryRef,
+    ) -> impl Future<Output = Result<Option<RepositoryInfo>>> + Send;
+
+    /// 从替代注册表获取 crate 的信息，没有配置该注册表时返回 [`AppError::UnknownRegistry`]
+    fn get_registry_crate_info(
+        &self,
+        registry: &str,
+        _name: &str,
+        _version: Option<&str>,
+    ) -> impl Future<Output = Result<CrateInfo>> + Send {
+        let error = AppError::UnknownRegistry(registry.to_string());
+        async { Err(error.into()) }
+    }
+
+    /// 获取 crate 在 crates.io 上的所有者（用户与团队）
+    fn get_crate_owners(&self, name: &str) -> impl Future<Output = Result<Vec<Maintainer>>> + Send;
+
+    /// 获取仓库贡献最多的前 `limit` 位贡献者，不支持的托管平台返回空列表
+    fn get_contributors(
+        &self,
+        _repo: &RepositoryRef,
+        _limit: usize,
+    ) -> impl Future<Output = Result<Vec<Maintainer>>> + Send {
+        async { Ok(vec![]) }
+    }
+
+    /// 在逐个处理依赖之前批量获取仓库信息，失败时不影响之后的逐个请求
+    fn prefetch_repositories(&self, _repos: &[RepositoryRef]) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+}
+
+/// 通过 crates.io 与各个代码托管平台的 API 获取元数据
+pub struct ApiSource {
+    crates_io: CratesioClient,
+    github: Option<GitHubClient>,
+    gitlab: Option<GitLabClient>,
+    gitea: Option<GiteaClient>,
+    bitbucket: Option<BitbucketClient>,
+    /// Cargo 配置中的替代注册表
+    registries: Vec<RegistryClient>,
+}
+
+impl ApiSource {
+    pub fn new(crates_io: CratesioClient, github: Option<GitHubClient>) -> Self {
+        Self {
+            crates_io,
+            github,
+            gitlab: None,
+            gitea: None,
+            bitbucket: None,
+            registries: vec![],
+        }
+    }
+
+    pub fn with_registries(mut self, registries: Vec<RegistryClient>) -> Self {
+        self.registries = registries;
+        self
+    }
+
+    pub fn with_gitlab(mut self, gitlab: Option<GitLabClient>) -> Self {
+        self.gitlab = gitlab;
+        self
+    }
+
+    pub fn with_gitea(mut self, gitea: Option<GiteaClient>) -> Self {
+        self.gitea = gitea;
+        self
+    }
+
+    pub fn with_bitbucket(mut self, bitbucket: Option<BitbucketClient>) -> Self {
+        self.bitbucket = bitbucket;
+        self
+    }
+
+    pub fn github(&self) -> Option<&GitHubClient> {
+        self.github.as_ref()
+    }
+
+    /// 在详细模式下输出各个 API 剩余的请求额度
+    pub fn log_rate_limits(&self) {
+        self.crates_io.limiter.log_budget();
+        if let Some(github) = &self.github {
+            github.limiter.log_budget();
+            github.graphql_limiter.log_budget();
+        }
+        let hosts = [
+            self.gitlab.as_ref().map(|client| &client.limiter),
+            self.gitea.as_ref().map(|client| &client.limiter),
+            self.bitbucket.as_ref().map(|client| &client.limiter),
+        ];
+        hosts
+            .into_iter()
+            .flatten()
+            .chain(self.registries.iter().map(|client| &client.limiter))
+            .for_each(RateLimiter::log_budget);
+    }
+}
+
+/// 使用托管平台的客户端获取仓库信息，没有对应的客户端时返回 `Ok(None)`
+async fn repository_info_from<H: RepositoryHost>(
+    host: Option<&H>,
+    repo: &RepositoryRef,
+) -> Result<Option<RepositoryInfo>> {
+    match host {
+        Some(host) => host.get_repository_info(repo).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+impl MetadataSource for ApiSource {
+    async fn get_crate_info(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        self.crates_io.get_crate_info(name, version).await
+    }
+
+    async fn get_readme(&self, name: &str, version: &str) -> Result<Option<String>> {
+        self.crates_io.get_readme(name, version).await
+    }
+
+    async fn get_crate_releases(&self, name: &str) -> Result<CrateReleases> {
+        self.crates_io.get_crate_releases(name).await
+    }
+
+    async fn get_repository_info(&self, repo: &RepositoryRef) -> Result<Option<RepositoryInfo>> {
+        match repo.kind {
+            HostKind::GitHub => repository_info_from(self.github.as_ref(), repo).await,
+            HostKind::GitLab => repository_info_from(self.gitlab.as_ref(), repo).await,
+            HostKind::Gitea => repository_info_from(self.gitea.as_ref(), repo).await,
+            HostKind::Bitbucket => repository_info_from(self.bitbucket.as_ref(), repo).await,
+        }
+    }
+
+    async fn get_registry_crate_info(
+        &self,
+        registry: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<CrateInfo> {
+        match self
+            .registries
+            .iter()
+            .find(|client| client.name() == registry)
+        {
+            Some(client) => client.get_crate_info(name, version).await,
+            None => Err(AppError::UnknownRegistry(registry.to_string()).into()),
+        }
+    }
+
+    async fn get_crate_owners(&self, name: &str) -> Result<Vec<Maintainer>> {
+        self.crates_io.get_crate_owners(name).await
+    }
+
+    /// 目前只支持 GitHub 仓库的贡献者
+    async fn get_contributors(
+        &self,
+        repo: &RepositoryRef,
+        limit: usize,
+    ) -> Result<Vec<Maintainer>> {
+        match (&self.github, repo.kind) {
+            (Some(github), HostKind::GitHub) => {
+                github
+                    .get_contributors(&repo.owner, &repo.repo, limit)
+                    .await
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// 通过 GitHub GraphQL 批量获取 GitHub 仓库，失败时回退到逐个请求 REST 接口
+    async fn prefetch_repositories(&self, repos: &[RepositoryRef]) {
+        let Some(github) = &self.github else {
+            return;
+        };
+
+        let repos = repos
+            .iter()
+            .filter(|repo| repo.kind == HostKind::GitHub)
+            .collect::<Vec<_>>();
+        match github.prefetch_repositories(&repos).await {
+            Ok(count) => debug!("{}", t!("sources.graphql_prefetched", count = count)),
+            Err(e) => debug!("{}", t!("sources.graphql_fallback", error = e.to_string())),
+        }
+    }
+}
+
+/// 请求 JSON 接口
+///
+/// `file://` 开头的地址会读取本地的 fixture 文件 (`<path>.json`)，
+/// 便于在没有网络的环境中使用预先准备好的数据
+async fn get_json(client: &Client, limiter: &RateLimiter, url: &str) -> Result<serde_json::Value> {
+    get_json_request(limiter, url, client.get(url)).await
+}
+
+/// 发送已经构造好的 `url` 请求，例如附加了只属于这一个请求的认证请求头
+async fn get_json_request(
+    limiter: &RateLimiter,
+    url: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<serde_json::Value> {
+    if url.starts_with("file://") {
+        let content = read_fixture(url, "json").await?;
+        return Ok(serde_json::from_str(&content)?);
+    }
+
+    let response = check_status(send(limiter, request).await?)?;
+    Ok(response.json().await.map_err(request_error)?)
+}
+
+/// 请求文本内容，资源不存在时返回 `Ok(None)`
+///
+/// `file://` 开头的地址读取本地的 fixture 文件 (`<path>.html`)
+async fn get_text(client: &Client, limiter: &RateLimiter, url: &str) -> Result<Option<String>> {
+    if url.starts_with("file://") {
+        return match read_fixture(url, "html").await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if matches!(e.downcast_ref::<AppError>(), Some(AppError::NotFound(_))) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        };
+    }
+
+    let response = send(limiter, client.get(url)).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let response = check_status(response)?;
+    Ok(Some(response.text().await.map_err(request_error)?))
+}
+
+/// 经过限流器发送请求
+///
+/// 被限流时等待限流器的暂停结束后重新发送，最多重试 [`rate_limit::MAX_RETRIES`] 次，
+/// 仍然被限流或者需要暂停太久时返回 [`AppError::RateLimited`](crate::errors::AppError::RateLimited)
+async fn send(
+    limiter: &RateLimiter,
+    mut request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response> {
+    let mut retries = 0;
+    loop {
+        limiter.acquire().await?;
+        let retry = request.try_clone();
+        let response = request.send().await.map_err(request_error)?;
+        match (limiter.update(response.status(), response.headers()), retry) {
+            (Err(_), Some(retry)) if retries < rate_limit::MAX_RETRIES => {
+                request = retry;
+                retries += 1;
+            }
+            (result, _) => return result.map(|_| response),
+        }
+    }
+}
+
+/// 将 HTTP 错误状态转换为对应的 [`AppError`]，成功的响应原样返回
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    let url = response.url().to_string();
+    let error = match status {
+        _ if !status.is_client_error() && !status.is_server_error() => return Ok(response),
+        StatusCode::NOT_FOUND | StatusCode::GONE => AppError::NotFound(url),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => AppError::Unauthorized(url),
+        StatusCode::TOO_MANY_REQUESTS => AppError::RateLimited(url),
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => AppError::Timeout(url),
+        _ if status.is_server_error() => AppError::Network(format!("{} ({})", status, url)),
+        _ => return Ok(response.error_for_status()?),
+    };
+    Err(error.into())
+}
+
+/// 区分请求失败的原因，超时与连接失败是临时性的错误
+fn request_error(error: reqwest::Error) -> AppError {
+    if error.is_timeout() {
+        AppError::Timeout(error.to_string())
+    } else if error.is_connect() || error.is_request() || error.is_body() {
+        AppError::Network(error.to_string())
+    } else {
+        AppError::HttpError(error)
+    }
+}
+
+async fn read_fixture(url: &str, extension: &str) -> Result<String> {
+    let mut path = Url::parse(url)?
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!(t!("sources.invalid_fixture_url", url = url)))?
+        .into_os_string();
+    path.push(".");
+    path.push(extension);
+    match tokio::fs::read_to_string(&path).await {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(AppError::NotFound(path.to_string_lossy().into_owned()).into())
+        }
+        result => Ok(result?),
+    }
+}
+
+/// 解析 crates.io 兼容 API 的 `/crates/{name}` 或 `/crates/{name}/{version}` 响应
+fn parse_crate_info(mut data: serde_json::Value, versioned: bool) -> Result<CrateInfo> {
+    match versioned {
+        true => Ok(serde_json::from_value::<VersionInfo>(data["version"].take())?.into()),
+        false => Ok(serde_json::from_value(data["crate"].take())?),
+    }
+}
+
+/// 创建带有统一超时与 User-Agent 的 HTTP 客户端
+fn build_http_client(headers: reqwest::header::HeaderMap) -> Result<Client> {
+    Ok(Client::builder()
+        .timeout(Duration::from_secs(10))
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .default_headers(headers)
+        .build()?)
 }
 
 pub struct CratesioClient {
     client: Client,
+    base_url: String,
+    cache: Option<Cache>,
+    limiter: RateLimiter,
 }
 
 impl CratesioClient {
-    pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(10))
-                .user_agent(concat!(
-                    env!("CARGO_PKG_NAME"),
-                    "/",
-                    env!("CARGO_PKG_VERSION")
-                ))
-                .build()
-                .expect(&format!("{}", t!("sources.failed_to_create_http_client"))),
-        }
+    pub const DEFAULT_API_URL: &'static str = "https://crates.io/api/v1";
+    /// crates.io 的爬虫政策要求每秒最多一个请求
+    pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            client: build_http_client(reqwest::header::HeaderMap::new())?,
+            base_url: Self::DEFAULT_API_URL.to_string(),
+            cache: None,
+            limiter: RateLimiter::new("crates.io", Self::MIN_REQUEST_INTERVAL),
+        })
+    }
+
+    /// 使用其他的 API 地址，例如本地的模拟服务或 `file://` fixture 目录
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
     }
 
     pub fn get_crate_url(name: &str) -> String {
         format!("https://crates.io/crates/{}", name)
     }
 
+    /// 获取 crate 的信息，指定版本时请求 `/crates/{name}/{version}`
+    #[instrument(skip(self))]
+    pub async fn get_crate_info(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::crate_info(name, version);
+                cache
+                    .get_or_fetch(&key, || self.fetch_crate_info(name, version))
+                    .await
+            }
+            None => self.fetch_crate_info(name, version).await,
+        }
+    }
+
+    async fn fetch_crate_info(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        let url = match version {
+            Some(version) => format!("{}/crates/{}/{}", self.base_url, name, version),
+            None => format!("{}/crates/{}", self.base_url, name),
+        };
+        let data = get_json(&self.client, &self.limiter, &url).await?;
+        parse_crate_info(data, version.is_some())
+    }
+
+    /// 获取指定版本的 README（HTML），用于检查弃用声明
+    #[instrument(skip(self))]
+    pub async fn get_readme(&self, name: &str, version: &str) -> Result<Option<String>> {
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::crate_readme(name, version);
+                cache
+                    .get_or_fetch(&key, || self.fetch_readme(name, version))
+                    .await
+            }
+            None => self.fetch_readme(name, version).await,
+        }
+    }
+
+    async fn fetch_readme(&self, name: &str, version: &str) -> Result<Option<String>> {
+        let url = format!("{}/crates/{}/{}/readme", self.base_url, name, version);
+        get_text(&self.client, &self.limiter, &url).await
+    }
+
+    /// 获取 crate 的发布信息，包括所有版本的发布时间与撤回状态
+    #[instrument(skip(self))]
+    pub async fn get_crate_releases(&self, name: &str) -> Result<CrateReleases> {
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::crate_releases(name);
+                cache
+                    .get_or_fetch(&key, || self.fetch_crate_releases(name))
+                    .await
+            }
+            None => self.fetch_crate_releases(name).await,
+        }
+    }
+
+    async fn fetch_crate_releases(&self, name: &str) -> Result<CrateReleases> {
+        let url = format!("{}/crates/{}", self.base_url, name);
+        let data = get_json(&self.client, &self.limiter, &url).await?;
+        let crate_info = &data["crate"];
+
+        Ok(CrateReleases {
+            name: serde_json::from_value(crate_info["name"].clone())?,
+            max_stable_version: serde_json::from_value(crate_info["max_stable_version"].clone())?,
+            max_version: serde_json::from_value(crate_info["max_version"].clone())?,
+            versions: serde_json::from_value(data["versions"].clone())?,
+        })
+    }
+
+    /// 获取 crate 的所有者，团队所有者的登录名为 `组织/团队`
     #[instrument(skip(self))]
-    pub async fn get_crate_info(&self, name: &str) -> Result<CrateInfo> {
-        let url = format!("https://crates.io/api/v1/crates/{}", name);
-        let response = self.client.get(&url).send().await?;
-        let data = response.json::<serde_json::Value>().await?;
-        let crate_info = data["crate"].clone();
+    pub async fn get_crate_owners(&self, name: &str) -> Result<Vec<Maintainer>> {
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::crate_owners(name);
+                cache
+                    .get_or_fetch(&key, || self.fetch_crate_owners(name))
+                    .await
+            }
+            None => self.fetch_crate_owners(name).await,
+        }
+    }
 
-        Ok(serde_json::from_value(crate_info)?)
+    async fn fetch_crate_owners(&self, name: &str) -> Result<Vec<Maintainer>> {
+        let url = format!("{}/crates/{}/owners", self.base_url, name);
+        let data = get_json(&self.client, &self.limiter, &url).await?;
+        crate::maintainers::from_owners(&data)
     }
 }
 
 pub struct GitHubClient {
     client: Client,
+    base_url: String,
+    cache: Option<Cache>,
+    limiter: RateLimiter,
+    /// GraphQL 接口的额度与 REST 接口分开计算
+    graphql_limiter: RateLimiter,
+    /// GraphQL 接口需要令牌，没有令牌时只使用 REST 接口
+    authenticated: bool,
+    /// 批量获取的仓库信息，键为小写的 `owner/repo`
+    prefetched: Mutex<HashMap<String, RepositoryInfo>>,
 }
 
 impl GitHubClient {
-    pub fn new(token: &str) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .user_agent(concat!(
-                env!("CARGO_PKG_NAME"),
-                "/",
-                env!("CARGO_PKG_VERSION")
-            ))
-            .default_headers({
-                let mut headers = reqwest::header::HeaderMap::new();
-                headers.insert(
-                    reqwest::header::AUTHORIZATION,
-                    reqwest::header::HeaderValue::from_str(&format!("token {}", token))?,
-                );
-                headers
-            })
-            .build()?;
+    pub const DEFAULT_API_URL: &'static str = "https://api.github.com";
+    /// 贡献者接口每页的最大数量
+    pub const CONTRIBUTORS_PER_PAGE: usize = 100;
+
+    /// 没有令牌时只能读取缓存 (`--offline`) 或使用未认证的低频率限制
+    pub fn new(token: Option<&str>) -> Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(token) = token {
+            headers.insert(
+                reqwest::header::AUTHORIZATION,
+                reqwest::header::HeaderValue::from_str(&format!("token {}", token))?,
+            );
+        }
+
+        Ok(Self {
+            client: build_http_client(headers)?,
+            base_url: Self::DEFAULT_API_URL.to_string(),
+            cache: None,
+            limiter: RateLimiter::new("GitHub", Duration::ZERO),
+            graphql_limiter: RateLimiter::new("GitHub GraphQL", Duration::ZERO),
+            authenticated: token.is_some(),
+            prefetched: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 使用其他的 API 地址，例如本地的模拟服务或 `file://` fixture 目录
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
 
-        Ok(Self { client })
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// 当前用户是否已经点赞了该仓库
+    ///
+    /// GitHub 返回 204 表示已点赞，404 表示未点赞
+    #[instrument(skip(self))]
+    pub async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        let url = format!("{}/user/starred/{}/{}", self.base_url, owner, repo);
+        let response = send(&self.limiter, self.client.get(&url)).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        check_status(response)?;
+        Ok(true)
     }
 
     #[instrument(skip(self))]
     pub async fn star_repository(&self, owner: &str, repo: &str) -> Result<()> {
-        let url = format!("https://api.github.com/user/starred/{}/{}", owner, repo);
-        self.client.put(&url).send().await?;
+        let url = format!("{}/user/starred/{}/{}", self.base_url, owner, repo);
+        let request = self
+            .client
+            .put(&url)
+            .header(reqwest::header::CONTENT_LENGTH, 0);
+        check_status(send(&self.limiter, request).await?)?;
         Ok(())
     }
 
+    /// 获取仓库信息，优先使用批量获取的结果
     #[instrument(skip(self))]
     pub async fn get_repository_info(&self, owner: &str, repo: &str) -> Result<RepositoryInfo> {
-        let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
-        let response = self.client.get(&url).send().await?;
-        Ok(response.json().await?)
+        let prefetched = self
+            .prefetched
+            .lock()
+            .unwrap()
+            .get(&format!("{}/{}", owner, repo).to_lowercase())
+            .cloned();
+        if let Some(repo_info) = prefetched {
+            return Ok(repo_info);
+        }
+
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::github_repository(owner, repo);
+                cache
+                    .get_or_fetch(&key, || self.fetch_repository_info(owner, repo))
+                    .await
+            }
+            None => self.fetch_repository_info(owner, repo).await,
+        }
+    }
+
+    async fn fetch_repository_info(&self, owner: &str, repo: &str) -> Result<RepositoryInfo> {
+        let url = format!("{}/repos/{}/{}", self.base_url, owner, repo);
+        let data = get_json(&self.client, &self.limiter, &url).await?;
+        let mut repo_info: RepositoryInfo = serde_json::from_value(data)?;
+
+        // REST 接口不返回赞助链接，读取仓库中的 FUNDING.yml，失败时不影响仓库信息
+        match self.fetch_funding_links(owner, repo).await {
+            Ok(links) => repo_info.funding_links = links,
+            Err(e) => debug!(
+                "{}",
+                t!(
+                    "sources.funding_fetch_failed",
+                    repo = repo_info.full_name,
+                    error = e.to_string()
+                )
+            ),
+        }
+        Ok(repo_info)
+    }
+
+    /// 读取仓库中的 `.github/FUNDING.yml`，不存在时返回空列表
+    async fn fetch_funding_links(&self, owner: &str, repo: &str) -> Result<Vec<FundingLink>> {
+        let url = format!(
+            "{}/repos/{}/{}/contents/.github/FUNDING.yml",
+            self.base_url, owner, repo
+        );
+        // fixture 目录中使用 `FUNDING.yml.html` 保存原始内容
+        let content = match url.starts_with("file://") {
+            true => get_text(&self.client, &self.limiter, &url).await?,
+            false => {
+                let request = self
+                    .client
+                    .get(&url)
+                    .header(reqwest::header::ACCEPT, "application/vnd.github.raw+json");
+                let response = send(&self.limiter, request).await?;
+                match response.status() {
+                    StatusCode::NOT_FOUND => None,
+                    _ => Some(
+                        check_status(response)?
+                            .text()
+                            .await
+                            .map_err(request_error)?,
+                    ),
+                }
+            }
+        };
+
+        match content {
+            Some(content) => crate::funding::from_funding_yml(&content),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// 获取仓库贡献最多的前 `limit` 位贡献者，不包括机器人账号
+    ///
+    /// 无论 `limit` 是多少都只请求第一页（最多 [`Self::CONTRIBUTORS_PER_PAGE`] 位），
+    /// 缓存的结果可以用于不同的 `limit`
+    #[instrument(skip(self))]
+    pub async fn get_contributors(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<Maintainer>> {
+        let mut contributors = match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::github_contributors(owner, repo);
+                cache
+                    .get_or_fetch(&key, || self.fetch_contributors(owner, repo))
+                    .await?
+            }
+            None => self.fetch_contributors(owner, repo).await?,
+        };
+        contributors.truncate(limit);
+        Ok(contributors)
+    }
+
+    async fn fetch_contributors(&self, owner: &str, repo: &str) -> Result<Vec<Maintainer>> {
+        let url = format!(
+            "{}/repos/{}/{}/contributors?per_page={}",
+            self.base_url,
+            owner,
+            repo,
+            Self::CONTRIBUTORS_PER_PAGE
+        );
+        let data = get_json(&self.client, &self.limiter, &url).await?;
+        crate::maintainers::from_contributors(&data)
+    }
+
+    /// 通过 GraphQL 接口批量获取仓库信息，每次请求最多查询 [`graphql::BATCH_SIZE`] 个仓库
+    ///
+    /// 结果保存在内存与缓存中，供之后的 [`Self::get_repository_info`] 使用，返回获取到的仓库数量。
+    /// 已缓存的仓库不会重复查询；没有令牌或使用 `file://` fixture 时不查询，
+    /// 查询失败时返回错误，调用者应继续使用 REST 接口
+    pub async fn prefetch_repositories(&self, repos: &[&RepositoryRef]) -> Result<usize> {
+        if !self.authenticated || self.base_url.starts_with("file://") {
+            return Ok(0);
+        }
+
+        let mut pending: Vec<&RepositoryRef> = Vec::new();
+        for repo in repos {
+            let cached = self.cache.as_ref().is_some_and(|cache| {
+                let key = CacheKey::github_repository(&repo.owner, &repo.repo);
+                cache.get::<RepositoryInfo>(&key).is_some()
+            });
+            let duplicated = pending
+                .iter()
+                .any(|p| p.full_name().eq_ignore_ascii_case(&repo.full_name()));
+            if !cached && !duplicated {
+                pending.push(repo);
+            }
+        }
+
+        let url = format!("{}/graphql", self.base_url);
+        let mut count = 0;
+        for batch in pending.chunks(graphql::BATCH_SIZE) {
+            let query = serde_json::json!({ "query": graphql::build_query(batch) });
+            let request = self.client.post(&url).json(&query);
+            let response = check_status(send(&self.graphql_limiter, request).await?)?
+                .json()
+                .await
+                .map_err(request_error)?;
+
+            let repositories = graphql::parse_response(response, batch.len())?;
+            let mut prefetched = self.prefetched.lock().unwrap();
+            for (repo, repo_info) in batch.iter().zip(repositories) {
+                let Some(repo_info) = repo_info else {
+                    continue;
+                };
+                if let Some(cache) = &self.cache {
+                    let key = CacheKey::github_repository(&repo.owner, &repo.repo);
+                    if let Err(e) = cache.put(&key, &repo_info) {
+                        debug!(
+                            "{}",
+                            t!(
+                                "sources.cache_write_failed",
+                                key = key,
+                                error = e.to_string()
+                            )
+                        );
+                    }
+                }
+                prefetched.insert(repo.full_name().to_lowercase(), repo_info);
+                count += 1;
+            }
+        }
+
+        Ok(count)
     }
 }
 
+impl RepositoryHost for GitHubClient {
+    async fn get_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        GitHubClient::get_repository_info(self, &repo.owner, &repo.repo).await
+    }
+}
+
+/// 仓库的统计信息，各个托管平台的响应都转换为 GitHub 的字段名
 #[allow(unused)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RepositoryInfo {
     pub full_name: String,
     pub description: Option<String>,
     pub stargazers_count: u32,
     pub html_url: String,
+    /// 仓库是否已归档（只读）
+    #[serde(default)]
+    pub archived: bool,
+    /// GitHub 识别出的许可证
+    #[serde(default)]
+    pub license: Option<RepositoryLicense>,
+    /// 最后一次推送的时间，例如 `2025-03-09T12:00:00Z`
+    #[serde(default)]
+    pub pushed_at: Option<String>,
+    /// 赞助链接，来自 GraphQL 的 `fundingLinks` 或仓库中的 `.github/FUNDING.yml`
+    #[serde(default)]
+    pub funding_links: Vec<FundingLink>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryLicense {
+    /// SPDX 标识符，GitHub 无法识别时为 `NOASSERTION`
+    pub spdx_id: Option<String>,
 }
 
 #[cfg(test)]
@@ -187,20 +949,524 @@ mod tests {
         }
     }
 
+    const SERDE_CRATE: &str = r#"{
+        "crate": {
+            "name": "serde",
+            "description": "A generic serialization/deserialization framework",
+            "repository": "https://github.com/serde-rs/serde",
+            "homepage": "https://serde.rs",
+            "documentation": "https://docs.rs/serde",
+            "downloads": 42,
+            "max_version": "1.0.220-rc.1",
+            "max_stable_version": "1.0.219"
+        },
+        "versions": [
+            { "num": "1.0.220-rc.1", "yanked": false, "created_at": "2025-04-01T10:00:00.000000+00:00" },
+            { "num": "1.0.219", "yanked": false, "created_at": "2025-03-09T17:09:56.371603+00:00" },
+            { "num": "1.0.218", "yanked": true, "created_at": "2025-02-20T08:00:00.000000+00:00" }
+        ]
+    }"#;
+
+    const SERDE_VERSION: &str = r#"{
+        "version": {
+            "crate": "serde",
+            "num": "1.0.219",
+            "description": "A generic serialization/deserialization framework",
+            "repository": "https://github.com/serde-rs/serde",
+            "homepage": "https://serde.rs",
+            "documentation": "https://docs.rs/serde",
+            "downloads": 7,
+            "yanked": true
+        }
+    }"#;
+
+    const SERDE_REPOSITORY: &str = r#"{
+        "full_name": "serde-rs/serde",
+        "description": "Serialization framework for Rust",
+        "stargazers_count": 9000,
+        "html_url": "https://github.com/serde-rs/serde"
+    }"#;
+
+    /// 启动一个本地 HTTP 模拟服务，按请求路径返回固定的 JSON，未知路径返回 404
+    async fn stub_server(routes: Vec<(&'static str, &'static str)>) -> String {
+        recording_stub_server(routes).await.0
+    }
+
+    /// 与 [`stub_server`] 相同，同时记录收到的原始请求，用于检查请求头
+    async fn recording_stub_server(
+        routes: Vec<(&'static str, &'static str)>,
+    ) -> (String, std::sync::Arc<Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(Mutex::new(vec![]));
+        let recorded = std::sync::Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 8192];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                recorded.lock().unwrap().push(request.to_lowercase());
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                let (status, body) = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => ("200 OK", *body),
+                    None => ("404 Not Found", r#"{"errors":[{"detail":"Not Found"}]}"#),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (format!("http://{}", addr), requests)
+    }
+
     #[tokio::test]
-    async fn test_cratesio_client_serde() {
-        let client = CratesioClient::new();
-        let crate_info = client.get_crate_info("serde").await.unwrap();
-        println!("{:?}", crate_info);
+    async fn test_cratesio_client_serde() -> Result<()> {
+        let base_url = stub_server(vec![("/api/v1/crates/serde", SERDE_CRATE)]).await;
+        let client = CratesioClient::new()?.with_base_url(&format!("{}/api/v1/", base_url));
+
+        let crate_info = client.get_crate_info("serde", None).await?;
         assert_eq!(crate_info.name, "serde");
+        assert_eq!(crate_info.version, None);
         assert!(crate_info.description.is_some());
-        assert!(crate_info.repository.is_some());
+        assert_eq!(
+            crate_info.repository.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+        assert_eq!(crate_info.homepage.as_deref(), Some("https://serde.rs"));
+        assert_eq!(crate_info.downloads, 42);
+
+        // 404 被归类为 NotFound，而不是 JSON 解析失败
+        let error = client
+            .get_crate_info("not-a-crate", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::NotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cratesio_client_readme() -> Result<()> {
+        let base_url = stub_server(vec![(
+            "/api/v1/crates/serde/1.0.219/readme",
+            "<h1>Serde</h1><p>This crate is no longer maintained.</p>",
+        )])
+        .await;
+        let client = CratesioClient::new()?.with_base_url(&format!("{}/api/v1", base_url));
+
+        let readme = client.get_readme("serde", "1.0.219").await?;
+        assert!(readme.is_some_and(|readme| readme.contains("no longer maintained")));
+
+        // 没有 README 时返回 None 而不是错误
+        assert!(client.get_readme("serde", "0.1.0").await?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cratesio_client_releases() -> Result<()> {
+        let base_url = stub_server(vec![("/api/v1/crates/serde", SERDE_CRATE)]).await;
+        let client = CratesioClient::new()?.with_base_url(&format!("{}/api/v1", base_url));
+
+        let releases = client.get_crate_releases("serde").await?;
+        assert_eq!(releases.name, "serde");
+        assert_eq!(releases.latest_version(), "1.0.219");
+        assert_eq!(releases.versions.len(), 3);
         assert!(
-            crate_info
-                .repository
-                .unwrap()
-                .eq("https://github.com/serde-rs/serde")
+            releases
+                .find("1.0.218")
+                .is_some_and(|release| release.yanked)
         );
-        assert!(crate_info.downloads > 0);
+        assert!(releases.find("0.1.0").is_none());
+
+        assert!(client.get_crate_releases("not-a-crate").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_registry_client_stub_server() -> Result<()> {
+        use crate::registries::Registry;
+
+        const SERDE_INDEX: &str = concat!(
+            r#"{"name":"serde","vers":"1.0.218","deps":[],"cksum":"00","features":{},"yanked":true}"#,
+            "\n",
+            r#"{"name":"serde","vers":"1.0.219","deps":[],"cksum":"00","features":{},"yanked":false}"#,
+            "\n"
+        );
+        let registry = |name: &str, index: String| Registry {
+            name: name.to_string(),
+            index,
+            token: Some("secret".to_string()),
+        };
+
+        // 只提供稀疏索引的注册表
+        let base_url = stub_server(vec![
+            ("/index/config.json", r#"{"dl":"https://example.com/dl"}"#),
+            ("/index/se/rd/serde", SERDE_INDEX),
+        ])
+        .await;
+        let client =
+            RegistryClient::new(&registry("internal", format!("sparse+{}/index/", base_url)))?;
+        assert_eq!(client.name(), "internal");
+
+        let crate_info = client.get_crate_info("serde", Some("1.0.218")).await?;
+        assert!(crate_info.yanked);
+        assert_eq!(crate_info.description, None);
+        let crate_info = client.get_crate_info("serde", None).await?;
+        assert_eq!(crate_info.version.as_deref(), Some("1.0.219"));
+        let error = client.get_crate_info("tokio", None).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::NotFound(_))
+        ));
+
+        // 提供 crates.io 兼容 API 的注册表
+        let api_url = stub_server(vec![("/api/v1/crates/serde/1.0.219", SERDE_VERSION)]).await;
+        let config: &'static str =
+            Box::leak(format!(r#"{{"dl":"{0}/dl","api":"{0}"}}"#, api_url).into_boxed_str());
+        let base_url = stub_server(vec![("/config.json", config)]).await;
+        let client = RegistryClient::new(&registry("with-api", format!("sparse+{}", base_url)))?;
+        let crate_info = client.get_crate_info("serde", Some("1.0.219")).await?;
+        assert_eq!(
+            crate_info.repository.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+
+        // git 索引无法读取
+        let client = RegistryClient::new(&registry("git", base_url))?;
+        let error = client.get_crate_info("serde", None).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::UnsupportedRegistry(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_maintainers_stub_server() -> Result<()> {
+        let base_url = stub_server(vec![
+            (
+                "/api/v1/crates/serde/owners",
+                r#"{"users":[{"id":3618,"login":"dtolnay","kind":"user","name":"David Tolnay","url":"https://github.com/dtolnay"}]}"#,
+            ),
+            (
+                "/repos/serde-rs/serde/contributors?per_page=100",
+                r#"[{"login":"dtolnay","html_url":"https://github.com/dtolnay","type":"User"},{"login":"dependabot[bot]","type":"Bot"},{"login":"oli-obk","html_url":"https://github.com/oli-obk","type":"User"}]"#,
+            ),
+        ])
+        .await;
+
+        let crates_io = CratesioClient::new()?.with_base_url(&format!("{}/api/v1", base_url));
+        let owners = crates_io.get_crate_owners("serde").await?;
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].name.as_deref(), Some("David Tolnay"));
+
+        let github = GitHubClient::new(None)?.with_base_url(&base_url);
+        let contributors = github.get_contributors("serde-rs", "serde", 1).await?;
+        assert_eq!(contributors.len(), 1);
+        assert_eq!(contributors[0].login, "dtolnay");
+        let contributors = github.get_contributors("serde-rs", "serde", 5).await?;
+        assert_eq!(contributors.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cratesio_client_version() -> Result<()> {
+        let base_url = stub_server(vec![("/api/v1/crates/serde/1.0.219", SERDE_VERSION)]).await;
+        let client = CratesioClient::new()?.with_base_url(&format!("{}/api/v1", base_url));
+
+        let crate_info = client.get_crate_info("serde", Some("1.0.219")).await?;
+        assert_eq!(crate_info.name, "serde");
+        assert_eq!(crate_info.version.as_deref(), Some("1.0.219"));
+        assert!(crate_info.yanked);
+        assert_eq!(
+            crate_info.repository.as_deref(),
+            Some("https://github.com/serde-rs/serde")
+        );
+        assert_eq!(crate_info.downloads, 7);
+
+        assert!(client.get_crate_info("serde", Some("0.1.0")).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_github_client_stub_server() -> Result<()> {
+        let base_url = stub_server(vec![
+            ("/repos/serde-rs/serde", SERDE_REPOSITORY),
+            (
+                "/repos/serde-rs/serde/contents/.github/FUNDING.yml",
+                "github: dtolnay\n",
+            ),
+        ])
+        .await;
+        let client = GitHubClient::new(Some("token"))?.with_base_url(&base_url);
+
+        let repo_info = client.get_repository_info("serde-rs", "serde").await?;
+        assert_eq!(repo_info.full_name, "serde-rs/serde");
+        assert_eq!(repo_info.stargazers_count, 9000);
+        // REST 接口读取仓库中的 FUNDING.yml
+        assert_eq!(
+            repo_info.funding_links,
+            vec![FundingLink {
+                platform: "GITHUB".to_string(),
+                url: "https://github.com/sponsors/dtolnay".to_string(),
+            }]
+        );
+
+        // 404 表示未点赞
+        assert!(!client.is_starred("serde-rs", "serde").await?);
+        Ok(())
+    }
+
+    const GRAPHQL_RESPONSE: &str = r#"{
+        "data": {
+            "r0": {
+                "nameWithOwner": "serde-rs/serde",
+                "description": "Serialization framework for Rust",
+                "stargazerCount": 9001,
+                "url": "https://github.com/serde-rs/serde",
+                "isArchived": false,
+                "pushedAt": "2025-03-09T12:00:00Z",
+                "licenseInfo": { "spdxId": "Apache-2.0" },
+                "fundingLinks": [{ "platform": "GITHUB", "url": "https://github.com/dtolnay" }]
+            },
+            "r1": null
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn test_github_client_graphql_batch() -> Result<()> {
+        let repo = |url: &str| RepositoryRef::from_url(&Url::parse(url).unwrap()).unwrap();
+        let serde = repo("https://github.com/serde-rs/serde");
+        let missing = repo("https://github.com/owner/missing");
+
+        // 批量获取的结果优先于 REST 接口
+        let base_url = stub_server(vec![
+            ("/graphql", GRAPHQL_RESPONSE),
+            ("/repos/serde-rs/serde", SERDE_REPOSITORY),
+        ])
+        .await;
+        let client = GitHubClient::new(Some("token"))?.with_base_url(&base_url);
+        assert_eq!(
+            client
+                .prefetch_repositories(&[&serde, &serde, &missing])
+                .await?,
+            1
+        );
+        let repo_info = client.get_repository_info("Serde-rs", "serde").await?;
+        assert_eq!(repo_info.stargazers_count, 9001);
+        assert_eq!(repo_info.funding_links.len(), 1);
+        assert!(
+            client
+                .get_repository_info("owner", "missing")
+                .await
+                .is_err()
+        );
+
+        // GraphQL 不可用时回退到 REST 接口
+        let base_url = stub_server(vec![("/repos/serde-rs/serde", SERDE_REPOSITORY)]).await;
+        let client = GitHubClient::new(Some("token"))?.with_base_url(&base_url);
+        assert!(client.prefetch_repositories(&[&serde]).await.is_err());
+        let repo_info = client.get_repository_info("serde-rs", "serde").await?;
+        assert_eq!(repo_info.stargazers_count, 9000);
+        assert!(repo_info.funding_links.is_empty());
+
+        // 没有令牌时不使用 GraphQL
+        let client = GitHubClient::new(None)?.with_base_url(&base_url);
+        assert_eq!(client.prefetch_repositories(&[&serde]).await?, 0);
+        Ok(())
+    }
+
+    const GITLAB_PROJECT: &str = r#"{
+        "path_with_namespace": "group/sub/repo",
+        "description": "A GitLab project",
+        "star_count": 12,
+        "web_url": "https://gitlab.com/group/sub/repo",
+        "archived": true
+    }"#;
+
+    const GITEA_REPOSITORY: &str = r#"{
+        "full_name": "owner/repo",
+        "description": "A Codeberg repository",
+        "stars_count": 34,
+        "html_url": "https://codeberg.org/owner/repo",
+        "archived": false
+    }"#;
+
+    const BITBUCKET_REPOSITORY: &str = r#"{
+        "full_name": "workspace/repo",
+        "description": "",
+        "links": { "html": { "href": "https://bitbucket.org/workspace/repo" } }
+    }"#;
+
+    #[tokio::test]
+    async fn test_repository_hosts_stub_server() -> Result<()> {
+        let base_url = stub_server(vec![
+            ("/gitlab/projects/group%2Fsub%2Frepo", GITLAB_PROJECT),
+            ("/gitea/repos/owner/repo", GITEA_REPOSITORY),
+            (
+                "/bitbucket/repositories/workspace/repo",
+                BITBUCKET_REPOSITORY,
+            ),
+            (
+                "/bitbucket/repositories/workspace/repo/watchers",
+                r#"{"size": 5, "values": []}"#,
+            ),
+        ])
+        .await;
+        let repo = |url: &str| RepositoryRef::from_url(&Url::parse(url).unwrap()).unwrap();
+
+        let source = ApiSource::new(CratesioClient::new()?, None)
+            .with_gitlab(Some(
+                GitLabClient::new(Some("token"), None)?
+                    .with_base_url(&format!("{}/gitlab", base_url)),
+            ))
+            .with_gitea(Some(
+                GiteaClient::new(None, None)?.with_base_url(&format!("{}/gitea", base_url)),
+            ))
+            .with_bitbucket(Some(
+                BitbucketClient::new(None)?.with_base_url(&format!("{}/bitbucket", base_url)),
+            ));
+
+        let info = source
+            .get_repository_info(&repo("https://gitlab.com/group/sub/repo"))
+            .await?
+            .unwrap();
+        assert_eq!(info.full_name, "group/sub/repo");
+        assert_eq!(info.stargazers_count, 12);
+        assert!(info.archived);
+
+        let info = source
+            .get_repository_info(&repo("https://codeberg.org/owner/repo"))
+            .await?
+            .unwrap();
+        assert_eq!(info.stargazers_count, 34);
+        assert!(!info.archived);
+
+        let info = source
+            .get_repository_info(&repo("https://bitbucket.org/workspace/repo"))
+            .await?
+            .unwrap();
+        assert_eq!(info.stargazers_count, 5);
+        assert_eq!(info.description, None);
+        assert_eq!(info.html_url, "https://bitbucket.org/workspace/repo");
+
+        assert!(
+            source
+                .get_repository_info(&repo("https://codeberg.org/owner/missing"))
+                .await
+                .is_err()
+        );
+        // 没有 GitHub 客户端时不提供 GitHub 仓库信息
+        assert!(
+            source
+                .get_repository_info(&repo("https://github.com/serde-rs/serde"))
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repository_hosts_token_host() -> Result<()> {
+        let (base_url, requests) = recording_stub_server(vec![
+            ("/gitlab/projects/group%2Frepo", GITLAB_PROJECT),
+            ("/gitea/repos/owner/repo", GITEA_REPOSITORY),
+        ])
+        .await;
+        let repo = |url: &str| RepositoryRef::from_url(&Url::parse(url).unwrap()).unwrap();
+        let gitlab_base_url = format!("{}/gitlab", base_url);
+        let gitea_base_url = format!("{}/gitea", base_url);
+        let authorized =
+            |index: usize, header: &str| requests.lock().unwrap()[index].contains(header);
+
+        // 只设置了 gitlab.com 的令牌时，其他主机上的仓库收不到令牌
+        let gitlab = GitLabClient::new(Some("secret"), None)?.with_base_url(&gitlab_base_url);
+        gitlab
+            .get_repository_info(&repo("https://gitlab.example.org/group/repo"))
+            .await?;
+        assert!(!authorized(0, "private-token"));
+        gitlab
+            .get_repository_info(&repo("https://gitlab.com/group/repo"))
+            .await?;
+        assert!(authorized(1, "private-token: secret"));
+
+        // 自建实例的令牌只发送给指定的主机
+        let gitlab = GitLabClient::new(Some("secret"), Some("GitLab.example.org"))?
+            .with_base_url(&gitlab_base_url);
+        gitlab
+            .get_repository_info(&repo("https://gitlab.example.org/group/repo"))
+            .await?;
+        assert!(authorized(2, "private-token: secret"));
+        gitlab
+            .get_repository_info(&repo("https://gitlab.com/group/repo"))
+            .await?;
+        assert!(!authorized(3, "private-token"));
+
+        let gitea = GiteaClient::new(Some("secret"), None)?.with_base_url(&gitea_base_url);
+        gitea
+            .get_repository_info(&repo("https://gitea.example.org/owner/repo"))
+            .await?;
+        assert!(!authorized(4, "authorization"));
+        gitea
+            .get_repository_info(&repo("https://codeberg.org/owner/repo"))
+            .await?;
+        assert!(authorized(5, "authorization: token secret"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_api_source_fixture_files() -> Result<()> {
+        let dir = assert_fs::TempDir::new()?;
+        std::fs::create_dir_all(dir.path().join("crates-io").join("crates"))?;
+        std::fs::write(
+            dir.path()
+                .join("crates-io")
+                .join("crates")
+                .join("serde.json"),
+            SERDE_CRATE,
+        )?;
+        std::fs::create_dir_all(dir.path().join("github").join("repos").join("serde-rs"))?;
+        std::fs::write(
+            dir.path()
+                .join("github")
+                .join("repos")
+                .join("serde-rs")
+                .join("serde.json"),
+            SERDE_REPOSITORY,
+        )?;
+
+        let base_url = Url::from_directory_path(dir.path()).unwrap();
+        let source = ApiSource::new(
+            CratesioClient::new()?.with_base_url(base_url.join("crates-io")?.as_str()),
+            Some(GitHubClient::new(None)?.with_base_url(base_url.join("github")?.as_str())),
+        );
+
+        let crate_info = source.get_crate_info("serde", None).await?;
+        assert_eq!(crate_info.downloads, 42);
+        assert!(source.get_crate_info("tokio", None).await.is_err());
+
+        let serde_repo =
+            RepositoryRef::from_url(&Url::parse("https://github.com/serde-rs/serde")?).unwrap();
+        let repo_info = source.get_repository_info(&serde_repo).await?;
+        assert_eq!(repo_info.map(|info| info.stargazers_count), Some(9000));
+
+        // 没有 GitHub 客户端时不提供仓库信息
+        let source = ApiSource::new(
+            CratesioClient::new()?.with_base_url(base_url.join("crates-io")?.as_str()),
+            None,
+        );
+        assert!(source.get_repository_info(&serde_repo).await?.is_none());
+        Ok(())
     }
 }
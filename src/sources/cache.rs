@@ -0,0 +1,404 @@
+use anyhow::Result;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::errors::AppError;
+
+/// 缓存的读取方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// 优先使用未过期的缓存，未命中时请求网络并写入缓存
+    #[default]
+    Normal,
+    /// 只使用缓存（忽略过期时间），未命中时不请求网络
+    Offline,
+    /// 忽略已有缓存，总是请求网络并刷新缓存
+    Refresh,
+}
+
+/// 缓存条目的键，对应缓存目录下的相对路径
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey(PathBuf);
+
+impl CacheKey {
+    /// crates.io 上的 crate 信息，指定版本时按版本分别缓存
+    pub fn crate_info(name: &str, version: Option<&str>) -> Self {
+        let mut path = PathBuf::from("crates-io");
+        match version {
+            Some(version) => {
+                path.push(sanitize(name));
+                path.push(format!("{}.json", sanitize(version)));
+            }
+            None => path.push(format!("{}.json", sanitize(name))),
+        }
+        Self(path)
+    }
+
+    /// 其它代码托管平台上的仓库信息，按主机名区分，多级的所有者（GitLab 群组）合并为一级
+    pub fn hosted_repository(host: &str, owner: &str, repo: &str) -> Self {
+        let mut path = PathBuf::from("repos");
+        path.push(sanitize(&host.to_lowercase()));
+        path.push(sanitize(&owner.to_lowercase()));
+        path.push(format!("{}.json", sanitize(&repo.to_lowercase())));
+        Self(path)
+    }
+
+    /// crates.io 上指定版本的 README
+    pub fn crate_readme(name: &str, version: &str) -> Self {
+        let mut path = PathBuf::from("crates-io-readme");
+        path.push(sanitize(name));
+        path.push(format!("{}.json", sanitize(version)));
+        Self(path)
+    }
+
+    /// crates.io 上 crate 的所有版本，与 crate 信息分开缓存
+    pub fn crate_releases(name: &str) -> Self {
+        let mut path = PathBuf::from("crates-io-releases");
+        path.push(format!("{}.json", sanitize(name)));
+        Self(path)
+    }
+
+    /// crates.io 上 crate 的所有者
+    pub fn crate_owners(name: &str) -> Self {
+        let mut path = PathBuf::from("crates-io-owners");
+        path.push(format!("{}.json", sanitize(name)));
+        Self(path)
+    }
+
+    /// GitHub 仓库的贡献者，按贡献数量排序
+    pub fn github_contributors(owner: &str, repo: &str) -> Self {
+        let mut path = PathBuf::from("github-contributors");
+        path.push(sanitize(&owner.to_lowercase()));
+        path.push(format!("{}.json", sanitize(&repo.to_lowercase())));
+        Self(path)
+    }
+
+    /// 替代注册表中的 crate 信息，按注册表名称区分，指定版本时按版本分别缓存
+    pub fn registry_crate_info(registry: &str, name: &str, version: Option<&str>) -> Self {
+        let mut path = PathBuf::from("registries");
+        path.push(sanitize(registry));
+        match version {
+            Some(version) => {
+                path.push(sanitize(name));
+                path.push(format!("{}.json", sanitize(version)));
+            }
+            None => path.push(format!("{}.json", sanitize(name))),
+        }
+        Self(path)
+    }
+
+    /// GitHub 仓库信息，`owner/repo` 不区分大小写
+    pub fn github_repository(owner: &str, repo: &str) -> Self {
+        let mut path = PathBuf::from("github");
+        path.push(sanitize(&owner.to_lowercase()));
+        path.push(format!("{}.json", sanitize(&repo.to_lowercase())));
+        Self(path)
+    }
+}
+
+impl std::fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.display())
+    }
+}
+
+/// 避免路径穿越等问题，只保留安全的字符
+fn sanitize(s: &str) -> String {
+    let s = s
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '+' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+
+    match s.trim_matches('.') {
+        "" => "_".to_string(),
+        _ => s,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    /// 写入时间，Unix 时间戳（秒）
+    fetched_at: u64,
+    data: T,
+}
+
+/// 保存在用户缓存目录中的 crates.io 与 GitHub 响应
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+    mode: CacheMode,
+}
+
+impl Cache {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration, mode: CacheMode) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+            mode,
+        }
+    }
+
+    /// 默认的缓存目录
+    ///
+    /// 优先使用 `CARGO_THANKU_CACHE_DIR`，否则使用各平台的用户缓存目录：
+    /// - Linux: `$XDG_CACHE_HOME/cargo-thanku` 或 `~/.cache/cargo-thanku`
+    /// - macOS: `~/Library/Caches/cargo-thanku`
+    /// - Windows: `%LOCALAPPDATA%\cargo-thanku`
+    pub fn default_dir() -> Option<PathBuf> {
+        let env_dir = |name: &str| {
+            std::env::var_os(name)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+        };
+
+        if let Some(dir) = env_dir("CARGO_THANKU_CACHE_DIR") {
+            return Some(dir);
+        }
+
+        let base = if cfg!(windows) {
+            env_dir("LOCALAPPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_dir("HOME").map(|home| home.join("Library").join("Caches"))
+        } else {
+            env_dir("XDG_CACHE_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".cache")))
+        };
+
+        base.map(|base| base.join(env!("CARGO_PKG_NAME")))
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(&key.0)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+
+    /// 读取缓存，`Refresh` 模式下总是未命中，`Offline` 模式下忽略过期时间
+    pub fn get<T: DeserializeOwned>(&self, key: &CacheKey) -> Option<T> {
+        if self.mode == CacheMode::Refresh {
+            return None;
+        }
+
+        let content = match std::fs::read_to_string(self.path(key)) {
+            Ok(content) => content,
+            Err(_) => {
+                debug!("{}", t!("sources.cache_miss", key = key));
+                return None;
+            }
+        };
+
+        let entry = match serde_json::from_str::<CacheEntry<T>>(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!(
+                    "{}",
+                    t!("sources.cache_invalid", key = key, error = e.to_string())
+                );
+                return None;
+            }
+        };
+
+        let age = Self::now().saturating_sub(entry.fetched_at);
+        if self.mode != CacheMode::Offline && age >= self.ttl.as_secs() {
+            debug!("{}", t!("sources.cache_expired", key = key, age = age));
+            return None;
+        }
+
+        debug!("{}", t!("sources.cache_hit", key = key, age = age));
+        Some(entry.data)
+    }
+
+    /// 写入缓存，先写入临时文件再重命名，避免并发读取到不完整的内容
+    pub fn put<T: Serialize>(&self, key: &CacheKey, data: &T) -> Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let entry = CacheEntry {
+            fetched_at: Self::now(),
+            data,
+        };
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp, serde_json::to_vec(&entry)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// 读取缓存，未命中时调用 `fetch` 获取并写入缓存
+    ///
+    /// `Offline` 模式下未命中会返回 [`AppError::NotCached`]
+    pub async fn get_or_fetch<T, F, Fut>(&self, key: &CacheKey, fetch: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if let Some(data) = self.get(key) {
+            return Ok(data);
+        }
+
+        if self.mode == CacheMode::Offline {
+            return Err(AppError::NotCached(key.to_string()).into());
+        }
+
+        let data = fetch().await?;
+        if let Err(e) = self.put(key, &data) {
+            debug!(
+                "{}",
+                t!(
+                    "sources.cache_write_failed",
+                    key = key,
+                    error = e.to_string()
+                )
+            );
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(
+            CacheKey::crate_info("serde", None).to_string(),
+            PathBuf::from("crates-io")
+                .join("serde.json")
+                .display()
+                .to_string()
+        );
+        assert_eq!(
+            CacheKey::crate_info("serde", Some("1.0.0")).0,
+            PathBuf::from("crates-io").join("serde").join("1.0.0.json")
+        );
+        assert_eq!(
+            CacheKey::crate_readme("serde", "1.0.0").0,
+            PathBuf::from("crates-io-readme")
+                .join("serde")
+                .join("1.0.0.json")
+        );
+        assert_eq!(
+            CacheKey::hosted_repository("gitlab.com", "Group/Sub", "repo").0,
+            PathBuf::from("repos")
+                .join("gitlab.com")
+                .join("group_sub")
+                .join("repo.json")
+        );
+        assert_eq!(
+            CacheKey::crate_releases("serde").0,
+            PathBuf::from("crates-io-releases").join("serde.json")
+        );
+        assert_eq!(
+            CacheKey::crate_owners("serde").0,
+            PathBuf::from("crates-io-owners").join("serde.json")
+        );
+        assert_eq!(
+            CacheKey::registry_crate_info("internal", "foo", Some("0.1.0")).0,
+            PathBuf::from("registries")
+                .join("internal")
+                .join("foo")
+                .join("0.1.0.json")
+        );
+        assert_eq!(
+            CacheKey::github_contributors("Serde-RS", "Serde").0,
+            PathBuf::from("github-contributors")
+                .join("serde-rs")
+                .join("serde.json")
+        );
+        assert_eq!(
+            CacheKey::github_repository("Serde-RS", "Serde").0,
+            PathBuf::from("github").join("serde-rs").join("serde.json")
+        );
+        // 不允许路径穿越
+        assert_eq!(
+            CacheKey::github_repository("..", "../etc").0,
+            PathBuf::from("github").join("_").join(".._etc.json")
+        );
+    }
+
+    #[test]
+    fn test_cache_put_get() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Normal);
+        let key = CacheKey::crate_info("serde", None);
+
+        assert_eq!(cache.get::<String>(&key), None);
+        cache.put(&key, &"cached".to_string())?;
+        assert_eq!(cache.get::<String>(&key), Some("cached".to_string()));
+
+        // 刷新模式忽略已有缓存
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Refresh);
+        assert_eq!(cache.get::<String>(&key), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_ttl() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = Cache::new(dir.path(), Duration::ZERO, CacheMode::Normal);
+        let key = CacheKey::github_repository("serde-rs", "serde");
+        cache.put(&key, &42u32)?;
+
+        // 过期的缓存不会被使用
+        assert_eq!(cache.get::<u32>(&key), None);
+
+        // 离线模式下过期的缓存仍然可用
+        let cache = Cache::new(dir.path(), Duration::ZERO, CacheMode::Offline);
+        assert_eq!(cache.get::<u32>(&key), Some(42));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_get_or_fetch() -> Result<()> {
+        let dir = TempDir::new()?;
+        let key = CacheKey::crate_info("tokio", None);
+
+        // 离线且未命中时不会调用 fetch
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Offline);
+        let result = cache
+            .get_or_fetch::<u32, _, _>(&key, || async { panic!("should not fetch") })
+            .await;
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<AppError>(),
+            Some(AppError::NotCached(_))
+        ));
+
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Normal);
+        let data = cache.get_or_fetch(&key, || async { Ok(1u32) }).await?;
+        assert_eq!(data, 1);
+
+        // 命中缓存时不会再次请求
+        let data = cache.get_or_fetch(&key, || async { Ok(2u32) }).await?;
+        assert_eq!(data, 1);
+
+        // 刷新模式总是请求并更新缓存
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Refresh);
+        let data = cache.get_or_fetch(&key, || async { Ok(3u32) }).await?;
+        assert_eq!(data, 3);
+
+        let cache = Cache::new(dir.path(), Cache::DEFAULT_TTL, CacheMode::Offline);
+        let data = cache
+            .get_or_fetch::<u32, _, _>(&key, || async { panic!("should not fetch") })
+            .await?;
+        assert_eq!(data, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use rust_i18n::t;
+use serde::Deserialize;
+
+use super::{RepositoryInfo, RepositoryLicense};
+use crate::{funding::FundingLink, sources::hosts::RepositoryRef};
+
+/// 单次 GraphQL 请求查询的仓库数量，GitHub 限制每次查询的节点数量
+pub const BATCH_SIZE: usize = 100;
+
+/// 与 REST 接口对应的字段，额外获取赞助链接
+const REPOSITORY_FRAGMENT: &str = "fragment repo on Repository { nameWithOwner description stargazerCount url isArchived pushedAt licenseInfo { spdxId } fundingLinks { platform url } }";
+
+/// 为一批仓库生成查询，第 `i` 个仓库使用别名 `r{i}`
+pub fn build_query(repos: &[&RepositoryRef]) -> String {
+    let fields = repos
+        .iter()
+        .enumerate()
+        .map(|(i, repo)| {
+            // JSON 字符串的转义规则与 GraphQL 字符串一致
+            format!(
+                "r{}: repository(owner: {}, name: {}) {{ ...repo }}",
+                i,
+                serde_json::Value::from(repo.owner.as_str()),
+                serde_json::Value::from(repo.repo.as_str())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!("query {{ {} }} {}", fields, REPOSITORY_FRAGMENT)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLRepository {
+    name_with_owner: String,
+    description: Option<String>,
+    stargazer_count: u32,
+    url: String,
+    is_archived: bool,
+    pushed_at: Option<String>,
+    license_info: Option<GraphQLLicense>,
+    #[serde(default)]
+    funding_links: Vec<FundingLink>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQLLicense {
+    spdx_id: Option<String>,
+}
+
+impl From<GraphQLRepository> for RepositoryInfo {
+    fn from(repository: GraphQLRepository) -> Self {
+        Self {
+            full_name: repository.name_with_owner,
+            description: repository.description,
+            stargazers_count: repository.stargazer_count,
+            html_url: repository.url,
+            archived: repository.is_archived,
+            license: repository.license_info.map(|license| RepositoryLicense {
+                spdx_id: license.spdx_id,
+            }),
+            pushed_at: repository.pushed_at,
+            funding_links: repository.funding_links,
+        }
+    }
+}
+
+/// 解析查询结果，按请求的顺序返回，不存在或无权访问的仓库为 `None`
+///
+/// 部分仓库不存在时 GitHub 仍会返回其它仓库的数据，只有整个查询失败时才返回错误
+pub fn parse_response(
+    response: serde_json::Value,
+    count: usize,
+) -> Result<Vec<Option<RepositoryInfo>>> {
+    let data = match &response["data"] {
+        serde_json::Value::Object(data) => data,
+        _ => {
+            let error = response["errors"][0]["message"]
+                .as_str()
+                .or_else(|| response["message"].as_str())
+                .unwrap_or_default();
+            anyhow::bail!(t!("sources.graphql_error", error = error));
+        }
+    };
+
+    (0..count)
+        .map(|i| match data.get(&format!("r{}", i)) {
+            Some(serde_json::Value::Null) | None => Ok(None),
+            Some(repository) => Ok(Some(
+                serde_json::from_value::<GraphQLRepository>(repository.clone())?.into(),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    fn repo(url: &str) -> RepositoryRef {
+        RepositoryRef::from_url(&Url::parse(url).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_build_query() {
+        let serde = repo("https://github.com/serde-rs/serde");
+        let quoted = RepositoryRef {
+            repo: "a\"b".to_string(),
+            ..repo("https://github.com/owner/repo")
+        };
+
+        let query = build_query(&[&serde, &quoted]);
+        assert!(query.starts_with(
+            "query { r0: repository(owner: \"serde-rs\", name: \"serde\") { ...repo } \
+             r1: repository(owner: \"owner\", name: \"a\\\"b\") { ...repo } }"
+        ));
+        assert!(query.ends_with(REPOSITORY_FRAGMENT));
+    }
+
+    #[test]
+    fn test_parse_response() -> Result<()> {
+        let response = serde_json::json!({
+            "data": {
+                "r0": {
+                    "nameWithOwner": "serde-rs/serde",
+                    "description": "Serialization framework for Rust",
+                    "stargazerCount": 9000,
+                    "url": "https://github.com/serde-rs/serde",
+                    "isArchived": false,
+                    "pushedAt": "2025-03-09T12:00:00Z",
+                    "licenseInfo": { "spdxId": "Apache-2.0" },
+                    "fundingLinks": [{ "platform": "GITHUB", "url": "https://github.com/dtolnay" }]
+                },
+                "r1": null
+            },
+            "errors": [{ "type": "NOT_FOUND", "message": "Could not resolve to a Repository" }]
+        });
+
+        let repositories = parse_response(response, 2)?;
+        assert_eq!(repositories.len(), 2);
+        let serde = repositories[0].as_ref().unwrap();
+        assert_eq!(serde.stargazers_count, 9000);
+        assert_eq!(
+            serde
+                .license
+                .as_ref()
+                .and_then(|license| license.spdx_id.as_deref()),
+            Some("Apache-2.0")
+        );
+        assert_eq!(serde.pushed_at.as_deref(), Some("2025-03-09T12:00:00Z"));
+        assert_eq!(serde.funding_links[0].platform, "GITHUB");
+        assert!(repositories[1].is_none());
+
+        let response = serde_json::json!({ "message": "Bad credentials" });
+        let error = parse_response(response, 1).unwrap_err();
+        assert!(error.to_string().contains("Bad credentials"));
+        Ok(())
+    }
+}
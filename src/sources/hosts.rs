@@ -0,0 +1,464 @@
+use anyhow::Result;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::future::Future;
+use std::time::Duration;
+use tracing::instrument;
+use url::Url;
+
+use super::cache::{Cache, CacheKey};
+use super::{RepositoryInfo, build_http_client, get_json_request, rate_limit::RateLimiter};
+
+/// 仓库所在的代码托管平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HostKind {
+    GitHub,
+    GitLab,
+    /// Gitea 及其分支 Forgejo，例如 Codeberg
+    Gitea,
+    Bitbucket,
+}
+
+/// 从仓库地址中解析出的托管平台与仓库路径
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryRef {
+    pub kind: HostKind,
+    /// 小写的主机名，例如 `codeberg.org`
+    pub host: String,
+    /// 仓库所有者，GitLab 中可以是多级群组，例如 `group/subgroup`
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepositoryRef {
+    /// 根据主机名识别托管平台，无法识别时返回 `None`
+    ///
+    /// 除了公共实例外，`gitlab.`、`gitea.`、`forgejo.` 开头的主机名视为自建实例
+    pub fn from_url(url: &Url) -> Option<Self> {
+        let host = url.host_str()?.to_lowercase();
+        let kind = match host.as_str() {
+            "github.com" => HostKind::GitHub,
+            "gitlab.com" => HostKind::GitLab,
+            "bitbucket.org" => HostKind::Bitbucket,
+            "codeberg.org" | "gitea.com" => HostKind::Gitea,
+            host if host.starts_with("gitlab.") => HostKind::GitLab,
+            host if host.starts_with("gitea.") || host.starts_with("forgejo.") => HostKind::Gitea,
+            _ => return None,
+        };
+
+        let path = url.path().trim_matches('/');
+        let (owner, repo) = match kind {
+            // GitLab 的仓库路径位于 `/-/` 之前，例如 `/group/sub/repo/-/tree/main`
+            HostKind::GitLab => path.split("/-/").next()?.rsplit_once('/')?,
+            // 只取前两段路径，例如 `/owner/repo/tree/master/crate` 中的 `owner/repo`
+            _ => {
+                let mut parts = path.split('/');
+                (parts.next()?, parts.next()?)
+            }
+        };
+        let repo = repo.trim_end_matches(".git");
+        if owner.is_empty() || repo.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            host,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// 输出中使用的来源名称
+    pub fn source_type(&self) -> &'static str {
+        match self.kind {
+            HostKind::GitHub => "GitHub",
+            HostKind::GitLab => "GitLab",
+            HostKind::Gitea if self.host == "codeberg.org" => "Codeberg",
+            HostKind::Gitea => "Gitea",
+            HostKind::Bitbucket => "Bitbucket",
+        }
+    }
+}
+
+/// 代码托管平台的仓库信息接口
+pub trait RepositoryHost: Send + Sync {
+    fn get_repository_info(
+        &self,
+        repo: &RepositoryRef,
+    ) -> impl Future<Output = Result<RepositoryInfo>> + Send;
+}
+
+/// 使用令牌时附加认证请求头
+pub(super) fn auth_headers(
+    token: Option<&str>,
+    name: HeaderName,
+    prefix: &str,
+) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = token {
+        headers.insert(
+            name,
+            HeaderValue::from_str(&format!("{}{}", prefix, token))?,
+        );
+    }
+    Ok(headers)
+}
+
+/// 托管平台的令牌，只发送给令牌所属的实例
+///
+/// 仓库地址来自依赖自己声明的 `repository` 字段，不能把令牌发送给任意主机
+#[derive(Clone)]
+struct HostToken {
+    /// 小写的主机名，例如 `gitlab.com`
+    host: String,
+    name: HeaderName,
+    value: HeaderValue,
+}
+
+impl HostToken {
+    fn new(
+        token: Option<&str>,
+        host: &str,
+        name: HeaderName,
+        prefix: &str,
+    ) -> Result<Option<Self>> {
+        token
+            .map(|token| {
+                Ok(Self {
+                    host: host.to_lowercase(),
+                    name: name.clone(),
+                    value: HeaderValue::from_str(&format!("{}{}", prefix, token))?,
+                })
+            })
+            .transpose()
+    }
+
+    /// 仓库位于令牌所属的实例时为请求附加认证请求头
+    fn authorize(
+        token: Option<&Self>,
+        repo: &RepositoryRef,
+        request: RequestBuilder,
+    ) -> RequestBuilder {
+        match token {
+            Some(token) if token.host == repo.host => {
+                request.header(token.name.clone(), token.value.clone())
+            }
+            _ => request,
+        }
+    }
+}
+
+/// 读取缓存，未命中时调用 `fetch`
+async fn cached<F, Fut>(
+    cache: Option<&Cache>,
+    repo: &RepositoryRef,
+    fetch: F,
+) -> Result<RepositoryInfo>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<RepositoryInfo>>,
+{
+    match cache {
+        Some(cache) => {
+            let key = CacheKey::hosted_repository(&repo.host, &repo.owner, &repo.repo);
+            cache.get_or_fetch(&key, fetch).await
+        }
+        None => fetch().await,
+    }
+}
+
+/// GitLab REST API (v4) 客户端，支持 gitlab.com 与自建实例
+pub struct GitLabClient {
+    client: Client,
+    token: Option<HostToken>,
+    base_url: Option<String>,
+    cache: Option<Cache>,
+    pub(super) limiter: RateLimiter,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    path_with_namespace: String,
+    description: Option<String>,
+    star_count: u32,
+    web_url: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl GitLabClient {
+    pub const DEFAULT_HOST: &'static str = "gitlab.com";
+
+    /// 令牌默认属于 gitlab.com，自建实例的令牌需要指定 `host`
+    pub fn new(token: Option<&str>, host: Option<&str>) -> Result<Self> {
+        let host = host.unwrap_or(Self::DEFAULT_HOST);
+        Ok(Self {
+            client: build_http_client(HeaderMap::new())?,
+            token: HostToken::new(token, host, HeaderName::from_static("private-token"), "")?,
+            base_url: None,
+            cache: None,
+            limiter: RateLimiter::new("GitLab", Duration::ZERO),
+        })
+    }
+
+    /// 固定使用的 API 地址，默认为 `https://<host>/api/v4`
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn fetch_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => format!("https://{}/api/v4", repo.host),
+        };
+        // 项目路径作为一个路径段，`/` 需要编码
+        let url = format!(
+            "{}/projects/{}",
+            base_url,
+            repo.full_name().replace('/', "%2F")
+        );
+        let request = HostToken::authorize(self.token.as_ref(), repo, self.client.get(&url));
+        let project: GitLabProject =
+            serde_json::from_value(get_json_request(&self.limiter, &url, request).await?)?;
+
+        Ok(RepositoryInfo {
+            full_name: project.path_with_namespace,
+            description: project.description,
+            stargazers_count: project.star_count,
+            html_url: project.web_url,
+            archived: project.archived,
+            ..Default::default()
+        })
+    }
+}
+
+impl RepositoryHost for GitLabClient {
+    #[instrument(skip(self))]
+    async fn get_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        cached(self.cache.as_ref(), repo, || {
+            self.fetch_repository_info(repo)
+        })
+        .await
+    }
+}
+
+/// Gitea/Forgejo REST API 客户端，例如 Codeberg
+pub struct GiteaClient {
+    client: Client,
+    token: Option<HostToken>,
+    base_url: Option<String>,
+    cache: Option<Cache>,
+    pub(super) limiter: RateLimiter,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaRepository {
+    full_name: String,
+    description: Option<String>,
+    stars_count: u32,
+    html_url: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl GiteaClient {
+    pub const DEFAULT_HOST: &'static str = "codeberg.org";
+
+    /// 令牌默认属于 Codeberg，其他实例的令牌需要指定 `host`
+    pub fn new(token: Option<&str>, host: Option<&str>) -> Result<Self> {
+        let host = host.unwrap_or(Self::DEFAULT_HOST);
+        Ok(Self {
+            client: build_http_client(HeaderMap::new())?,
+            token: HostToken::new(token, host, AUTHORIZATION, "token ")?,
+            base_url: None,
+            cache: None,
+            limiter: RateLimiter::new("Gitea", Duration::ZERO),
+        })
+    }
+
+    /// 固定使用的 API 地址，默认为 `https://<host>/api/v1`
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn fetch_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => format!("https://{}/api/v1", repo.host),
+        };
+        let url = format!("{}/repos/{}/{}", base_url, repo.owner, repo.repo);
+        let request = HostToken::authorize(self.token.as_ref(), repo, self.client.get(&url));
+        let repository: GiteaRepository =
+            serde_json::from_value(get_json_request(&self.limiter, &url, request).await?)?;
+
+        Ok(RepositoryInfo {
+            full_name: repository.full_name,
+            description: repository.description,
+            stargazers_count: repository.stars_count,
+            html_url: repository.html_url,
+            archived: repository.archived,
+            ..Default::default()
+        })
+    }
+}
+
+impl RepositoryHost for GiteaClient {
+    #[instrument(skip(self))]
+    async fn get_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        cached(self.cache.as_ref(), repo, || {
+            self.fetch_repository_info(repo)
+        })
+        .await
+    }
+}
+
+/// Bitbucket Cloud REST API (2.0) 客户端
+///
+/// Bitbucket 没有点赞，使用关注者数量作为统计，也没有归档状态
+pub struct BitbucketClient {
+    client: Client,
+    token: Option<HostToken>,
+    base_url: String,
+    cache: Option<Cache>,
+    pub(super) limiter: RateLimiter,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketRepository {
+    full_name: String,
+    description: Option<String>,
+    links: BitbucketLinks,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketLinks {
+    html: BitbucketLink,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketLink {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPage {
+    size: u32,
+}
+
+impl BitbucketClient {
+    pub const DEFAULT_API_URL: &'static str = "https://api.bitbucket.org/2.0";
+
+    pub fn new(token: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            client: build_http_client(HeaderMap::new())?,
+            token: HostToken::new(token, "bitbucket.org", AUTHORIZATION, "Bearer ")?,
+            base_url: Self::DEFAULT_API_URL.to_string(),
+            cache: None,
+            limiter: RateLimiter::new("Bitbucket", Duration::ZERO),
+        })
+    }
+
+    /// 使用其他的 API 地址，例如本地的模拟服务或 `file://` fixture 目录
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn fetch_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        let url = format!(
+            "{}/repositories/{}/{}",
+            self.base_url, repo.owner, repo.repo
+        );
+        let get = |url: String| async move {
+            let request = HostToken::authorize(self.token.as_ref(), repo, self.client.get(&url));
+            get_json_request(&self.limiter, &url, request).await
+        };
+        let repository: BitbucketRepository = serde_json::from_value(get(url.clone()).await?)?;
+        let watchers: BitbucketPage =
+            serde_json::from_value(get(format!("{}/watchers", url)).await?)?;
+
+        Ok(RepositoryInfo {
+            full_name: repository.full_name,
+            description: repository.description.filter(|d| !d.is_empty()),
+            stargazers_count: watchers.size,
+            html_url: repository.links.html.href,
+            archived: false,
+            ..Default::default()
+        })
+    }
+}
+
+impl RepositoryHost for BitbucketClient {
+    #[instrument(skip(self))]
+    async fn get_repository_info(&self, repo: &RepositoryRef) -> Result<RepositoryInfo> {
+        cached(self.cache.as_ref(), repo, || {
+            self.fetch_repository_info(repo)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Option<RepositoryRef> {
+        RepositoryRef::from_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_repository_ref_from_url() {
+        let repo = parse("https://GitHub.com/serde-rs/serde.git").unwrap();
+        assert_eq!(repo.kind, HostKind::GitHub);
+        assert_eq!(repo.host, "github.com");
+        assert_eq!(repo.full_name(), "serde-rs/serde");
+
+        let repo = parse("https://gitlab.com/group/sub/repo/-/tree/main/crate").unwrap();
+        assert_eq!(repo.kind, HostKind::GitLab);
+        assert_eq!(repo.owner, "group/sub");
+        assert_eq!(repo.repo, "repo");
+
+        let repo = parse("https://codeberg.org/owner/repo/src/branch/main").unwrap();
+        assert_eq!(repo.kind, HostKind::Gitea);
+        assert_eq!(repo.source_type(), "Codeberg");
+        assert_eq!(repo.full_name(), "owner/repo");
+
+        let repo = parse("https://gitea.example.org/owner/repo").unwrap();
+        assert_eq!(repo.source_type(), "Gitea");
+
+        let repo = parse("https://gitlab.gnome.org/GNOME/glib").unwrap();
+        assert_eq!(repo.kind, HostKind::GitLab);
+
+        let repo = parse("https://bitbucket.org/workspace/repo").unwrap();
+        assert_eq!(repo.kind, HostKind::Bitbucket);
+
+        assert_eq!(parse("https://github.com/serde-rs"), None);
+        assert_eq!(parse("https://example.com/owner/repo"), None);
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use rust_i18n::t;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info};
+
+use crate::errors::AppError;
+
+/// 被限流但响应中没有给出等待时间时的暂停时长
+const DEFAULT_PAUSE: Duration = Duration::from_secs(60);
+
+/// 超过该时长的暂停不再等待，直接返回 [`AppError::RateLimited`]，
+/// 例如 GitHub 的额度可能要一个小时后才重置
+pub const MAX_PAUSE: Duration = Duration::from_secs(120);
+
+/// 被限流的请求在暂停结束后最多重新发送的次数
+pub const MAX_RETRIES: u32 = 3;
+
+/// 从响应头中解析出的限流信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitInfo {
+    /// `X-RateLimit-Limit`
+    pub limit: Option<u64>,
+    /// `X-RateLimit-Remaining`
+    pub remaining: Option<u64>,
+    /// 本次请求被限流（HTTP 429，或额度耗尽 / 带有 `Retry-After` 的 HTTP 403）
+    pub limited: bool,
+    /// 之后的请求需要暂停的时长
+    pub pause: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// 解析 `X-RateLimit-*` 与 `Retry-After` 响应头，`now` 用于计算 `X-RateLimit-Reset` 的剩余时间
+    pub fn parse(status: StatusCode, headers: &HeaderMap, now: SystemTime) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        let limit = header("x-ratelimit-limit");
+        let remaining = header("x-ratelimit-remaining");
+        let retry_after = header("retry-after").map(Duration::from_secs);
+        // 重置时间为 Unix 时间戳（秒）
+        let reset = header("x-ratelimit-reset").map(|reset| {
+            let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+            Duration::from_secs(reset).saturating_sub(now)
+        });
+
+        let exhausted = remaining == Some(0);
+        let limited = status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::FORBIDDEN && (exhausted || retry_after.is_some()));
+        let pause = if limited {
+            Some(retry_after.or(reset).unwrap_or(DEFAULT_PAUSE))
+        } else if exhausted {
+            reset
+        } else {
+            None
+        };
+
+        Self {
+            limit,
+            remaining,
+            limited,
+            pause,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    /// 下一个请求最早的发送时间
+    next_request: Instant,
+    paused_until: Option<Instant>,
+    limit: Option<u64>,
+    remaining: Option<u64>,
+}
+
+/// 同一个 API 的所有请求共享的限流器
+///
+/// 请求之前调用 [`Self::acquire`]，收到响应后调用 [`Self::update`]。
+/// 被限流时暂停的是整个 API 而不是单个任务，其它并发的请求也会等待
+#[derive(Debug)]
+pub struct RateLimiter {
+    host: String,
+    /// 两个请求之间的最小间隔，例如 crates.io 要求每秒最多一个请求
+    min_interval: Duration,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn new(host: &str, min_interval: Duration) -> Self {
+        Self {
+            host: host.to_string(),
+            min_interval,
+            state: Mutex::new(State {
+                next_request: Instant::now(),
+                paused_until: None,
+                limit: None,
+                remaining: None,
+            }),
+        }
+    }
+
+    /// 等待到可以发送下一个请求，暂停时间超过 [`MAX_PAUSE`] 时直接返回错误
+    pub async fn acquire(&self) -> Result<()> {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let resume = state.paused_until.filter(|until| *until > now);
+            if let Some(resume) = resume
+                && resume - now > MAX_PAUSE
+            {
+                return Err(self.rate_limited(resume - now).into());
+            }
+
+            let start = state.next_request.max(resume.unwrap_or(now)).max(now);
+            state.next_request = start + self.min_interval;
+            start - now
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// 根据响应更新剩余额度，被限流时暂停之后的请求并返回 [`AppError::RateLimited`]
+    pub fn update(&self, status: StatusCode, headers: &HeaderMap) -> Result<()> {
+        let info = RateLimitInfo::parse(status, headers, SystemTime::now());
+
+        let mut state = self.state.lock().unwrap();
+        if info.remaining.is_some() {
+            state.limit = info.limit.or(state.limit);
+            state.remaining = info.remaining;
+        }
+
+        if let Some(pause) = info.pause {
+            let until = Instant::now() + pause;
+            if state.paused_until.is_none_or(|paused| paused < until) {
+                state.paused_until = Some(until);
+                info!(
+                    "{}",
+                    t!(
+                        "sources.rate_limit_paused",
+                        host = self.host,
+                        seconds = pause.as_secs()
+                    )
+                );
+            }
+            if info.limited {
+                return Err(self.rate_limited(pause).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn rate_limited(&self, pause: Duration) -> AppError {
+        AppError::RateLimited(
+            t!(
+                "sources.rate_limited",
+                host = self.host,
+                seconds = pause.as_secs()
+            )
+            .to_string(),
+        )
+    }
+
+    /// 在详细模式下输出剩余的请求额度，没有收到额度信息时不输出
+    pub fn log_budget(&self) {
+        let state = self.state.lock().unwrap();
+        if let Some(remaining) = state.remaining {
+            debug!(
+                "{}",
+                t!(
+                    "sources.rate_limit_budget",
+                    host = self.host,
+                    remaining = remaining,
+                    limit = state
+                        .limit
+                        .map_or_else(|| "?".to_string(), |limit| limit.to_string())
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_rate_limit_info_parse() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        let info = RateLimitInfo::parse(
+            StatusCode::OK,
+            &headers(&[
+                ("x-ratelimit-limit", "5000"),
+                ("x-ratelimit-remaining", "4999"),
+                ("x-ratelimit-reset", "4600"),
+            ]),
+            now,
+        );
+        assert_eq!(
+            info,
+            RateLimitInfo {
+                limit: Some(5000),
+                remaining: Some(4999),
+                limited: false,
+                pause: None,
+            }
+        );
+
+        // 额度耗尽后暂停到重置时间
+        let info = RateLimitInfo::parse(
+            StatusCode::FORBIDDEN,
+            &headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1030"),
+            ]),
+            now,
+        );
+        assert!(info.limited);
+        assert_eq!(info.pause, Some(Duration::from_secs(30)));
+
+        // Retry-After 优先于重置时间
+        let info = RateLimitInfo::parse(
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("retry-after", "5"), ("x-ratelimit-reset", "1030")]),
+            now,
+        );
+        assert_eq!(info.pause, Some(Duration::from_secs(5)));
+
+        let info = RateLimitInfo::parse(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), now);
+        assert_eq!(info.pause, Some(DEFAULT_PAUSE));
+
+        // 没有限流信息的 403（例如没有权限）不是限流
+        let info = RateLimitInfo::parse(StatusCode::FORBIDDEN, &HeaderMap::new(), now);
+        assert!(!info.limited);
+        assert_eq!(info.pause, None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_pause() -> Result<()> {
+        let limiter = RateLimiter::new("example.com", Duration::ZERO);
+        limiter.acquire().await?;
+        limiter.update(StatusCode::OK, &headers(&[("x-ratelimit-remaining", "10")]))?;
+
+        let error = limiter
+            .update(
+                StatusCode::TOO_MANY_REQUESTS,
+                &headers(&[("retry-after", "3600")]),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::RateLimited(_))
+        ));
+
+        // 暂停时间过长时不等待，直接失败
+        assert!(limiter.acquire().await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_min_interval() -> Result<()> {
+        let limiter = RateLimiter::new("crates.io", Duration::from_millis(50));
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await?;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        Ok(())
+    }
+}
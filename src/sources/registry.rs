@@ -0,0 +1,180 @@
+use anyhow::Result;
+use reqwest::Client;
+use reqwest::header::AUTHORIZATION;
+use rust_i18n::t;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{debug, instrument};
+
+use super::cache::{Cache, CacheKey};
+use super::{
+    CrateInfo, build_http_client, get_json, get_text, hosts::auth_headers, parse_crate_info,
+    rate_limit::RateLimiter,
+};
+use crate::{errors::AppError, registries::Registry};
+
+/// 索引根目录下的 `config.json`
+#[derive(Debug, Default, Deserialize)]
+struct IndexConfig {
+    /// crates.io 兼容 API 的地址，只提供索引的注册表没有该项
+    #[serde(default)]
+    api: Option<String>,
+}
+
+/// 索引文件中的一行，对应 crate 的一个版本
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    name: String,
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// 稀疏索引中 crate 文件的相对路径，与 Cargo 的规则一致
+///
+/// 例如 `a` → `1/a`，`abc` → `3/a/abc`，`serde` → `se/rd/serde`
+fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// 替代注册表客户端
+///
+/// 注册表提供 crates.io 兼容的 API 时使用 API 获取描述与仓库地址，
+/// 否则从稀疏索引中读取版本与撤回状态；git 索引只在有 API 时可用，目前无法读取其 `config.json`
+pub struct RegistryClient {
+    name: String,
+    index_url: String,
+    sparse: bool,
+    client: Client,
+    cache: Option<Cache>,
+    pub(super) limiter: RateLimiter,
+    config: OnceCell<IndexConfig>,
+}
+
+impl RegistryClient {
+    /// 令牌按照 Cargo 的方式直接作为 `Authorization` 请求头发送
+    pub fn new(registry: &Registry) -> Result<Self> {
+        let headers = auth_headers(registry.token.as_deref(), AUTHORIZATION, "")?;
+        Ok(Self {
+            name: registry.name.clone(),
+            index_url: registry.index_url(),
+            sparse: registry.is_sparse(),
+            client: build_http_client(headers)?,
+            cache: None,
+            limiter: RateLimiter::new(&registry.name, Duration::ZERO),
+            config: OnceCell::new(),
+        })
+    }
+
+    pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cargo 配置中的注册表名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取 crate 的信息，指定版本时获取该版本的信息
+    #[instrument(skip(self))]
+    pub async fn get_crate_info(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        match &self.cache {
+            Some(cache) => {
+                let key = CacheKey::registry_crate_info(&self.name, name, version);
+                cache
+                    .get_or_fetch(&key, || self.fetch_crate_info(name, version))
+                    .await
+            }
+            None => self.fetch_crate_info(name, version).await,
+        }
+    }
+
+    async fn fetch_crate_info(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        if !self.sparse {
+            return Err(AppError::UnsupportedRegistry(self.index_url.clone()).into());
+        }
+
+        let config = self
+            .config
+            .get_or_try_init(|| async {
+                let url = format!("{}config.json", self.index_url);
+                let config = get_json(&self.client, &self.limiter, &url).await?;
+                Ok::<_, anyhow::Error>(serde_json::from_value::<IndexConfig>(config)?)
+            })
+            .await?;
+
+        if let Some(api) = &config.api {
+            let api = api.trim_end_matches('/');
+            let url = match version {
+                Some(version) => format!("{}/api/v1/crates/{}/{}", api, name, version),
+                None => format!("{}/api/v1/crates/{}", api, name),
+            };
+            // 部分注册表只实现了发布所需的 API，查询失败时回退到索引
+            match get_json(&self.client, &self.limiter, &url).await {
+                Ok(data) => return parse_crate_info(data, version.is_some()),
+                Err(e) => debug!(
+                    "{}",
+                    t!(
+                        "sources.registry_api_fallback",
+                        registry = self.name,
+                        error = e.to_string()
+                    )
+                ),
+            }
+        }
+
+        self.fetch_index_entry(name, version).await
+    }
+
+    /// 从稀疏索引中读取版本信息，没有指定版本时使用最后一个未撤回的版本
+    async fn fetch_index_entry(&self, name: &str, version: Option<&str>) -> Result<CrateInfo> {
+        let url = format!("{}{}", self.index_url, index_path(name));
+        let content = get_text(&self.client, &self.limiter, &url)
+            .await?
+            .ok_or_else(|| AppError::NotFound(url.clone()))?;
+
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str::<IndexEntry>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let entry = match version {
+            Some(version) => entries.into_iter().find(|entry| entry.vers == version),
+            None => entries.into_iter().rev().find(|entry| !entry.yanked),
+        }
+        .ok_or_else(|| AppError::NotFound(url.clone()))?;
+
+        Ok(CrateInfo {
+            name: entry.name,
+            description: None,
+            repository: None,
+            homepage: None,
+            documentation: None,
+            downloads: 0,
+            version: Some(entry.vers),
+            yanked: entry.yanked,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_path() {
+        assert_eq!(index_path("a"), "1/a");
+        assert_eq!(index_path("ab"), "2/ab");
+        assert_eq!(index_path("abc"), "3/a/abc");
+        assert_eq!(index_path("Serde"), "se/rd/serde");
+        assert_eq!(index_path("cargo-thanku"), "ca/rg/cargo-thanku");
+    }
+}
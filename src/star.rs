@@ -0,0 +1,300 @@
+use anyhow::Result;
+use rust_i18n::t;
+use std::collections::BTreeMap;
+use tracing::{info, instrument, warn};
+use url::Url;
+
+use crate::{
+    output::DependencyInfo,
+    sources::{GitHubClient, Source},
+};
+
+/// 点赞所需的 GitHub 接口，便于在测试中替换
+pub trait Stargazer {
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool>;
+    async fn star_repository(&self, owner: &str, repo: &str) -> Result<()>;
+}
+
+impl Stargazer for GitHubClient {
+    async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        GitHubClient::is_starred(self, owner, repo).await
+    }
+
+    async fn star_repository(&self, owner: &str, repo: &str) -> Result<()> {
+        GitHubClient::star_repository(self, owner, repo).await
+    }
+}
+
+/// 一个待点赞的 GitHub 仓库，以及使用该仓库的 crates
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarTarget {
+    pub owner: String,
+    pub repo: String,
+    pub crates: Vec<String>,
+}
+
+impl StarTarget {
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+/// 点赞结果，仓库以 `owner/repo` 表示
+#[derive(Debug, Default)]
+pub struct StarReport {
+    pub newly_starred: Vec<String>,
+    pub already_starred: Vec<String>,
+    pub would_star: Vec<String>,
+    pub excluded: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+impl StarReport {
+    pub fn log(&self) {
+        for repo in &self.newly_starred {
+            info!("{}", t!("star.newly_starred", repo = repo));
+        }
+        for repo in &self.already_starred {
+            info!("{}", t!("star.already_starred", repo = repo));
+        }
+        for repo in &self.would_star {
+            info!("{}", t!("star.would_star", repo = repo));
+        }
+        for repo in &self.excluded {
+            info!("{}", t!("star.excluded", repo = repo));
+        }
+        for (repo, error) in &self.failed {
+            warn!("{}", t!("star.failed", repo = repo, error = error));
+        }
+
+        info!(
+            "{}",
+            t!(
+                "star.summary",
+                newly = self.newly_starred.len(),
+                already = self.already_starred.len(),
+                would = self.would_star.len(),
+                excluded = self.excluded.len(),
+                failed = self.failed.len()
+            )
+        );
+    }
+}
+
+/// 从依赖项中收集 GitHub 仓库，多个 crates 共用的仓库只保留一次
+///
+/// 排除列表可以是 crate 名称或 `owner/repo`（不区分大小写）：
+/// - 仓库名匹配时整个仓库被排除
+/// - 仓库下所有 crates 都被排除时，仓库也被排除
+///
+/// 返回 `(待点赞的仓库, 被排除的仓库)`
+pub fn collect_targets(
+    deps: &[DependencyInfo],
+    exclude: &[String],
+) -> (Vec<StarTarget>, Vec<String>) {
+    let is_excluded = |name: &str| exclude.iter().any(|e| e.eq_ignore_ascii_case(name));
+
+    let mut repos: BTreeMap<String, (StarTarget, bool)> = BTreeMap::new();
+    for dep in deps {
+        let url = dep
+            .source_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok());
+        let Some(Source::GitHub { owner, repo, .. }) = Source::from_url(&url) else {
+            continue;
+        };
+
+        let key = format!("{}/{}", owner, repo).to_lowercase();
+        let (target, all_excluded) = repos.entry(key).or_insert_with(|| {
+            (
+                StarTarget {
+                    owner,
+                    repo,
+                    crates: vec![],
+                },
+                true,
+            )
+        });
+        target.crates.push(dep.name.clone());
+        *all_excluded &= is_excluded(&dep.name);
+    }
+
+    let mut targets = vec![];
+    let mut excluded = vec![];
+    for (_, (target, all_excluded)) in repos {
+        if all_excluded || is_excluded(&target.full_name()) {
+            excluded.push(target.full_name());
+        } else {
+            targets.push(target);
+        }
+    }
+
+    (targets, excluded)
+}
+
+/// 为依赖所在的 GitHub 仓库点赞
+///
+/// `dry_run` 时只检查点赞状态（没有客户端时跳过检查），不会真正点赞
+#[instrument(skip_all)]
+pub async fn star_repositories<C: Stargazer>(
+    client: Option<&C>,
+    targets: &[StarTarget],
+    dry_run: bool,
+) -> StarReport {
+    let mut report = StarReport::default();
+
+    for target in targets {
+        let full_name = target.full_name();
+
+        let Some(client) = client else {
+            report.would_star.push(full_name);
+            continue;
+        };
+
+        match client.is_starred(&target.owner, &target.repo).await {
+            Ok(true) => report.already_starred.push(full_name),
+            Ok(false) if dry_run => report.would_star.push(full_name),
+            Ok(false) => match client.star_repository(&target.owner, &target.repo).await {
+                Ok(()) => report.newly_starred.push(full_name),
+                Err(e) => report.failed.push((full_name, e.to_string())),
+            },
+            Err(e) => report.failed.push((full_name, e.to_string())),
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// 记录点赞操作的假客户端
+    struct FakeStargazer {
+        starred: Mutex<HashSet<String>>,
+    }
+
+    impl FakeStargazer {
+        fn new(starred: &[&str]) -> Self {
+            Self {
+                starred: Mutex::new(starred.iter().map(|s| s.to_string()).collect()),
+            }
+        }
+    }
+
+    impl Stargazer for FakeStargazer {
+        async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+            Ok(self
+                .starred
+                .lock()
+                .unwrap()
+                .contains(&format!("{}/{}", owner, repo)))
+        }
+
+        async fn star_repository(&self, owner: &str, repo: &str) -> Result<()> {
+            self.starred
+                .lock()
+                .unwrap()
+                .insert(format!("{}/{}", owner, repo));
+            Ok(())
+        }
+    }
+
+    fn dep(name: &str, source_url: &str) -> DependencyInfo {
+        DependencyInfo {
+            name: name.to_string(),
+            source_type: "GitHub".to_string(),
+            source_url: Some(source_url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn deps() -> Vec<DependencyInfo> {
+        vec![
+            dep("serde", "https://github.com/serde-rs/serde"),
+            dep("serde_derive", "https://github.com/serde-rs/serde"),
+            dep("tokio", "https://github.com/tokio-rs/tokio"),
+            dep(
+                "tokio-util",
+                "https://github.com/tokio-rs/tokio/tree/master/tokio-util",
+            ),
+            dep("url", "https://github.com/servo/rust-url.git"),
+            dep("home", "https://example.com/home"),
+        ]
+    }
+
+    #[test]
+    fn test_collect_targets() {
+        let (targets, excluded) = collect_targets(&deps(), &[]);
+        assert!(excluded.is_empty());
+        assert_eq!(
+            targets
+                .iter()
+                .map(StarTarget::full_name)
+                .collect::<Vec<_>>(),
+            vec!["serde-rs/serde", "servo/rust-url", "tokio-rs/tokio"]
+        );
+        assert_eq!(targets[0].crates, vec!["serde", "serde_derive"]);
+        assert_eq!(targets[2].crates, vec!["tokio", "tokio-util"]);
+    }
+
+    #[test]
+    fn test_collect_targets_exclude() {
+        // 只排除 serde 时，serde_derive 仍然使用同一个仓库
+        let (targets, excluded) = collect_targets(&deps(), &["serde".to_string()]);
+        assert_eq!(targets.len(), 3);
+        assert!(excluded.is_empty());
+
+        let exclude = vec![
+            "serde".to_string(),
+            "serde_derive".to_string(),
+            "Tokio-RS/Tokio".to_string(),
+        ];
+        let (targets, excluded) = collect_targets(&deps(), &exclude);
+        assert_eq!(
+            targets
+                .iter()
+                .map(StarTarget::full_name)
+                .collect::<Vec<_>>(),
+            vec!["servo/rust-url"]
+        );
+        assert_eq!(excluded, vec!["serde-rs/serde", "tokio-rs/tokio"]);
+    }
+
+    #[tokio::test]
+    async fn test_star_repositories() {
+        let client = FakeStargazer::new(&["tokio-rs/tokio"]);
+        let (targets, _) = collect_targets(&deps(), &[]);
+
+        let report = star_repositories(Some(&client), &targets, false).await;
+        assert_eq!(
+            report.newly_starred,
+            vec!["serde-rs/serde", "servo/rust-url"]
+        );
+        assert_eq!(report.already_starred, vec!["tokio-rs/tokio"]);
+        assert!(report.would_star.is_empty());
+
+        // 再次运行时全部都已点赞
+        let report = star_repositories(Some(&client), &targets, false).await;
+        assert!(report.newly_starred.is_empty());
+        assert_eq!(report.already_starred.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_star_repositories_dry_run() {
+        let client = FakeStargazer::new(&["tokio-rs/tokio"]);
+        let (targets, _) = collect_targets(&deps(), &[]);
+
+        let report = star_repositories(Some(&client), &targets, true).await;
+        assert_eq!(report.would_star, vec!["serde-rs/serde", "servo/rust-url"]);
+        assert_eq!(report.already_starred, vec!["tokio-rs/tokio"]);
+        assert!(report.newly_starred.is_empty());
+        assert_eq!(client.starred.lock().unwrap().len(), 1);
+
+        // 没有令牌时列出所有仓库
+        let report = star_repositories::<FakeStargazer>(None, &targets, true).await;
+        assert_eq!(report.would_star.len(), 3);
+    }
+}
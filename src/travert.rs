@@ -33,7 +33,7 @@ impl Travert {
         }
 
         let file_name = file_name.unwrap().to_string_lossy().to_string();
-        let format_identifier = file_name.split(['_']).last();
+        let format_identifier = file_name.split(['_']).next_back();
 
         match format_identifier {
             Some("md") => Ok(OutputFormat::MarkdownTable),
@@ -56,22 +56,19 @@ impl Travert {
 
         // find the first match
         // then split it into groups
-        if let Some(captures) = table_re.captures(content) {
-            if captures.len() >= 3 {
-                if let (Some(header_match), Some(separator_match)) =
-                    (captures.get(1), captures.get(2))
-                {
-                    let header_line = header_match.as_str().trim();
-                    let separator_line = separator_match.as_str().trim();
+        if let Some(captures) = table_re.captures(content)
+            && captures.len() >= 3
+            && let (Some(header_match), Some(separator_match)) = (captures.get(1), captures.get(2))
+        {
+            let header_line = header_match.as_str().trim();
+            let separator_line = separator_match.as_str().trim();
 
-                    let header_parts: Vec<&str> = header_line.split('|').collect();
+            let header_parts: Vec<&str> = header_line.split('|').collect();
 
-                    let separator_parts: Vec<&str> = separator_line.split('|').collect();
+            let separator_parts: Vec<&str> = separator_line.split('|').collect();
 
-                    if header_parts.len() > 0 && header_parts.len() == separator_parts.len() {
-                        return Ok(OutputFormat::MarkdownTable);
-                    }
-                }
+            if !header_parts.is_empty() && header_parts.len() == separator_parts.len() {
+                return Ok(OutputFormat::MarkdownTable);
             }
         }
 
@@ -85,7 +82,7 @@ impl Travert {
         let extension = path.extension().unwrap_or_default().to_ascii_lowercase();
         // println!("extension:  {}", &extension.clone().into_string().unwrap());
 
-        return match extension.to_str() {
+        match extension.to_str() {
             Some("md") => {
                 if file_exists {
                     let content = std::fs::read_to_string(path)?;
@@ -100,7 +97,7 @@ impl Travert {
             Some("yaml") => Ok(OutputFormat::Yaml),
             Some("json") => Ok(OutputFormat::Json),
             _ => anyhow::bail!(t!("travert.failed_to_judge_format", path = path.display())),
-        };
+        }
 
         // anyhow::bail!(t!("travert.failed_to_judge_format", path = path.display()))
     }
@@ -110,6 +107,7 @@ impl Travert {
 pub struct Converter {
     pub source: Travert,
     pub targets: Vec<Travert>,
+    pub options: output::FormatOptions,
 }
 
 impl Converter {
@@ -120,6 +118,7 @@ impl Converter {
                 .into_iter()
                 .map(|p| Travert::new(p))
                 .collect::<Result<Vec<_>>>()?,
+            options: output::FormatOptions::default(),
         })
     }
 
@@ -128,6 +127,7 @@ impl Converter {
         Ok(Self {
             source,
             targets: target,
+            options: output::FormatOptions::default(),
         })
     }
 
@@ -143,7 +143,11 @@ impl Converter {
             .iter()
             .map(|target| {
                 let mut buffer = Vec::new();
-                let mut manager = output::OutputManager::new(target.format, &mut buffer);
+                let mut manager = output::OutputManager::new_with_options(
+                    target.format,
+                    &mut buffer,
+                    self.options.clone(),
+                );
                 manager.write(&dependencies_info)?;
                 Ok((&target.path, buffer))
             })
@@ -169,7 +173,7 @@ mod tests {
         | 名称 | 描述 |\n\
         |---|---|\n\
         | hello | world |";
-        let format = Travert::detect_markdown_content_format(&content)?;
+        let format = Travert::detect_markdown_content_format(content)?;
         assert_eq!(format, OutputFormat::MarkdownTable);
         Ok(())
     }
@@ -182,7 +186,7 @@ mod tests {
     |:-----|:------:|------:|\n\
     | data | data   | data  |";
         assert_eq!(
-            Travert::detect_markdown_content_format(&content1)?,
+            Travert::detect_markdown_content_format(content1)?,
             OutputFormat::MarkdownTable
         );
 
@@ -192,7 +196,7 @@ mod tests {
     |------------|----------|\n\
     | some value | another  |";
         assert_eq!(
-            Travert::detect_markdown_content_format(&content2)?,
+            Travert::detect_markdown_content_format(content2)?,
             OutputFormat::MarkdownTable
         );
 
@@ -201,7 +205,7 @@ mod tests {
     - Item 1\n\
     - Item 2";
         assert_eq!(
-            Travert::detect_markdown_content_format(&content3)?,
+            Travert::detect_markdown_content_format(content3)?,
             OutputFormat::MarkdownList
         );
 
@@ -214,7 +218,7 @@ mod tests {
         * hello
         * world
         ";
-        let format = Travert::detect_markdown_content_format(&content)?;
+        let format = Travert::detect_markdown_content_format(content)?;
         assert_eq!(format, OutputFormat::MarkdownList);
         Ok(())
     }
@@ -225,7 +229,7 @@ mod tests {
         - |:---:|:---:|
         - | hello | world |
         ";
-        let format = Travert::detect_markdown_content_format(&content)?;
+        let format = Travert::detect_markdown_content_format(content)?;
         assert_eq!(format, OutputFormat::MarkdownList);
         Ok(())
     }